@@ -0,0 +1,235 @@
+//! Rust types from `aln particle` definitions.
+//!
+//! Each particle becomes a serde struct whose fields follow the ALN field
+//! types, with one enum per `enum` field and doc comments taken from the
+//! `;` annotations and `invariant` statements. Call [`Builder::compile`]
+//! from a build script and `include!` the result, so the Rust types are
+//! regenerated whenever the shard changes:
+//!
+//! ```no_run
+//! // build.rs
+//! aln_codegen::Builder::new()
+//!     .particle_as("../../specs/augmented.citizen.rights.kernel.v1.aln", "RightsKernelSnapshot")
+//!     .compile("aln_particles.rs")
+//!     .expect("ALN particle codegen");
+//! ```
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use aln_parser::{AlnDocument, FieldType, ParticleDocument};
+
+#[derive(Debug, thiserror::Error)]
+pub enum CodegenError {
+    #[error("cannot read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("{path} does not parse:\n{}", diagnostics.join("\n"))]
+    Parse { path: PathBuf, diagnostics: Vec<String> },
+
+    #[error("{path} is not an `aln particle` shard")]
+    NotParticle { path: PathBuf },
+
+    #[error("`{0}` is generated twice")]
+    DuplicateType(String),
+
+    #[error("`{0}` is not a valid Rust type name")]
+    InvalidTypeName(String),
+
+    #[error("OUT_DIR is not set; `compile` is meant to run from a build script")]
+    MissingOutDir,
+}
+
+/// Particles to generate, in order, into one file.
+#[derive(Debug, Default)]
+pub struct Builder {
+    particles: Vec<(PathBuf, Option<String>)>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generate the particle at `path` under its default name, see [`type_name_for`].
+    pub fn particle(mut self, path: impl Into<PathBuf>) -> Self {
+        self.particles.push((path.into(), None));
+        self
+    }
+
+    /// Generate the particle at `path` as `type_name`.
+    pub fn particle_as(mut self, path: impl Into<PathBuf>, type_name: &str) -> Self {
+        self.particles.push((path.into(), Some(type_name.to_string())));
+        self
+    }
+
+    /// Source text for every registered particle.
+    pub fn generate(&self) -> Result<String, CodegenError> {
+        let mut out = String::new();
+        let mut names = Vec::new();
+        for (path, type_name) in &self.particles {
+            let source = fs::read_to_string(path).map_err(|source| CodegenError::Io { path: path.clone(), source })?;
+            let doc = particle(path, &source)?;
+            let name = type_name.clone().unwrap_or_else(|| type_name_for(&doc.name.value));
+            if names.contains(&name) {
+                return Err(CodegenError::DuplicateType(name));
+            }
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&generate_particle(&doc, &name, path)?);
+            names.push(name);
+        }
+        Ok(out)
+    }
+
+    /// Write the generated source to `$OUT_DIR/file_name` and ask Cargo to
+    /// rerun the build script when any particle changes.
+    pub fn compile(&self, file_name: &str) -> Result<PathBuf, CodegenError> {
+        for (path, _) in &self.particles {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+        let out_dir = env::var_os("OUT_DIR").ok_or(CodegenError::MissingOutDir)?;
+        let dest = Path::new(&out_dir).join(file_name);
+        let source = self.generate()?;
+        fs::write(&dest, source).map_err(|source| CodegenError::Io { path: dest.clone(), source })?;
+        Ok(dest)
+    }
+}
+
+/// `augmented.citizen.rights.kernel.v1` → `AugmentedCitizenRightsKernel`;
+/// a trailing `vN` segment is dropped.
+pub fn type_name_for(particle: &str) -> String {
+    let mut segments: Vec<&str> = particle.split('.').collect();
+    if segments.len() > 1 && segments.last().is_some_and(|s| is_version(s)) {
+        segments.pop();
+    }
+    segments.iter().map(|s| pascal_case(s)).collect()
+}
+
+/// Source for one particle: the struct, then one enum per `enum` field.
+pub fn generate_particle(doc: &ParticleDocument, type_name: &str, origin: &Path) -> Result<String, CodegenError> {
+    if !is_type_name(type_name) {
+        return Err(CodegenError::InvalidTypeName(type_name.to_string()));
+    }
+    let mut out = String::new();
+    let mut enums = String::new();
+
+    writeln!(out, "// @generated by aln-codegen from {}. Do not edit.", origin.display()).unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "/// ALN particle `{}`.", doc.name.value).unwrap();
+    for invariant in &doc.invariants {
+        writeln!(out, "///").unwrap();
+        writeln!(out, "/// Invariant: {}", invariant.value).unwrap();
+    }
+    writeln!(out, "#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]").unwrap();
+    writeln!(out, "pub struct {type_name} {{").unwrap();
+    for field in doc.fields() {
+        if let Some(comment) = &field.comment {
+            writeln!(out, "    /// {}", one_line(&comment.value)).unwrap();
+        }
+        let enum_name = format!("{type_name}{}", pascal_case(&field.name.value));
+        let rust_type = rust_type(&field.ty.value, &enum_name);
+        writeln!(out, "    pub {}: {rust_type},", field_ident(&field.name.value)).unwrap();
+        if let Some(variants) = enum_variants(&field.ty.value) {
+            enums.push('\n');
+            write_enum(&mut enums, &enum_name, &doc.name.value, &field.name.value, variants);
+        }
+    }
+    writeln!(out, "}}").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "impl {type_name} {{").unwrap();
+    writeln!(out, "    pub const PARTICLE: &'static str = {:?};", doc.name.value).unwrap();
+    writeln!(out, "}}").unwrap();
+    out.push_str(&enums);
+    Ok(out)
+}
+
+fn particle(path: &Path, source: &str) -> Result<ParticleDocument, CodegenError> {
+    let parsed = aln_parser::parse(source);
+    if parsed.has_errors() {
+        let file = path.display().to_string();
+        let diagnostics = parsed.diagnostics.iter().filter(|d| d.is_error()).map(|d| d.render(&file)).collect();
+        return Err(CodegenError::Parse { path: path.to_path_buf(), diagnostics });
+    }
+    match parsed.document {
+        Some(AlnDocument::Particle(doc)) => Ok(doc),
+        _ => Err(CodegenError::NotParticle { path: path.to_path_buf() }),
+    }
+}
+
+fn write_enum(out: &mut String, name: &str, particle: &str, field: &str, variants: &[aln_parser::Spanned<String>]) {
+    writeln!(out, "/// Values of `{particle}` field `{field}`.").unwrap();
+    writeln!(out, "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]").unwrap();
+    writeln!(out, "pub enum {name} {{").unwrap();
+    for variant in variants {
+        writeln!(out, "    #[serde(rename = {:?})]", variant.value).unwrap();
+        writeln!(out, "    {},", pascal_case(&variant.value)).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+}
+
+fn rust_type(ty: &FieldType, enum_name: &str) -> String {
+    match ty {
+        FieldType::String => "String".into(),
+        FieldType::Bool => "bool".into(),
+        FieldType::Float | FieldType::F64 => "f64".into(),
+        FieldType::F32 => "f32".into(),
+        FieldType::U32 => "u32".into(),
+        FieldType::U64 => "u64".into(),
+        FieldType::I64 => "i64".into(),
+        FieldType::Enum(_) => enum_name.into(),
+        FieldType::List(inner) => format!("Vec<{}>", rust_type(inner, enum_name)),
+    }
+}
+
+fn enum_variants(ty: &FieldType) -> Option<&[aln_parser::Spanned<String>]> {
+    match ty {
+        FieldType::Enum(variants) => Some(variants),
+        FieldType::List(inner) => enum_variants(inner),
+        _ => None,
+    }
+}
+
+const KEYWORDS: [&str; 38] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self",
+    "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while", "gen",
+];
+
+fn field_ident(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("r#{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+fn pascal_case(s: &str) -> String {
+    s.split(['_', '-'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase()).into_iter();
+            first.chain(chars).collect::<String>()
+        })
+        .collect()
+}
+
+fn is_version(segment: &str) -> bool {
+    segment.len() > 1 && segment.starts_with('v') && segment[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Annotations may wrap across lines; doc comments keep them on one.
+fn one_line(comment: &str) -> String {
+    comment.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,109 @@
+// @generated by aln-codegen from ../../specs/augmented.citizen.rights.kernel.v1.aln. Do not edit.
+
+/// ALN particle `augmented.citizen.rights.kernel.v1`.
+///
+/// Invariant: Once granted, neurorights_*_min SHALL NOT be regressed or revoked by policy, vendor, regulator, or third‑party actor.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RightsKernelSnapshot {
+    pub id: String,
+    /// e.g. "v1"
+    pub version: String,
+    /// governance / host council
+    pub issuer_did: String,
+    pub cognitive_liberty_min: bool,
+    pub mental_privacy_min: bool,
+    pub mental_integrity_min: bool,
+    pub psychological_continuity_min: bool,
+    pub fair_access_min: bool,
+    pub no_forced_deaugmentation: bool,
+    pub no_policy_only_rollback: bool,
+    pub host_initiated_only_downgrade: bool,
+    /// veto new steps, never kill‑switch
+    pub cybostate_veto_one_way: bool,
+    /// precedence in lattice
+    pub rights_kernel_rank: u32,
+    /// -> cybostate.factor.citizen.v1
+    pub cybostate_factor_ref: String,
+    /// -> bio.safety.envelope.citizen.v1
+    pub bio_safety_envelope_ref: String,
+}
+
+impl RightsKernelSnapshot {
+    pub const PARTICLE: &'static str = "augmented.citizen.rights.kernel.v1";
+}
+
+// @generated by aln-codegen from ../../specs/off_world_jurisdiction.artemis_accords.v1.aln. Do not edit.
+
+/// ALN particle `off_world_jurisdiction.artemis_accords.v1`.
+///
+/// Invariant: Off‑world jurisdiction MAY constrain operations (safety zones, due regard), but SHALL NOT disable host veto power, SHALL NOT regress neurorights, and SHALL NOT raise bio limits above nanoswarm.compliance.field.v1 thresholds.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct JurisdictionContext {
+    pub id: String,
+    pub version: String,
+    /// e.g. "cyb.ai/orbital-governance"
+    pub issuer: String,
+    /// link/hash to Artemis Accords digest
+    pub reference_text: String,
+    pub safety_zone_radius_m: f64,
+    pub due_regard_min_distance_m: f64,
+    pub collision_risk_ceiling: f64,
+    /// augmented.citizen.rights.kernel.v1
+    pub must_respect_rights_kernel_ref: String,
+    /// cybostate.factor.citizen.v1
+    pub must_respect_cybostate_ref: String,
+    /// nanoswarm.compliance.field.v1
+    pub must_respect_nanoswarm_field: String,
+    pub forbid_disable_host_veto: bool,
+    pub forbid_rights_regression: bool,
+}
+
+impl JurisdictionContext {
+    pub const PARTICLE: &'static str = "off_world_jurisdiction.artemis_accords.v1";
+}
+
+// @generated by aln-codegen from ../../specs/space_duty_mode_profile.v1.aln. Do not edit.
+
+/// ALN particle `space_duty_mode_profile.v1`.
+///
+/// Invariant: Mode switches MAY tighten biophysical envelopes and duty-cycle ceilings, but SHALL NOT weaken neurorights floors defined in augmented.citizen.rights.kernel.v1 nor disable cybostate veto or rollback observability.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SpaceDutyModeProfile {
+    pub id: String,
+    pub host_did: String,
+    pub mission_id: String,
+    pub version: String,
+    /// augmented.citizen.profile.v1
+    pub base_profile_ref: String,
+    pub mode: SpaceDutyModeProfileMode,
+    /// e.g. ["off_world_jurisdiction.artemis_accords.v1", "policy.jurisdiction.lattice.v2"]
+    pub jurisdiction_stack: Vec<String>,
+    pub radiation_budget_gy_per_day: f64,
+    /// 0..1
+    pub duty_cycle_max: f64,
+    /// work fraction
+    pub microgravity_countermeasure_max: f64,
+    /// normalized 0..1
+    pub psych_density_max: f64,
+    pub preserve_rights_kernel: bool,
+    pub allow_tighter_safety_only: bool,
+}
+
+impl SpaceDutyModeProfile {
+    pub const PARTICLE: &'static str = "space_duty_mode_profile.v1";
+}
+
+/// Values of `space_duty_mode_profile.v1` field `mode`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum SpaceDutyModeProfileMode {
+    #[serde(rename = "transit")]
+    Transit,
+    #[serde(rename = "surface_ops")]
+    SurfaceOps,
+    #[serde(rename = "eva")]
+    Eva,
+    #[serde(rename = "rehab")]
+    Rehab,
+    #[serde(rename = "sleep_cycle")]
+    SleepCycle,
+}
@@ -0,0 +1,60 @@
+//! Generates the repository's `specs/*.aln` particles and compares them with
+//! `tests/generated/particles.rs`, which is also compiled here so the output
+//! is known to build. `UPDATE_GOLDEN=1` rewrites it.
+
+use std::fs;
+
+use aln_codegen::Builder;
+
+// Not every generated type is exercised below.
+#[allow(dead_code)]
+#[path = "generated/particles.rs"]
+mod particles;
+
+const GOLDEN: &str = "tests/generated/particles.rs";
+
+fn builder() -> Builder {
+    Builder::new()
+        .particle_as("../../specs/augmented.citizen.rights.kernel.v1.aln", "RightsKernelSnapshot")
+        .particle_as("../../specs/off_world_jurisdiction.artemis_accords.v1.aln", "JurisdictionContext")
+        .particle("../../specs/space_duty_mode_profile.v1.aln")
+}
+
+#[test]
+fn generated_specs_match_golden() {
+    let generated = builder().generate().unwrap();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(GOLDEN, &generated).unwrap();
+    }
+    assert_eq!(fs::read_to_string(GOLDEN).unwrap(), generated, "rerun with UPDATE_GOLDEN=1");
+}
+
+#[test]
+fn generated_types_round_trip_through_serde() {
+    use particles::{SpaceDutyModeProfile, SpaceDutyModeProfileMode};
+
+    let json = serde_json::json!({
+        "id": "sdm-1",
+        "host_did": "did:example:host",
+        "mission_id": "artemis-3",
+        "version": "v1",
+        "base_profile_ref": "augmented.citizen.profile.v1",
+        "mode": "surface_ops",
+        "jurisdiction_stack": ["off_world_jurisdiction.artemis_accords.v1"],
+        "radiation_budget_gy_per_day": 0.002,
+        "duty_cycle_max": 0.6,
+        "microgravity_countermeasure_max": 0.2,
+        "psych_density_max": 0.5,
+        "preserve_rights_kernel": true,
+        "allow_tighter_safety_only": true
+    });
+    let profile: SpaceDutyModeProfile = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(profile.mode, SpaceDutyModeProfileMode::SurfaceOps);
+    assert_eq!(serde_json::to_value(&profile).unwrap(), json);
+    assert_eq!(SpaceDutyModeProfile::PARTICLE, "space_duty_mode_profile.v1");
+    assert_eq!(particles::RightsKernelSnapshot::PARTICLE, "augmented.citizen.rights.kernel.v1");
+
+    let mut unknown = json;
+    unknown["mode"] = "warp".into();
+    assert!(serde_json::from_value::<SpaceDutyModeProfile>(unknown).is_err());
+}
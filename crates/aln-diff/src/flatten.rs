@@ -0,0 +1,197 @@
+//! Every shard syntax flattened to `path → leaf`, so versions can be
+//! compared key by key regardless of layout.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use aln_parser::{AlnDocument, CsvDocument, FieldType, ScalarValue, YamlEntry, YamlNode};
+
+use crate::Direction;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Leaf {
+    Number(f64),
+    Bool(bool),
+    Text(String),
+    /// A list of scalars, compared as a set.
+    Set(BTreeSet<String>),
+}
+
+impl std::fmt::Display for Leaf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Leaf::Number(n) => write!(f, "{n}"),
+            Leaf::Bool(b) => write!(f, "{b}"),
+            Leaf::Text(t) => write!(f, "{t:?}"),
+            Leaf::Set(items) => write!(f, "[{}]", items.iter().cloned().collect::<Vec<_>>().join(", ")),
+        }
+    }
+}
+
+/// A leaf plus where it was defined and any direction the shard itself
+/// declares (a metric's `op`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub leaf: Leaf,
+    pub line: u32,
+    pub declared: Option<Direction>,
+}
+
+pub type Flat = BTreeMap<String, Entry>;
+
+pub fn flatten(doc: &AlnDocument) -> Flat {
+    let mut out = Flat::new();
+    match doc {
+        AlnDocument::Yaml(doc) => yaml(&doc.root, "", &mut out),
+        AlnDocument::Particle(doc) => {
+            for section in &doc.sections {
+                for meta in &section.meta {
+                    put(&mut out, format!("meta.{}", meta.key.value), Leaf::Text(meta.value.value.clone()), meta.key.span.line);
+                }
+            }
+            for field in doc.fields() {
+                put(&mut out, format!("fields.{}", field.name.value), Leaf::Text(type_text(&field.ty.value)), field.name.span.line);
+            }
+            let invariants = doc.invariants.iter().map(|i| i.value.clone()).collect();
+            let line = doc.invariants.first().map_or(doc.name.span.line, |i| i.span.line);
+            put(&mut out, "invariants".into(), Leaf::Set(invariants), line);
+        }
+        AlnDocument::Metric(doc) => {
+            for m in &doc.metrics {
+                let p = format!("metric.{}", m.name.value);
+                let op = match m.op.value {
+                    aln_parser::MetricOp::Max => Direction::Max,
+                    aln_parser::MetricOp::Min => Direction::Min,
+                };
+                out.insert(
+                    format!("{p}.bound"),
+                    Entry { leaf: Leaf::Number(m.bound.value), line: m.bound.span.line, declared: Some(op) },
+                );
+                put(&mut out, format!("{p}.op"), Leaf::Text(format!("{:?}", m.op.value).to_lowercase()), m.op.span.line);
+                put(&mut out, format!("{p}.unit"), Leaf::Text(m.unit.value.clone()), m.unit.span.line);
+                put(&mut out, format!("{p}.domain"), Leaf::Text(m.domain.value.clone()), m.domain.span.line);
+                put(&mut out, format!("{p}.module"), Leaf::Text(m.module.value.clone()), m.module.span.line);
+                let evidence = m.evidence.iter().map(|e| e.value.clone()).collect();
+                put(&mut out, format!("{p}.evidence"), Leaf::Set(evidence), m.name.span.line);
+            }
+            for c in &doc.contracts {
+                for clause in &c.clauses {
+                    let args = clause.args.iter().map(|a| a.value.clone()).collect();
+                    put(&mut out, format!("contract.{}.{}", c.name.value, clause.keyword.value), Leaf::Set(args), clause.keyword.span.line);
+                }
+            }
+        }
+        AlnDocument::Csv(doc) => csv(doc, &mut out),
+    }
+    out
+}
+
+fn put(out: &mut Flat, path: String, leaf: Leaf, line: u32) {
+    out.insert(path, Entry { leaf, line, declared: None });
+}
+
+fn yaml(entries: &[YamlEntry], prefix: &str, out: &mut Flat) {
+    for (i, entry) in entries.iter().enumerate() {
+        let key = &entry.key.value;
+        let repeated = entries.iter().filter(|e| &e.key.value == key).count() > 1;
+        let segment = if repeated {
+            // Repeated keys (`research_goal:`) are told apart by their `id`.
+            match item_id(&entry.value) {
+                Some(id) => format!("{key}[{id}]"),
+                None => format!("{key}[{}]", entries[..i].iter().filter(|e| &e.key.value == key).count()),
+            }
+        } else {
+            key.clone()
+        };
+        let path = if prefix.is_empty() { segment } else { format!("{prefix}.{segment}") };
+        node(&entry.value, &path, entry.key.span.line, out);
+    }
+}
+
+fn node(value: &YamlNode, path: &str, line: u32, out: &mut Flat) {
+    match value {
+        YamlNode::Scalar(s) => put(out, path.to_string(), scalar(&s.value), line),
+        YamlNode::Map { entries, .. } => yaml(entries, path, out),
+        YamlNode::Seq { items, .. } => {
+            if items.iter().all(|i| matches!(i, YamlNode::Scalar(_))) {
+                let set = items
+                    .iter()
+                    .filter_map(|i| match i {
+                        YamlNode::Scalar(s) => Some(scalar(&s.value).to_string().trim_matches('"').to_string()),
+                        _ => None,
+                    })
+                    .collect();
+                put(out, path.to_string(), Leaf::Set(set), line);
+                return;
+            }
+            for (i, item) in items.iter().enumerate() {
+                let segment = item_id(item).unwrap_or_else(|| i.to_string());
+                node(item, &format!("{path}[{segment}]"), item.span().line, out);
+            }
+        }
+    }
+}
+
+fn item_id(node: &YamlNode) -> Option<String> {
+    let YamlNode::Map { entries, .. } = node else {
+        return None;
+    };
+    entries.iter().find(|e| e.key.value == "id" || e.key.value == "name").and_then(|e| match &e.value {
+        YamlNode::Scalar(s) => s.as_str().map(str::to_string),
+        _ => None,
+    })
+}
+
+fn scalar(value: &ScalarValue) -> Leaf {
+    match value {
+        ScalarValue::Null => Leaf::Text("null".into()),
+        ScalarValue::Bool(b) => Leaf::Bool(*b),
+        ScalarValue::Int(i) => Leaf::Number(*i as f64),
+        ScalarValue::Float(f) => Leaf::Number(*f),
+        // Amounts are often quoted (`"1000.000000"`) to keep their precision.
+        ScalarValue::Str(s) => match s.parse::<f64>() {
+            Ok(n) if n.is_finite() && s.chars().all(|c| c.is_ascii_digit() || c == '.' || c == '-') => Leaf::Number(n),
+            _ => Leaf::Text(s.clone()),
+        },
+    }
+}
+
+/// Rows are keyed by `path.entitytype[id].key`, where `id` is the value of
+/// the `id` row in the same blank-line separated group.
+fn csv(doc: &CsvDocument, out: &mut Flat) {
+    let group_id = |group: u32| {
+        doc.rows
+            .iter()
+            .filter(|r| r.group == group)
+            .find(|r| doc.cell(r, "key") == Some("id"))
+            .and_then(|r| doc.cell(r, "value"))
+            .map_or_else(|| group.to_string(), str::to_string)
+    };
+    for row in &doc.rows {
+        let cell = |name| doc.cell(row, name).unwrap_or_default();
+        let key = cell("key");
+        if key == "id" {
+            continue;
+        }
+        let path = format!("{}.{}[{}].{}", cell("path"), cell("entitytype"), group_id(row.group), key);
+        let value = cell("value");
+        let leaf = match cell("datatype") {
+            "bool" => value.parse().map(Leaf::Bool).unwrap_or_else(|_| Leaf::Text(value.into())),
+            "float" | "int" | "uint" => value.parse().map(Leaf::Number).unwrap_or_else(|_| Leaf::Text(value.into())),
+            _ => Leaf::Text(value.into()),
+        };
+        let line = row.span.line;
+        put(out, path.clone(), leaf, line);
+        put(out, format!("{path}.constraints"), Leaf::Text(cell("constraints").into()), line);
+    }
+}
+
+fn type_text(ty: &FieldType) -> String {
+    match ty {
+        FieldType::Enum(variants) => {
+            let names: Vec<_> = variants.iter().map(|v| v.value.as_str()).collect();
+            format!("enum({})", names.join("|"))
+        }
+        FieldType::List(inner) => format!("list {}", type_text(inner)),
+        other => format!("{other:?}").to_lowercase(),
+    }
+}
@@ -0,0 +1,484 @@
+//! Compares two versions of one ALN shard and says, for every change,
+//! whether it tightens or loosens what the shard allows.
+//!
+//! Each leaf gets a [`Direction`]: from the shard itself where it says so
+//! (a metric's `op max` / `op min`), otherwise from [`DirectionRules`]
+//! matched against its path. A loosening on a neurorights path, or of a
+//! risk-of-harm ceiling, is reported as [`ChangeClass::NeurorightsRegressing`].
+
+mod flatten;
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use aln_parser::{AlnDocument, Diagnostic};
+
+pub use flatten::{flatten, Entry, Flat, Leaf};
+
+/// What moving a leaf means.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Upper bound: lowering it tightens.
+    Max,
+    /// Lower bound: raising it tightens.
+    Min,
+    /// Protection: `true`, or an item being present, tightens.
+    Guard,
+    /// Grant: an item being present loosens (`permissions`).
+    Grant,
+    Neutral,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeClass {
+    Neutral,
+    Tightening,
+    Loosening,
+    NeurorightsRegressing,
+}
+
+impl fmt::Display for ChangeClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChangeClass::Neutral => "neutral",
+            ChangeClass::Tightening => "tightening",
+            ChangeClass::Loosening => "loosening",
+            ChangeClass::NeurorightsRegressing => "neurorights-regressing",
+        })
+    }
+}
+
+/// Path segments that put a change under neurorights protection.
+const NEURORIGHTS_MARKERS: [&str; 8] = [
+    "neurorights",
+    "rights",
+    "consent",
+    "privacy",
+    "mental_",
+    "cognitive_liberty",
+    "psychological",
+    "inner_state",
+];
+
+/// Path segments naming a risk-of-harm ceiling: raising one lets more harm
+/// through to the host, so it counts against neurorights too.
+const ROH_CEILING_MARKERS: [&str; 2] = ["roh_ceiling", "risk_of_harm_ceiling"];
+
+/// Direction of each path, by explicit override first and then by the
+/// naming conventions the shards follow.
+#[derive(Clone, Debug, Default)]
+pub struct DirectionRules {
+    overrides: Vec<(String, Direction)>,
+}
+
+impl DirectionRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give `pattern` a direction. The pattern matches a whole path or a
+    /// trailing run of its segments (`veto_window_hours` matches
+    /// `quorum.page_publish.veto_window_hours`).
+    pub fn with(mut self, pattern: &str, direction: Direction) -> Self {
+        self.overrides.push((pattern.to_string(), direction));
+        self
+    }
+
+    pub fn direction(&self, path: &str, entry: &Entry) -> Direction {
+        let bare = strip_ids(path);
+        if let Some((_, d)) = self.overrides.iter().rev().find(|(p, _)| bare == *p || bare.ends_with(&format!(".{p}"))) {
+            return *d;
+        }
+        if let Some(d) = entry.declared {
+            return d;
+        }
+        let segments: Vec<&str> = bare.split('.').collect();
+        let last = segments.last().copied().unwrap_or_default();
+        match &entry.leaf {
+            Leaf::Number(_) => {
+                if last.ends_with("_ceiling") || last.ends_with("_max") || last.ends_with("-max") || last.starts_with("max_") || last.ends_with("_limit") {
+                    Direction::Max
+                } else if last.ends_with("_floor")
+                    || last.ends_with("_min")
+                    || last.ends_with("-min")
+                    || last.ends_with("_fraction")
+                    || last.starts_with("veto_window")
+                    || segments.iter().any(|s| s.starts_with("min_"))
+                {
+                    Direction::Min
+                } else if last.contains("risk_of_harm") {
+                    Direction::Max
+                } else {
+                    Direction::Neutral
+                }
+            }
+            Leaf::Bool(_) => {
+                let guard = ["no_", "require", "forbid_", "preserve_", "must_", "nonwaivable"].iter().any(|p| last.starts_with(p))
+                    || ["_required", "_only", "_min", "-consent"].iter().any(|s| last.ends_with(s))
+                    || segments.iter().any(|s| s.contains("neurorights"));
+                if guard {
+                    Direction::Guard
+                } else {
+                    Direction::Neutral
+                }
+            }
+            Leaf::Set(_) => {
+                if last == "permissions" {
+                    Direction::Grant
+                } else if last == "invariants" || last.ends_with("_flags") || last.ends_with("_triplet") || segments.iter().any(|s| s.contains("neurorights")) {
+                    Direction::Guard
+                } else {
+                    Direction::Neutral
+                }
+            }
+            // Particle schema fields are guards when their names say so.
+            Leaf::Text(_) if segments.first() == Some(&"fields") => {
+                let guard = ["no_", "forbid_", "preserve_", "must_"].iter().any(|p| last.starts_with(p)) || last.ends_with("_min");
+                if guard {
+                    Direction::Guard
+                } else {
+                    Direction::Neutral
+                }
+            }
+            // Dropping a neurorights anchor drops the protection it points at.
+            Leaf::Text(_) if (segments.first() == Some(&"anchors") || last.ends_with("_ref")) && is_neurorights(&bare) => Direction::Guard,
+            // A role gains whatever the role it inherits resolves to.
+            Leaf::Text(_) if last == "inherits" && segments.len() >= 3 && segments[segments.len() - 3] == "roles" => Direction::Grant,
+            Leaf::Text(_) => Direction::Neutral,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub path: String,
+    pub old: Option<Leaf>,
+    pub new: Option<Leaf>,
+    pub old_line: Option<u32>,
+    pub new_line: Option<u32>,
+    pub direction: Direction,
+    pub class: ChangeClass,
+}
+
+#[derive(Debug)]
+pub enum DiffError {
+    /// Either side failed to parse; diagnostics are rendered with file names.
+    Parse(Vec<String>),
+    SyntaxMismatch { old: aln_parser::AlnSyntax, new: aln_parser::AlnSyntax },
+}
+
+impl fmt::Display for DiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffError::Parse(diagnostics) => write!(f, "cannot parse shard:\n{}", diagnostics.join("\n")),
+            DiffError::SyntaxMismatch { old, new } => write!(f, "old shard is {old:?} but new shard is {new:?}"),
+        }
+    }
+}
+
+impl std::error::Error for DiffError {}
+
+/// Parse both versions and diff them.
+pub fn diff_sources(
+    old_name: &str,
+    old: &str,
+    new_name: &str,
+    new: &str,
+    rules: &DirectionRules,
+) -> Result<Vec<Change>, DiffError> {
+    let parse = |name: &str, source: &str| {
+        let parsed = aln_parser::parse(source);
+        let errors: Vec<String> = parsed.diagnostics.iter().filter(|d| d.is_error()).map(|d: &Diagnostic| d.render(name)).collect();
+        match parsed.document {
+            Some(doc) if errors.is_empty() => Ok(doc),
+            _ => Err(errors),
+        }
+    };
+    let (old, new) = match (parse(old_name, old), parse(new_name, new)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (old, new) => {
+            let mut errors = old.err().unwrap_or_default();
+            errors.extend(new.err().unwrap_or_default());
+            return Err(DiffError::Parse(errors));
+        }
+    };
+    if old.syntax() != new.syntax() {
+        return Err(DiffError::SyntaxMismatch { old: old.syntax(), new: new.syntax() });
+    }
+    Ok(diff(&old, &new, rules))
+}
+
+pub fn diff(old: &AlnDocument, new: &AlnDocument, rules: &DirectionRules) -> Vec<Change> {
+    let old = flatten(old);
+    let new = flatten(new);
+    let mut paths: Vec<&String> = old.keys().chain(new.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut out = Vec::new();
+    for path in paths {
+        let (o, n) = (old.get(path), new.get(path));
+        if o.map(|e| &e.leaf) == n.map(|e| &e.leaf) {
+            continue;
+        }
+        let direction = rules.direction(path, o.or(n).expect("path from one side"));
+        if let Some(role) = path.strip_suffix(".inherits").filter(|_| direction == Direction::Grant) {
+            out.extend(inherits_changes(path, role, (&old, o), (&new, n)));
+            continue;
+        }
+        match (o.map(|e| &e.leaf), n.map(|e| &e.leaf)) {
+            // Sets change item by item, so one added permission and one
+            // removed one are reported separately.
+            (Some(Leaf::Set(a)), Some(Leaf::Set(b))) => {
+                for item in a.difference(b) {
+                    out.push(set_change(path, item, o, None, direction, false));
+                }
+                for item in b.difference(a) {
+                    out.push(set_change(path, item, None, n, direction, true));
+                }
+            }
+            (ol, nl) => out.push(Change {
+                path: path.clone(),
+                old: ol.cloned(),
+                new: nl.cloned(),
+                old_line: o.map(|e| e.line),
+                new_line: n.map(|e| e.line),
+                direction,
+                class: escalate(path, classify(ol, nl, direction)),
+            }),
+        }
+    }
+    out
+}
+
+fn set_change(path: &str, item: &str, old: Option<&Entry>, new: Option<&Entry>, direction: Direction, added: bool) -> Change {
+    let class = match (direction, added) {
+        (Direction::Guard, true) | (Direction::Grant, false) => ChangeClass::Tightening,
+        (Direction::Guard, false) | (Direction::Grant, true) => ChangeClass::Loosening,
+        _ => ChangeClass::Neutral,
+    };
+    let item_path = format!("{path}[{item}]");
+    let leaf = Some(Leaf::Text(item.to_string()));
+    Change {
+        class: escalate(&item_path, class),
+        path: item_path,
+        old: if added { None } else { leaf.clone() },
+        new: if added { leaf } else { None },
+        old_line: old.map(|e| e.line),
+        new_line: new.map(|e| e.line),
+        direction,
+    }
+}
+
+/// An `inherits` change is reported per permission the role gains or loses
+/// through it, comparing the role's resolved sets. Items its own
+/// `permissions` list also changed are left to that list's diff.
+fn inherits_changes(path: &str, role: &str, (old, o): (&Flat, Option<&Entry>), (new, n): (&Flat, Option<&Entry>)) -> Vec<Change> {
+    let own_path = format!("{role}.permissions");
+    let own = |flat: &Flat, item: &String| matches!(flat.get(&own_path), Some(Entry { leaf: Leaf::Set(s), .. }) if s.contains(item));
+    let through_inherits = |item: &&String| own(old, item) == own(new, item);
+    let (before, after) = (resolved_permissions(old, role), resolved_permissions(new, role));
+
+    let mut out: Vec<Change> = before.difference(&after).filter(through_inherits).map(|item| set_change(path, item, o, None, Direction::Grant, false)).collect();
+    out.extend(after.difference(&before).filter(through_inherits).map(|item| set_change(path, item, None, n, Direction::Grant, true)));
+    if out.is_empty() {
+        out.push(Change {
+            path: path.to_string(),
+            old: o.map(|e| e.leaf.clone()),
+            new: n.map(|e| e.leaf.clone()),
+            old_line: o.map(|e| e.line),
+            new_line: n.map(|e| e.line),
+            direction: Direction::Grant,
+            class: ChangeClass::Neutral,
+        });
+    }
+    out
+}
+
+/// `role`'s own permissions plus those along its `inherits` chain.
+fn resolved_permissions(flat: &Flat, role: &str) -> BTreeSet<String> {
+    let roles = role.rsplit_once('.').map_or("", |(roles, _)| roles);
+    let mut seen = BTreeSet::new();
+    let mut out = BTreeSet::new();
+    let mut current = role.to_string();
+    while seen.insert(current.clone()) {
+        if let Some(Entry { leaf: Leaf::Set(items), .. }) = flat.get(&format!("{current}.permissions")) {
+            out.extend(items.iter().cloned());
+        }
+        match flat.get(&format!("{current}.inherits")) {
+            Some(Entry { leaf: Leaf::Text(parent), .. }) => current = format!("{roles}.{parent}"),
+            _ => break,
+        }
+    }
+    out
+}
+
+fn classify(old: Option<&Leaf>, new: Option<&Leaf>, direction: Direction) -> ChangeClass {
+    use ChangeClass::*;
+    match (direction, old, new) {
+        (Direction::Max, Some(Leaf::Number(a)), Some(Leaf::Number(b))) => {
+            if b < a {
+                Tightening
+            } else {
+                Loosening
+            }
+        }
+        (Direction::Min, Some(Leaf::Number(a)), Some(Leaf::Number(b))) => {
+            if b > a {
+                Tightening
+            } else {
+                Loosening
+            }
+        }
+        (Direction::Guard, Some(Leaf::Bool(a)), Some(Leaf::Bool(b))) => match (a, b) {
+            (true, false) => Loosening,
+            (false, true) => Tightening,
+            _ => Neutral,
+        },
+        // A bound or protection appearing or disappearing.
+        (Direction::Max | Direction::Min, None, Some(_)) => Tightening,
+        (Direction::Max | Direction::Min, Some(_), None) => Loosening,
+        (Direction::Guard, None, Some(leaf)) => {
+            if leaf == &Leaf::Bool(false) {
+                Neutral
+            } else {
+                Tightening
+            }
+        }
+        (Direction::Guard, Some(leaf), None) => {
+            if leaf == &Leaf::Bool(false) {
+                Neutral
+            } else {
+                Loosening
+            }
+        }
+        (Direction::Grant, None, Some(_)) => Loosening,
+        (Direction::Grant, Some(_), None) => Tightening,
+        _ => Neutral,
+    }
+}
+
+fn escalate(path: &str, class: ChangeClass) -> ChangeClass {
+    if class == ChangeClass::Loosening && is_neurorights(path) {
+        ChangeClass::NeurorightsRegressing
+    } else {
+        class
+    }
+}
+
+fn is_neurorights(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    NEURORIGHTS_MARKERS.iter().chain(&ROH_CEILING_MARKERS).any(|m| lower.contains(m))
+}
+
+/// `research_goal[goal.x].id` → `research_goal.id`, for pattern matching.
+fn strip_ids(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut depth = 0;
+    for c in path.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classes(old: &str, new: &str) -> Vec<(String, ChangeClass)> {
+        diff_sources("old.aln", old, "new.aln", new, &DirectionRules::new())
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.path, c.class))
+            .collect()
+    }
+
+    #[test]
+    fn classifies_governance_changes() {
+        let old = "id: g.v1\nroles:\n  council:\n    permissions:\n      - website.page.review\n  min_stake:\n    council: \"10.0\"\nneurorights:\n  no_score_from_inner_state: true\nrisk_of_harm_ceiling: 0.3\nhexstamp: \"0x1\"\n";
+        let new = "id: g.v1\nroles:\n  council:\n    permissions:\n      - website.page.review\n      - website.page.publish\n  min_stake:\n    council: \"20.0\"\nneurorights:\n  no_score_from_inner_state: false\nrisk_of_harm_ceiling: 0.25\nhexstamp: \"0x2\"\n";
+        assert_eq!(
+            classes(old, new),
+            [
+                ("hexstamp".to_string(), ChangeClass::Neutral),
+                ("neurorights.no_score_from_inner_state".to_string(), ChangeClass::NeurorightsRegressing),
+                ("risk_of_harm_ceiling".to_string(), ChangeClass::Tightening),
+                ("roles.council.permissions[website.page.publish]".to_string(), ChangeClass::Loosening),
+                ("roles.min_stake.council".to_string(), ChangeClass::Tightening),
+            ]
+        );
+    }
+
+    #[test]
+    fn metric_op_sets_bound_direction() {
+        let metric = |op: &str, bound: &str| {
+            format!("particle p.v1\nmetric m\n  domain d\n  module x\n  op {op}\n  unit scalar\n  bound {bound}\ncontract c!\n  neurorights mental_privacy\n")
+        };
+        assert_eq!(classes(&metric("max", "0.3"), &metric("max", "0.2"))[0].1, ChangeClass::Tightening);
+        assert_eq!(classes(&metric("min", "0.8"), &metric("min", "0.7"))[0].1, ChangeClass::Loosening);
+
+        let dropped = metric("max", "0.3").replace(" mental_privacy", "");
+        assert_eq!(
+            classes(&metric("max", "0.3"), &dropped),
+            [("contract.c!.neurorights[mental_privacy]".to_string(), ChangeClass::NeurorightsRegressing)]
+        );
+    }
+
+    #[test]
+    fn risk_of_harm_defaults_to_max_and_neurorights_anchors_guard() {
+        let old = "id: a.v1\nanchors:\n  neurorights_envelope_ref: neurorights.envelope.citizen.v1\nrisk:\n  default_risk_of_harm: 0.08\n";
+        let new = "id: a.v1\nrisk:\n  default_risk_of_harm: 0.29\n";
+        assert_eq!(
+            classes(old, new),
+            [
+                ("anchors.neurorights_envelope_ref".to_string(), ChangeClass::NeurorightsRegressing),
+                ("risk.default_risk_of_harm".to_string(), ChangeClass::Loosening),
+            ]
+        );
+    }
+
+    /// Raising the nanoswarm envelope's RoH ceiling past 0.3 must fail the diff.
+    #[test]
+    fn raising_the_roh_ceiling_regresses_neurorights() {
+        let head = include_str!("../../../specs/aln/nanoswarm.lifeforce.envelope.v1.aln");
+        let raised = head.replacen("op      max\n  unit    scalar\n  bound   0.30", "op      max\n  unit    scalar\n  bound   0.45", 1);
+        assert_ne!(head, raised);
+        assert_eq!(
+            classes(head, &raised),
+            [("metric.envelope_roh_ceiling.bound".to_string(), ChangeClass::NeurorightsRegressing)]
+        );
+        assert_eq!(classes(&raised, head)[0].1, ChangeClass::Tightening);
+    }
+
+    /// The website shard as first committed against its current version,
+    /// where `council` and `superchair` gained `inherits`.
+    #[test]
+    fn inherits_grants_the_resolved_permissions() {
+        let baseline = include_str!("../tests/fixtures/governance.chat.website.v1.aln.baseline");
+        let head = include_str!("../../../aln/governance.chat.website.v1.aln");
+        let granted: Vec<(String, ChangeClass)> = classes(baseline, head).into_iter().filter(|(path, _)| path.starts_with("roles.")).collect();
+        let grant = |role: &str, permission: &str| (format!("roles.{role}.inherits[{permission}]"), ChangeClass::Loosening);
+        assert_eq!(
+            granted,
+            [
+                grant("council", "website.comment.propose"),
+                grant("superchair", "website.comment.moderate"),
+                grant("superchair", "website.comment.propose"),
+                grant("superchair", "website.page.propose"),
+                grant("superchair", "website.page.review"),
+                grant("superchair", "website.section.review"),
+            ]
+        );
+
+        let dropped = head.replace("    inherits: stakeholder\n", "");
+        assert_eq!(
+            classes(head, &dropped),
+            [("roles.council.inherits[website.comment.propose]".to_string(), ChangeClass::Tightening)]
+        );
+    }
+}
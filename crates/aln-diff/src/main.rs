@@ -0,0 +1,89 @@
+use std::fs;
+use std::process::ExitCode;
+
+use aln_diff::{diff_sources, Change, ChangeClass, Direction, DirectionRules};
+use serde_json::json;
+
+const USAGE: &str = "usage: aln-diff [--json] [--direction PATH=max|min|guard|grant|neutral]... OLD NEW";
+
+fn main() -> ExitCode {
+    let mut files = Vec::new();
+    let mut as_json = false;
+    let mut rules = DirectionRules::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => as_json = true,
+            "--direction" => {
+                let Some((path, direction)) = args.next().as_deref().and_then(parse_direction) else {
+                    eprintln!("{USAGE}");
+                    return ExitCode::from(2);
+                };
+                rules = rules.with(&path, direction);
+            }
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ => files.push(arg),
+        }
+    }
+    let [old_path, new_path] = files.as_slice() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let read = |path: &String| fs::read_to_string(path).map_err(|e| eprintln!("aln-diff: cannot read {path}: {e}"));
+    let (Ok(old), Ok(new)) = (read(old_path), read(new_path)) else {
+        return ExitCode::from(2);
+    };
+    let changes = match diff_sources(old_path, &old, new_path, &new, &rules) {
+        Ok(changes) => changes,
+        Err(e) => {
+            eprintln!("aln-diff: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if as_json {
+        let report: Vec<_> = changes.iter().map(change_json).collect();
+        println!("{}", serde_json::to_string_pretty(&json!({ "old": old_path, "new": new_path, "changes": report })).expect("report serializes"));
+    } else {
+        for c in &changes {
+            let value = |leaf: &Option<aln_diff::Leaf>| leaf.as_ref().map_or("-".to_string(), ToString::to_string);
+            println!("{:<22} {}: {} -> {}", c.class.to_string(), c.path, value(&c.old), value(&c.new));
+        }
+    }
+
+    let regressions = changes.iter().filter(|c| c.class == ChangeClass::NeurorightsRegressing).count();
+    if regressions > 0 {
+        eprintln!("aln-diff: {regressions} neurorights-regressing change(s)");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn parse_direction(arg: &str) -> Option<(String, Direction)> {
+    let (path, direction) = arg.split_once('=')?;
+    let direction = match direction {
+        "max" => Direction::Max,
+        "min" => Direction::Min,
+        "guard" => Direction::Guard,
+        "grant" => Direction::Grant,
+        "neutral" => Direction::Neutral,
+        _ => return None,
+    };
+    Some((path.to_string(), direction))
+}
+
+fn change_json(c: &Change) -> serde_json::Value {
+    json!({
+        "path": c.path,
+        "class": c.class.to_string(),
+        "direction": format!("{:?}", c.direction).to_lowercase(),
+        "old": c.old.as_ref().map(ToString::to_string),
+        "new": c.new.as_ref().map(ToString::to_string),
+        "old_line": c.old_line,
+        "new_line": c.new_line,
+    })
+}
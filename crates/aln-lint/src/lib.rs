@@ -0,0 +1,121 @@
+//! Checks the doctrine invariants that the shards state in prose but that
+//! can be verified mechanically: RoH ceilings, evidence bundle shape,
+//! role stake ordering and cross-shard references.
+
+mod rules;
+
+use std::path::Path;
+
+use aln_parser::{AlnWorkspace, Severity, Span};
+use serde_json::{json, Value};
+
+pub use rules::{
+    default_rules, EvidenceTags, MinStakeMonotone, ParseDiagnostics, RefsResolve, RohCeiling, Rule,
+    EVIDENCE_TAG_COUNT, ROH_CEILING_MAX, ROLE_ORDER,
+};
+
+/// One rule violation at a location in a shard.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finding {
+    pub rule: &'static str,
+    pub level: Severity,
+    /// Index into [`AlnWorkspace::shards`].
+    pub shard: usize,
+    pub span: Span,
+    pub message: String,
+}
+
+impl Finding {
+    pub fn new(rule: &'static str, level: Severity, shard: usize, span: Span, message: impl Into<String>) -> Self {
+        Finding { rule, level, shard, span, message: message.into() }
+    }
+}
+
+/// Run `rules` over every shard, in rule order.
+pub fn lint(workspace: &AlnWorkspace, rules: &[Box<dyn Rule>]) -> Vec<Finding> {
+    let mut out = Vec::new();
+    for rule in rules {
+        rule.check(workspace, &mut out);
+    }
+    out
+}
+
+/// SARIF 2.1.0-shaped report; `uri`s are relative to `root`.
+pub fn sarif(workspace: &AlnWorkspace, rules: &[Box<dyn Rule>], findings: &[Finding], root: &Path) -> Value {
+    let rules: Vec<Value> = rules
+        .iter()
+        .map(|r| json!({ "id": r.id(), "shortDescription": { "text": r.description() } }))
+        .collect();
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|f| {
+            let path = &workspace.shards()[f.shard].path;
+            let uri = path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+            json!({
+                "ruleId": f.rule,
+                "level": match f.level {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                },
+                "message": { "text": f.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri },
+                        "region": {
+                            "startLine": f.span.line,
+                            "startColumn": f.span.col,
+                            "charOffset": f.span.start,
+                            "charLength": f.span.end - f.span.start,
+                        }
+                    }
+                }]
+            })
+        })
+        .collect();
+    json!({
+        "version": "2.1.0",
+        "runs": [{
+            "tool": { "driver": { "name": "aln-lint", "version": env!("CARGO_PKG_VERSION"), "rules": rules } },
+            "results": results,
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_each_rule_at_its_location() {
+        let mut ws = AlnWorkspace::new();
+        ws.add(
+            "aln/asset.aln",
+            "id: asset.v1\nfields:\n  min_stake:\n    stakeholder: \"100.5\"\n    council: \"99.75\"\n    superchair: \"1000\"\n  risk:\n    risk_of_harm_ceiling: 0.35\n",
+        );
+        ws.add(
+            "aln/gov.aln",
+            "id: gov.v1\nstake_ref: asset.v1:min_stake.chair\ndoctrine_ref: external.doctrine.v1\nroh_ceiling: 0.3\n",
+        );
+        ws.add(
+            "specs/aln/m.aln",
+            "particle m.v1\nmetric roh_ceiling\n  domain d\n  module m\n  op max\n  unit scalar\n  bound 0.3\n  evidence 0x01 0xzz\n",
+        );
+
+        let findings = lint(&ws, &default_rules());
+        let found: Vec<_> = findings.iter().map(|f| (f.rule, f.shard, f.span.line, f.level)).collect();
+        assert_eq!(
+            found,
+            [
+                ("roh-ceiling", 0, 8, Severity::Error),
+                ("evidence-tags", 2, 8, Severity::Error),
+                ("evidence-tags", 2, 8, Severity::Error),
+                ("min-stake-monotone", 0, 5, Severity::Error),
+                ("ref-resolves", 1, 2, Severity::Error),
+                ("ref-resolves", 1, 3, Severity::Warning),
+            ]
+        );
+
+        let report = sarif(&ws, &default_rules(), &findings, Path::new(""));
+        assert_eq!(report["runs"][0]["results"][3]["locations"][0]["physicalLocation"]["region"]["startColumn"], 14);
+    }
+}
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aln_lint::{default_rules, lint, sarif};
+use aln_parser::{AlnWorkspace, Severity};
+
+const USAGE: &str = "usage: aln-lint [--output FILE] [ROOT]";
+
+fn main() -> ExitCode {
+    let mut root = PathBuf::from(".");
+    let mut output: Option<PathBuf> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--output" | "-o" => match args.next() {
+                Some(path) => output = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("{USAGE}");
+                    return ExitCode::from(2);
+                }
+            },
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ => root = PathBuf::from(arg),
+        }
+    }
+
+    let workspace = match AlnWorkspace::load_dir(&root) {
+        Ok(ws) => ws,
+        Err(e) => {
+            eprintln!("aln-lint: cannot read {}: {e}", root.display());
+            return ExitCode::from(2);
+        }
+    };
+    let rules = default_rules();
+    let findings = lint(&workspace, &rules);
+    let report = serde_json::to_string_pretty(&sarif(&workspace, &rules, &findings, &root)).expect("report serializes");
+
+    match &output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, report + "\n") {
+                eprintln!("aln-lint: cannot write {}: {e}", path.display());
+                return ExitCode::from(2);
+            }
+        }
+        None => println!("{report}"),
+    }
+
+    let errors = findings.iter().filter(|f| f.level == Severity::Error).count();
+    eprintln!(
+        "aln-lint: {} shards, {} errors, {} warnings",
+        workspace.shards().len(),
+        errors,
+        findings.len() - errors
+    );
+    if errors > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
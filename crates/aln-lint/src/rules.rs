@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+
+use aln_parser::{
+    AlnDocument, AlnWorkspace, MetricOp, ScalarValue, Severity, Span, YamlDocument, YamlEntry, YamlNode,
+};
+
+use crate::Finding;
+
+/// Doctrine-wide Risk-of-Harm ceiling; no shard may declare a looser one.
+pub const ROH_CEILING_MAX: f64 = 0.3;
+
+/// Evidence bundles carry exactly this many hex tags.
+pub const EVIDENCE_TAG_COUNT: usize = 10;
+
+/// Governance roles from least to most privileged.
+pub const ROLE_ORDER: [&str; 3] = ["stakeholder", "council", "superchair"];
+
+const ROH_CEILING_KEYS: [&str; 4] = ["roh_ceiling", "risk_of_harm_ceiling", "roh_max", "roh-max"];
+
+/// One machine-checkable invariant over the loaded shards.
+pub trait Rule {
+    fn id(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn check(&self, workspace: &AlnWorkspace, out: &mut Vec<Finding>);
+}
+
+pub fn default_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(ParseDiagnostics),
+        Box::new(RohCeiling),
+        Box::new(EvidenceTags),
+        Box::new(MinStakeMonotone),
+        Box::new(RefsResolve),
+    ]
+}
+
+/// Parser errors and warnings, so a malformed shard is never silently skipped.
+pub struct ParseDiagnostics;
+
+impl Rule for ParseDiagnostics {
+    fn id(&self) -> &'static str {
+        "aln-parse"
+    }
+
+    fn description(&self) -> &'static str {
+        "Shard parses cleanly"
+    }
+
+    fn check(&self, workspace: &AlnWorkspace, out: &mut Vec<Finding>) {
+        for (shard, s) in workspace.shards().iter().enumerate() {
+            for d in &s.parsed.diagnostics {
+                out.push(Finding::new(self.id(), d.severity, shard, d.span, format!("{} ({})", d.message, d.code)));
+            }
+        }
+    }
+}
+
+/// Every declared RoH ceiling is at most [`ROH_CEILING_MAX`].
+pub struct RohCeiling;
+
+impl RohCeiling {
+    fn verdict(&self, shard: usize, span: Span, name: &str, value: Option<f64>, out: &mut Vec<Finding>) {
+        match value {
+            Some(v) if v <= ROH_CEILING_MAX => {}
+            Some(v) => out.push(Finding::new(
+                self.id(),
+                Severity::Error,
+                shard,
+                span,
+                format!("`{name}` is {v}, above the doctrine ceiling {ROH_CEILING_MAX}"),
+            )),
+            None => out.push(Finding::new(
+                self.id(),
+                Severity::Error,
+                shard,
+                span,
+                format!("`{name}` is not a number"),
+            )),
+        }
+    }
+}
+
+impl Rule for RohCeiling {
+    fn id(&self) -> &'static str {
+        "roh-ceiling"
+    }
+
+    fn description(&self) -> &'static str {
+        "Risk-of-Harm ceilings are at most 0.3"
+    }
+
+    fn check(&self, workspace: &AlnWorkspace, out: &mut Vec<Finding>) {
+        for (shard, s) in workspace.shards().iter().enumerate() {
+            match &s.parsed.document {
+                Some(AlnDocument::Yaml(doc)) => {
+                    walk(&doc.root, &mut |entry| {
+                        if let (true, YamlNode::Scalar(scalar)) = (ROH_CEILING_KEYS.contains(&entry.key.value.as_str()), &entry.value) {
+                            self.verdict(shard, scalar.span, &entry.key.value, number(&scalar.value), out);
+                        }
+                    });
+                }
+                Some(AlnDocument::Metric(doc)) => {
+                    for metric in doc.metrics.iter().filter(|m| m.op.value == MetricOp::Max) {
+                        if ROH_CEILING_KEYS.iter().any(|k| metric.name.value.ends_with(k)) {
+                            self.verdict(shard, metric.bound.span, &metric.name.value, Some(metric.bound.value), out);
+                        }
+                    }
+                }
+                Some(AlnDocument::Csv(doc)) => {
+                    for row in &doc.rows {
+                        let key = doc.cell(row, "key").unwrap_or_default();
+                        if !ROH_CEILING_KEYS.contains(&key) {
+                            continue;
+                        }
+                        let value = doc.column("value").and_then(|c| row.cells.get(c));
+                        let span = value.map_or(row.span, |v| v.span);
+                        self.verdict(shard, span, key, value.and_then(|v| v.value.parse().ok()), out);
+                    }
+                }
+                Some(AlnDocument::Particle(_)) | None => {}
+            }
+        }
+    }
+}
+
+/// Metric evidence lists hold exactly [`EVIDENCE_TAG_COUNT`] `0x…` hex tags.
+pub struct EvidenceTags;
+
+impl Rule for EvidenceTags {
+    fn id(&self) -> &'static str {
+        "evidence-tags"
+    }
+
+    fn description(&self) -> &'static str {
+        "Evidence lists have exactly 10 hex tags"
+    }
+
+    fn check(&self, workspace: &AlnWorkspace, out: &mut Vec<Finding>) {
+        for (shard, s) in workspace.shards().iter().enumerate() {
+            let Some(AlnDocument::Metric(doc)) = &s.parsed.document else {
+                continue;
+            };
+            for metric in doc.metrics.iter().filter(|m| !m.evidence.is_empty()) {
+                if metric.evidence.len() != EVIDENCE_TAG_COUNT {
+                    out.push(Finding::new(
+                        self.id(),
+                        Severity::Error,
+                        shard,
+                        metric.evidence[0].span,
+                        format!(
+                            "metric `{}` lists {} evidence tags, expected {EVIDENCE_TAG_COUNT}",
+                            metric.name.value,
+                            metric.evidence.len()
+                        ),
+                    ));
+                }
+                for tag in metric.evidence.iter().filter(|t| !is_hex_tag(&t.value)) {
+                    out.push(Finding::new(
+                        self.id(),
+                        Severity::Error,
+                        shard,
+                        tag.span,
+                        format!("`{}` is not a 0x-prefixed hex tag", tag.value),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// `min_stake` never decreases from stakeholder to council to superchair.
+pub struct MinStakeMonotone;
+
+impl Rule for MinStakeMonotone {
+    fn id(&self) -> &'static str {
+        "min-stake-monotone"
+    }
+
+    fn description(&self) -> &'static str {
+        "min_stake is monotone across governance roles"
+    }
+
+    fn check(&self, workspace: &AlnWorkspace, out: &mut Vec<Finding>) {
+        for (shard, s) in workspace.shards().iter().enumerate() {
+            let Some(AlnDocument::Yaml(doc)) = &s.parsed.document else {
+                continue;
+            };
+            let Some(YamlNode::Map { entries, .. }) = min_stake(doc) else {
+                continue;
+            };
+            let mut previous: Option<(&str, String)> = None;
+            for role in ROLE_ORDER {
+                let Some(entry) = entries.iter().find(|e| e.key.value == role) else {
+                    continue;
+                };
+                let YamlNode::Scalar(scalar) = &entry.value else {
+                    continue;
+                };
+                let Some(value) = decimal_text(&scalar.value) else {
+                    out.push(Finding::new(
+                        self.id(),
+                        Severity::Error,
+                        shard,
+                        scalar.span,
+                        format!("min_stake.{role} is not a decimal amount"),
+                    ));
+                    continue;
+                };
+                if let Some((prev_role, prev)) = &previous {
+                    if cmp_decimal(&value, prev) == Ordering::Less {
+                        out.push(Finding::new(
+                            self.id(),
+                            Severity::Error,
+                            shard,
+                            scalar.span,
+                            format!("min_stake.{role} ({value}) is below min_stake.{prev_role} ({prev})"),
+                        ));
+                    }
+                }
+                previous = Some((role, value));
+            }
+        }
+    }
+}
+
+/// A `*_ref` into a loaded shard must name a path that exists there. Refs to
+/// shards outside the workspace stay warnings, as the parser reports them,
+/// since doctrine and research shards cite documents kept elsewhere.
+pub struct RefsResolve;
+
+impl Rule for RefsResolve {
+    fn id(&self) -> &'static str {
+        "ref-resolves"
+    }
+
+    fn description(&self) -> &'static str {
+        "Every _ref resolves to a loaded shard"
+    }
+
+    fn check(&self, workspace: &AlnWorkspace, out: &mut Vec<Finding>) {
+        for resolved in workspace.resolve() {
+            if let Some(diagnostic) = resolved.diagnostic() {
+                out.push(Finding::new(
+                    self.id(),
+                    diagnostic.severity,
+                    resolved.from,
+                    resolved.reference.target.span,
+                    format!("{}: {}", resolved.reference.key.value, diagnostic.message),
+                ));
+            }
+        }
+    }
+}
+
+fn walk<'a>(entries: &'a [YamlEntry], f: &mut impl FnMut(&'a YamlEntry)) {
+    for entry in entries {
+        f(entry);
+        match &entry.value {
+            YamlNode::Map { entries, .. } => walk(entries, f),
+            YamlNode::Seq { items, .. } => {
+                for item in items {
+                    if let YamlNode::Map { entries, .. } = item {
+                        walk(entries, f);
+                    }
+                }
+            }
+            YamlNode::Scalar(_) => {}
+        }
+    }
+}
+
+fn min_stake(doc: &YamlDocument) -> Option<&YamlNode> {
+    doc.get("min_stake").or_else(|| doc.get("fields.min_stake"))
+}
+
+fn number(value: &ScalarValue) -> Option<f64> {
+    match value {
+        ScalarValue::Int(i) => Some(*i as f64),
+        ScalarValue::Float(f) => Some(*f),
+        ScalarValue::Str(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn is_hex_tag(tag: &str) -> bool {
+    tag.strip_prefix("0x")
+        .is_some_and(|hex| !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Plain decimal text of an amount, whether written quoted or bare.
+fn decimal_text(value: &ScalarValue) -> Option<String> {
+    let text = match value {
+        ScalarValue::Int(i) if *i >= 0 => i.to_string(),
+        ScalarValue::Float(f) if *f >= 0.0 => f.to_string(),
+        ScalarValue::Str(s) => s.clone(),
+        _ => return None,
+    };
+    let (int, frac) = text.split_once('.').unwrap_or((&text, ""));
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    (!int.is_empty() && digits(int) && digits(frac)).then_some(text)
+}
+
+/// Exact comparison of two non-negative decimal strings.
+fn cmp_decimal(a: &str, b: &str) -> Ordering {
+    let split = |s: &str| {
+        let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+        let int = int.trim_start_matches('0').to_string();
+        (int, frac.trim_end_matches('0').to_string())
+    };
+    let (ai, af) = split(a);
+    let (bi, bf) = split(b);
+    ai.len()
+        .cmp(&bi.len())
+        .then_with(|| ai.cmp(&bi))
+        .then_with(|| af.cmp(&bf))
+}
@@ -0,0 +1,247 @@
+use crate::span::{Span, Spanned};
+
+/// The four shard syntaxes found in the repository.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlnSyntax {
+    /// `key: value` documents (`aln/*.aln`, `research/*.aln`).
+    Yaml,
+    /// `aln particle NAME … end` schema blocks (`specs/*.aln`), and the
+    /// sectioned `particle NAME` / `meta` / `fields` variant (`logs/*.aln`).
+    Particle,
+    /// `particle NAME` followed by `metric … bound` and `contract` blocks (`specs/aln/*.aln`).
+    Metric,
+    /// `aln` / `<kind> <name>` / CSV header and rows (`evolution/*.aln`).
+    Csv,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlnDocument {
+    Yaml(YamlDocument),
+    Particle(ParticleDocument),
+    Metric(MetricDocument),
+    Csv(CsvDocument),
+}
+
+impl AlnDocument {
+    pub fn syntax(&self) -> AlnSyntax {
+        match self {
+            AlnDocument::Yaml(_) => AlnSyntax::Yaml,
+            AlnDocument::Particle(_) => AlnSyntax::Particle,
+            AlnDocument::Metric(_) => AlnSyntax::Metric,
+            AlnDocument::Csv(_) => AlnSyntax::Csv,
+        }
+    }
+
+    /// The shard's own identifier, as other shards refer to it.
+    pub fn id(&self) -> Option<Spanned<&str>> {
+        match self {
+            AlnDocument::Yaml(doc) => doc.id(),
+            AlnDocument::Particle(doc) => Some(Spanned::new(doc.name.value.as_str(), doc.name.span)),
+            AlnDocument::Metric(doc) => Some(Spanned::new(doc.particle.value.as_str(), doc.particle.span)),
+            AlnDocument::Csv(doc) => {
+                let name = doc.name.value.as_str();
+                Some(Spanned::new(name.strip_suffix(".aln").unwrap_or(name), doc.name.span))
+            }
+        }
+    }
+}
+
+// ---- YAML-style ----
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct YamlDocument {
+    pub root: Vec<YamlEntry>,
+}
+
+/// Keys that name a YAML-style shard, in order of preference.
+const YAML_ID_KEYS: [&str; 4] = ["id", "shard_id", "policy", "shard_type"];
+
+impl YamlDocument {
+    pub fn id(&self) -> Option<Spanned<&str>> {
+        YAML_ID_KEYS.iter().find_map(|key| match self.get(key)? {
+            YamlNode::Scalar(s) => s.as_str().map(|v| Spanned::new(v, s.span)),
+            _ => None,
+        })
+    }
+
+    /// First node at a dotted `path` from the root, e.g. `fields.min_stake.council`.
+    pub fn get(&self, path: &str) -> Option<&YamlNode> {
+        let mut keys = path.split('.');
+        let first = keys.next()?;
+        let mut node = &self.root.iter().find(|e| e.key.value == first)?.value;
+        for key in keys {
+            let YamlNode::Map { entries, .. } = node else {
+                return None;
+            };
+            node = &entries.iter().find(|e| e.key.value == key)?.value;
+        }
+        Some(node)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct YamlEntry {
+    pub key: Spanned<String>,
+    pub value: YamlNode,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum YamlNode {
+    Scalar(Scalar),
+    Map { entries: Vec<YamlEntry>, span: Span },
+    Seq { items: Vec<YamlNode>, span: Span },
+}
+
+impl YamlNode {
+    pub fn span(&self) -> Span {
+        match self {
+            YamlNode::Scalar(s) => s.span,
+            YamlNode::Map { span, .. } | YamlNode::Seq { span, .. } => *span,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scalar {
+    pub value: ScalarValue,
+    pub span: Span,
+}
+
+impl Scalar {
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            ScalarValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScalarValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+// ---- particle blocks ----
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParticleDocument {
+    pub name: Spanned<String>,
+    /// `aln particle … end` rather than the sectioned `particle` form.
+    pub block: bool,
+    pub sections: Vec<ParticleSection>,
+    pub invariants: Vec<Spanned<String>>,
+}
+
+impl ParticleDocument {
+    pub fn fields(&self) -> impl Iterator<Item = &ParticleField> {
+        self.sections.iter().flat_map(|s| s.fields.iter())
+    }
+}
+
+/// Top-level fields (`name: None`) or a named section such as `meta` / `fields`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParticleSection {
+    pub name: Option<Spanned<String>>,
+    pub fields: Vec<ParticleField>,
+    pub meta: Vec<MetaEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParticleField {
+    pub name: Spanned<String>,
+    pub ty: Spanned<FieldType>,
+    pub comment: Option<Spanned<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldType {
+    String,
+    Bool,
+    Float,
+    F32,
+    F64,
+    U32,
+    U64,
+    I64,
+    Enum(Vec<Spanned<String>>),
+    List(Box<FieldType>),
+}
+
+/// `key value` line in a `meta` section.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetaEntry {
+    pub key: Spanned<String>,
+    pub value: Spanned<String>,
+}
+
+// ---- metric / bound ----
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricDocument {
+    pub particle: Spanned<String>,
+    pub metrics: Vec<Metric>,
+    pub contracts: Vec<Contract>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricOp {
+    Max,
+    Min,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Metric {
+    pub name: Spanned<String>,
+    pub domain: Spanned<String>,
+    pub module: Spanned<String>,
+    pub op: Spanned<MetricOp>,
+    pub unit: Spanned<String>,
+    pub bound: Spanned<f64>,
+    pub evidence: Vec<Spanned<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contract {
+    pub name: Spanned<String>,
+    pub clauses: Vec<ContractClause>,
+}
+
+/// `keyword arg arg …`, e.g. `neurorights mental_privacy non_coercion`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractClause {
+    pub keyword: Spanned<String>,
+    pub args: Vec<Spanned<String>>,
+}
+
+// ---- CSV datashard ----
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvDocument {
+    /// Second line's first word, e.g. `QPU.Datashard`.
+    pub kind: Spanned<String>,
+    pub name: Spanned<String>,
+    pub header: Vec<Spanned<String>>,
+    pub rows: Vec<CsvRow>,
+}
+
+impl CsvDocument {
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.header.iter().position(|h| h.value == name)
+    }
+
+    pub fn cell<'a>(&self, row: &'a CsvRow, column: &str) -> Option<&'a str> {
+        Some(row.cells.get(self.column(column)?)?.value.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvRow {
+    /// Blank-line separated block the row belongs to, from 0.
+    pub group: u32,
+    pub cells: Vec<Spanned<String>>,
+    pub span: Span,
+}
@@ -0,0 +1,162 @@
+//! `aln` / `<kind> <name>` / header / rows datashards.
+
+use crate::ast::{CsvDocument, CsvRow};
+use crate::diag::Diagnostic;
+use crate::span::{lines, Line, Span, Spanned};
+
+pub(crate) fn parse(source: &str) -> (Option<CsvDocument>, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let all = lines(source);
+    let mut meaningful = all.iter().filter(|l| !l.text.trim().is_empty());
+
+    match meaningful.next() {
+        Some(l) if l.text.trim() == "aln" => {}
+        other => {
+            let span = other.map(|l| l.span()).unwrap_or_default();
+            diagnostics.push(Diagnostic::error("expected-aln", span, "datashard must start with `aln`"));
+            return (None, diagnostics);
+        }
+    }
+    let (kind, name) = match meaningful.next() {
+        Some(l) => match l.text.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [kind, name] => (
+                Spanned::new(kind.to_string(), l.span_of(kind)),
+                Spanned::new(name.to_string(), l.span_of(name)),
+            ),
+            _ => {
+                diagnostics.push(Diagnostic::error("expected-kind", l.span(), "expected `<kind> <name>`"));
+                return (None, diagnostics);
+            }
+        },
+        None => {
+            diagnostics.push(Diagnostic::error("expected-kind", eof(&all), "missing `<kind> <name>` line"));
+            return (None, diagnostics);
+        }
+    };
+    let Some(header_line) = meaningful.next() else {
+        diagnostics.push(Diagnostic::error("expected-header", eof(&all), "missing CSV header"));
+        return (None, diagnostics);
+    };
+    let header = cells(header_line, &mut diagnostics);
+    if header.iter().any(|h| h.value.is_empty()) {
+        diagnostics.push(Diagnostic::error("empty-column", header_line.span(), "header has an empty column name"));
+    }
+
+    let mut rows = Vec::new();
+    let mut group = 0;
+    let mut blank_before = false;
+    for line in all.iter().skip(header_line.no as usize) {
+        if line.text.trim().is_empty() {
+            blank_before = !rows.is_empty();
+            continue;
+        }
+        if blank_before {
+            group += 1;
+            blank_before = false;
+        }
+        let mut cells = cells(line, &mut diagnostics);
+        if cells.len() < header.len() {
+            diagnostics.push(Diagnostic::error(
+                "too-few-fields",
+                line.span(),
+                format!("row has {} fields, header has {}", cells.len(), header.len()),
+            ));
+        } else if cells.len() > header.len() {
+            diagnostics.push(Diagnostic::warning(
+                "too-many-fields",
+                cells[header.len()].span,
+                format!(
+                    "row has {} fields, header has {}; extra fields are joined into `{}`",
+                    cells.len(),
+                    header.len(),
+                    header.last().map(|h| h.value.as_str()).unwrap_or_default()
+                ),
+            ));
+            // Keep the raw tail, commas and spacing included.
+            let extra = cells.split_off(header.len() - 1);
+            let span = extra[0].span.to(extra[extra.len() - 1].span);
+            let raw = &line.text[span.start - line.start..span.end - line.start];
+            cells.push(Spanned::new(raw.trim().to_string(), span));
+        }
+        rows.push(CsvRow { group, cells, span: line.span() });
+    }
+
+    (Some(CsvDocument { kind, name, header, rows }), diagnostics)
+}
+
+/// Comma-separated cells; double-quoted cells may contain commas and `""`.
+fn cells(line: &Line<'_>, diagnostics: &mut Vec<Diagnostic>) -> Vec<Spanned<String>> {
+    let text = line.text;
+    let mut out = Vec::new();
+    let mut start = 0;
+    loop {
+        let rest = &text[start..];
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices().peekable();
+            let mut close = None;
+            while let Some((i, c)) = chars.next() {
+                if c == '"' {
+                    if chars.peek().is_some_and(|(_, c)| *c == '"') {
+                        chars.next();
+                        value.push('"');
+                    } else {
+                        close = Some(i);
+                        break;
+                    }
+                } else {
+                    value.push(c);
+                }
+            }
+            let Some(close) = close else {
+                diagnostics.push(Diagnostic::error("unterminated-string", line.span_of(rest), "unterminated quoted field"));
+                out.push(Spanned::new(value, line.span_of(rest)));
+                return out;
+            };
+            let end = start + 1 + close + 1;
+            out.push(Spanned::new(value, line.span_of(&text[start..end])));
+            match text[end..].chars().next() {
+                None => return out,
+                Some(',') => start = end + 1,
+                Some(_) => {
+                    diagnostics.push(Diagnostic::error(
+                        "expected-comma",
+                        line.span_of(&text[end..end + 1]),
+                        "expected `,` after quoted field",
+                    ));
+                    return out;
+                }
+            }
+        } else {
+            let end = rest.find(',').map_or(text.len(), |i| start + i);
+            let raw = &text[start..end];
+            out.push(Spanned::new(raw.trim().to_string(), line.span_of(raw)));
+            if end == text.len() {
+                return out;
+            }
+            start = end + 1;
+        }
+    }
+}
+
+fn eof(all: &[Line<'_>]) -> Span {
+    all.last().map(|l| l.span()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_quotes_and_extra_fields() {
+        let src = "aln\nK shard.aln\na,b,c\n1,\"x, y\",z\n\n2,3,4,5\n";
+        let (doc, diags) = parse(src);
+        let doc = doc.unwrap();
+        assert_eq!(doc.rows.len(), 2);
+        assert_eq!(doc.cell(&doc.rows[0], "b"), Some("x, y"));
+        assert_eq!(doc.rows[1].group, 1);
+        assert_eq!(doc.cell(&doc.rows[1], "c"), Some("4,5"));
+        let found: Vec<_> = diags.iter().map(|d| (d.code, d.span.line, d.span.col)).collect();
+        assert_eq!(found, [("too-many-fields", 6, 7)]);
+    }
+}
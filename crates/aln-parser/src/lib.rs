@@ -0,0 +1,93 @@
+//! Parser for the ALN shard files in this repository.
+//!
+//! Four syntaxes are in use and all are recognised from the file itself:
+//!
+//! * YAML-style `key: value` documents (`aln/*.aln`, `research/*.aln`);
+//! * `aln particle NAME … end` schema blocks (`specs/*.aln`) and the
+//!   sectioned `particle NAME` / `meta` / `fields` form (`logs/*.aln`);
+//! * `particle NAME` with `metric` bound and `contract` blocks (`specs/aln/*.aln`);
+//! * `aln` / `<kind> <name>` CSV datashards (`evolution/*.aln`).
+//!
+//! Every AST node carries a [`Span`], and problems are reported as
+//! [`Diagnostic`]s with a stable code rather than as a single error, so one
+//! pass reports everything wrong with a file. [`AlnWorkspace`] resolves
+//! `*_ref` entries across shards.
+
+mod ast;
+mod csv;
+mod diag;
+mod metric;
+mod particle;
+mod resolve;
+mod span;
+mod yaml;
+
+pub use ast::{
+    AlnDocument, AlnSyntax, Contract, ContractClause, CsvDocument, CsvRow, FieldType, MetaEntry, Metric,
+    MetricDocument, MetricOp, ParticleDocument, ParticleField, ParticleSection, Scalar, ScalarValue,
+    YamlDocument, YamlEntry, YamlNode,
+};
+pub use diag::{Diagnostic, Severity};
+pub use resolve::{references, AlnWorkspace, Reference, Resolution, ResolvedRef, Shard};
+pub use span::{Span, Spanned};
+
+/// Result of parsing one file. `document` is `None` only when the file is
+/// too malformed to produce any AST; otherwise it is returned alongside
+/// whatever diagnostics were found.
+#[derive(Clone, Debug)]
+pub struct Parsed {
+    pub document: Option<AlnDocument>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Parsed {
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(Diagnostic::is_error)
+    }
+}
+
+pub fn parse(source: &str) -> Parsed {
+    let (document, diagnostics) = match detect_syntax(source) {
+        AlnSyntax::Yaml => {
+            let (doc, diags) = yaml::parse(source);
+            (Some(AlnDocument::Yaml(doc)), diags)
+        }
+        AlnSyntax::Particle => {
+            let (doc, diags) = particle::parse(source);
+            (doc.map(AlnDocument::Particle), diags)
+        }
+        AlnSyntax::Metric => {
+            let (doc, diags) = metric::parse(source);
+            (doc.map(AlnDocument::Metric), diags)
+        }
+        AlnSyntax::Csv => {
+            let (doc, diags) = csv::parse(source);
+            (doc.map(AlnDocument::Csv), diags)
+        }
+    };
+    Parsed { document, diagnostics }
+}
+
+/// Syntax of `source`, from its first meaningful line.
+pub fn detect_syntax(source: &str) -> AlnSyntax {
+    let mut meaningful = source
+        .lines()
+        .map(str::trim_end)
+        .filter(|l| !l.trim().is_empty() && !l.trim_start().starts_with(['#', ';']));
+    let Some(first) = meaningful.next() else {
+        return AlnSyntax::Yaml;
+    };
+    if first == "aln" {
+        AlnSyntax::Csv
+    } else if first.starts_with("aln particle ") {
+        AlnSyntax::Particle
+    } else if first.starts_with("particle ") {
+        if meaningful.any(|l| l.starts_with("metric ")) {
+            AlnSyntax::Metric
+        } else {
+            AlnSyntax::Particle
+        }
+    } else {
+        AlnSyntax::Yaml
+    }
+}
@@ -0,0 +1,211 @@
+//! `particle NAME` followed by `metric NAME` bound blocks and `contract NAME!` blocks.
+
+use crate::ast::{Contract, ContractClause, Metric, MetricDocument, MetricOp};
+use crate::diag::Diagnostic;
+use crate::span::{lines, strip_comment, Line, Span, Spanned};
+
+const COMMENT_MARKERS: [char; 2] = ['#', ';'];
+const METRIC_KEYS: [&str; 6] = ["domain", "module", "op", "unit", "bound", "evidence"];
+
+/// One logical line: its words after `\` continuations are joined.
+struct Logical<'a> {
+    line: Line<'a>,
+    words: Vec<Spanned<&'a str>>,
+}
+
+pub(crate) fn parse(source: &str) -> (Option<MetricDocument>, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let logical = logical_lines(source);
+
+    let Some((header, rest)) = logical.split_first() else {
+        diagnostics.push(Diagnostic::error("empty", Span::default(), "empty metric shard"));
+        return (None, diagnostics);
+    };
+    let particle = match header.words.as_slice() {
+        [kw, name] if kw.value == "particle" => Spanned::new(name.value.to_string(), name.span),
+        _ => {
+            diagnostics.push(Diagnostic::error("expected-particle", header.line.span(), "expected `particle NAME`"));
+            return (None, diagnostics);
+        }
+    };
+
+    let mut doc = MetricDocument { particle, metrics: Vec::new(), contracts: Vec::new() };
+    let mut i = 0;
+    while i < rest.len() {
+        let head = &rest[i];
+        i += 1;
+        let body_start = i;
+        while rest.get(i).is_some_and(|l| l.line.indent() > head.line.indent()) {
+            i += 1;
+        }
+        let body = &rest[body_start..i];
+
+        match head.words.as_slice() {
+            [kw, name] if kw.value == "metric" => {
+                if let Some(metric) = metric(name, body, &mut diagnostics) {
+                    doc.metrics.push(metric);
+                }
+            }
+            [kw, name] if kw.value == "contract" => {
+                let clauses = body
+                    .iter()
+                    .map(|l| ContractClause {
+                        keyword: owned(&l.words[0]),
+                        args: l.words[1..].iter().map(owned).collect(),
+                    })
+                    .collect();
+                doc.contracts.push(Contract { name: owned(name), clauses });
+            }
+            _ => diagnostics.push(Diagnostic::error(
+                "expected-block",
+                head.line.span_of(head.line.text.trim()),
+                "expected `metric NAME` or `contract NAME`",
+            )),
+        }
+    }
+
+    let mut seen: Vec<&str> = Vec::new();
+    for metric in &doc.metrics {
+        if seen.contains(&metric.name.value.as_str()) {
+            diagnostics.push(Diagnostic::error(
+                "duplicate-metric",
+                metric.name.span,
+                format!("metric `{}` is defined twice", metric.name.value),
+            ));
+        }
+        seen.push(&metric.name.value);
+    }
+    (Some(doc), diagnostics)
+}
+
+fn metric(name: &Spanned<&str>, body: &[Logical<'_>], diagnostics: &mut Vec<Diagnostic>) -> Option<Metric> {
+    let mut values: [Option<&Logical<'_>>; 6] = Default::default();
+    for line in body {
+        let key = &line.words[0];
+        let Some(slot) = METRIC_KEYS.iter().position(|k| *k == key.value) else {
+            diagnostics.push(Diagnostic::error("unknown-key", key.span, format!("unknown metric key `{}`", key.value)));
+            continue;
+        };
+        if values[slot].is_some() {
+            diagnostics.push(Diagnostic::error("duplicate-key", key.span, format!("`{}` is given twice", key.value)));
+            continue;
+        }
+        if line.words.len() < 2 {
+            diagnostics.push(Diagnostic::error("missing-value", key.span, format!("`{}` has no value", key.value)));
+            continue;
+        }
+        values[slot] = Some(line);
+    }
+
+    let before = diagnostics.len();
+    let mut single = |slot: usize| -> Option<Spanned<&str>> {
+        let Some(line) = values[slot] else {
+            diagnostics.push(Diagnostic::error(
+                "missing-key",
+                name.span,
+                format!("metric `{}` has no `{}`", name.value, METRIC_KEYS[slot]),
+            ));
+            return None;
+        };
+        if let Some(extra) = line.words.get(2) {
+            diagnostics.push(Diagnostic::error(
+                "extra-value",
+                extra.span,
+                format!("`{}` takes one value", METRIC_KEYS[slot]),
+            ));
+        }
+        Some(line.words[1].clone())
+    };
+    let domain = single(0);
+    let module = single(1);
+    let op = single(2);
+    let unit = single(3);
+    let bound = single(4);
+
+    let op = op.and_then(|op| match op.value {
+        "max" => Some(Spanned::new(MetricOp::Max, op.span)),
+        "min" => Some(Spanned::new(MetricOp::Min, op.span)),
+        other => {
+            diagnostics.push(Diagnostic::error("invalid-op", op.span, format!("op must be `max` or `min`, not `{other}`")));
+            None
+        }
+    });
+    let bound = bound.and_then(|b| match b.value.parse::<f64>() {
+        Ok(v) if v.is_finite() => Some(Spanned::new(v, b.span)),
+        _ => {
+            diagnostics.push(Diagnostic::error("invalid-bound", b.span, format!("bound `{}` is not a number", b.value)));
+            None
+        }
+    });
+    let evidence = values[5].map(|l| l.words[1..].iter().map(owned).collect()).unwrap_or_default();
+
+    if diagnostics.len() > before {
+        return None;
+    }
+    Some(Metric {
+        name: owned(name),
+        domain: owned(&domain?),
+        module: owned(&module?),
+        op: op?,
+        unit: owned(&unit?),
+        bound: bound?,
+        evidence,
+    })
+}
+
+fn logical_lines(source: &str) -> Vec<Logical<'_>> {
+    let mut out: Vec<Logical<'_>> = Vec::new();
+    let mut continues = false;
+    for line in lines(source) {
+        let (content, _) = strip_comment(line.text, &COMMENT_MARKERS);
+        let (content, next_continues) = match content.strip_suffix('\\') {
+            Some(c) => (c, true),
+            None => (content, false),
+        };
+        let words = content.split_whitespace().map(|w| Spanned::new(w, line.span_of(w)));
+        match out.last_mut() {
+            Some(prev) if continues => prev.words.extend(words),
+            _ if content.trim().is_empty() => {}
+            _ => out.push(Logical { words: words.collect(), line }),
+        }
+        continues = next_continues;
+    }
+    out
+}
+
+fn owned(word: &Spanned<&str>) -> Spanned<String> {
+    Spanned::new(word.value.to_string(), word.span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metric_with_continued_evidence_and_contract() {
+        let src = "particle p.v1\n\nmetric m\n  domain d\n  module x\n  op max\n  unit scalar\n  bound 0.3\n  evidence 0x1 \\\n           0x2\n\ncontract c!\n  neurorights a b\n";
+        let (doc, diags) = parse(src);
+        assert!(diags.is_empty(), "{diags:?}");
+        let doc = doc.unwrap();
+        assert_eq!(doc.metrics[0].bound.value, 0.3);
+        assert_eq!(doc.metrics[0].evidence.len(), 2);
+        assert_eq!(doc.metrics[0].evidence[1].span.line, 10);
+        assert_eq!(doc.contracts[0].clauses[0].args.len(), 2);
+    }
+
+    #[test]
+    fn missing_and_invalid_keys() {
+        let (_, diags) = parse("particle p.v1\nmetric m\n  domain d\n  op most\n  bound x\n  colour red\n");
+        let found: Vec<_> = diags.iter().map(|d| (d.code, d.span.line, d.span.col)).collect();
+        assert_eq!(
+            found,
+            [
+                ("unknown-key", 6, 3),
+                ("missing-key", 2, 8),
+                ("missing-key", 2, 8),
+                ("invalid-op", 4, 6),
+                ("invalid-bound", 5, 9),
+            ]
+        );
+    }
+}
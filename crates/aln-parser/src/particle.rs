@@ -0,0 +1,243 @@
+//! `aln particle NAME … end` schema blocks and the sectioned
+//! `particle NAME` / `meta` / `fields` form.
+
+use crate::ast::{FieldType, MetaEntry, ParticleDocument, ParticleField, ParticleSection};
+use crate::diag::Diagnostic;
+use crate::span::{lines, strip_comment, Line, Span, Spanned};
+
+const COMMENT_MARKERS: [char; 2] = [';', '#'];
+
+pub(crate) fn parse(source: &str) -> (Option<ParticleDocument>, Vec<Diagnostic>) {
+    let all = lines(source);
+    let mut diagnostics = Vec::new();
+    // Comment-stripped, non-blank lines with their trailing comment.
+    let mut body: Vec<(Line<'_>, &str, Option<&str>)> = Vec::new();
+    let mut comment_depth = 0i32;
+    for line in &all {
+        if comment_depth > 0 {
+            // Continuation of a bracketed example inside the previous comment.
+            comment_depth += bracket_delta(line.text);
+            if let Some((_, _, Some(comment))) = body.last_mut() {
+                let prev_start = comment.as_ptr() as usize - source.as_ptr() as usize;
+                let end = line.start + line.text.len();
+                *comment = &source[prev_start..end];
+            }
+            continue;
+        }
+        let (content, comment) = strip_comment(line.text, &COMMENT_MARKERS);
+        if let Some(comment) = comment {
+            comment_depth = bracket_delta(comment).max(0);
+        }
+        if !content.trim().is_empty() {
+            body.push((line.clone(), content, comment));
+        }
+    }
+
+    let Some(((header_line, header, _), rest)) = body.split_first() else {
+        diagnostics.push(Diagnostic::error("empty", Span::default(), "empty particle shard"));
+        return (None, diagnostics);
+    };
+    let words: Vec<&str> = header.split_whitespace().collect();
+    let (block, name) = match words.as_slice() {
+        ["aln", "particle", name] => (true, *name),
+        ["particle", name] => (false, *name),
+        _ => {
+            diagnostics.push(Diagnostic::error(
+                "expected-particle",
+                header_line.span_of(header.trim()),
+                "expected `aln particle NAME` or `particle NAME`",
+            ));
+            return (None, diagnostics);
+        }
+    };
+    let name = Spanned::new(name.to_string(), header_line.span_of(name));
+
+    let mut doc = ParticleDocument {
+        name,
+        block,
+        sections: vec![ParticleSection { name: None, fields: Vec::new(), meta: Vec::new() }],
+        invariants: Vec::new(),
+    };
+    let mut ended = false;
+    let mut i = 0;
+    while i < rest.len() {
+        let (line, content, comment) = &rest[i];
+        let text = content.trim();
+        let words: Vec<&str> = text.split_whitespace().collect();
+        i += 1;
+
+        if ended {
+            diagnostics.push(Diagnostic::error("after-end", line.span_of(text), "content after `end`"));
+            continue;
+        }
+        if text == "end" {
+            if !block {
+                diagnostics.push(Diagnostic::error("unexpected-end", line.span_of(text), "`end` closes only `aln particle` blocks"));
+            }
+            ended = true;
+            continue;
+        }
+        if text == "invariant" {
+            match invariant_text(source, rest.get(i)) {
+                Some((value, span)) => {
+                    doc.invariants.push(Spanned::new(value, span));
+                    // Skip the physical lines the quoted text covered.
+                    while rest.get(i).is_some_and(|(l, _, _)| l.start < span.end) {
+                        i += 1;
+                    }
+                }
+                None => diagnostics.push(Diagnostic::error(
+                    "expected-invariant",
+                    line.span_of(text),
+                    "`invariant` must be followed by a quoted statement",
+                )),
+            }
+            continue;
+        }
+        if let Some(variant) = text.strip_prefix("- ") {
+            let variant = variant.trim();
+            let last = doc.sections.last_mut().and_then(|s| s.fields.last_mut());
+            match last.map(|f| &mut f.ty.value) {
+                Some(FieldType::Enum(variants)) => variants.push(Spanned::new(variant.to_string(), line.span_of(variant))),
+                _ => diagnostics.push(Diagnostic::error(
+                    "stray-variant",
+                    line.span_of(text),
+                    "`- variant` lines must follow an `enum` field",
+                )),
+            }
+            continue;
+        }
+
+        // A lone word whose next line is deeper opens a section.
+        let next_deeper = rest.get(i).is_some_and(|(l, _, _)| l.indent() > line.indent());
+        if words.len() == 1 && next_deeper && comment.is_none() {
+            doc.sections.push(ParticleSection {
+                name: Some(Spanned::new(text.to_string(), line.span_of(text))),
+                fields: Vec::new(),
+                meta: Vec::new(),
+            });
+            continue;
+        }
+
+        let name = words[0];
+        if !is_identifier(name) {
+            diagnostics.push(Diagnostic::error("expected-field", line.span_of(name), format!("`{name}` is not a field name")));
+            continue;
+        }
+        let name = Spanned::new(name.to_string(), line.span_of(name));
+        let section = doc.sections.last_mut().expect("root section");
+        let in_meta = section.name.as_ref().is_some_and(|n| n.value == "meta");
+        let after_name = text[words[0].len()..].trim();
+
+        if in_meta {
+            let value = after_name.trim_matches('"');
+            section.meta.push(MetaEntry { key: name, value: Spanned::new(value.to_string(), line.span_of(after_name)) });
+            continue;
+        }
+        if after_name.is_empty() {
+            diagnostics.push(Diagnostic::error("missing-type", name.span, format!("field `{}` has no type", name.value)));
+            continue;
+        }
+        let ty_span = line.span_of(after_name);
+        let Some(ty) = parse_type(&words[1..]) else {
+            diagnostics.push(Diagnostic::error("unknown-type", ty_span, format!("unknown field type `{after_name}`")));
+            continue;
+        };
+        let comment = comment.map(|c| {
+            let text = c.trim_start_matches(COMMENT_MARKERS).trim();
+            let start = c.as_ptr() as usize - source.as_ptr() as usize;
+            Spanned::new(text.to_string(), span_in(&all, start, c.len()))
+        });
+        section.fields.push(ParticleField { name, ty: Spanned::new(ty, ty_span), comment });
+    }
+
+    if block && !ended {
+        let eof = all.last().map(|l| l.span()).unwrap_or_default();
+        diagnostics.push(Diagnostic::error("missing-end", eof, format!("`aln particle {}` is missing `end`", doc.name.value)));
+    }
+    for field in doc.fields() {
+        if matches!(&field.ty.value, FieldType::Enum(v) if v.is_empty()) {
+            diagnostics.push(Diagnostic::error("empty-enum", field.ty.span, format!("enum field `{}` lists no variants", field.name.value)));
+        }
+    }
+    doc.sections.retain(|s| s.name.is_some() || !s.fields.is_empty() || !s.meta.is_empty());
+    (Some(doc), diagnostics)
+}
+
+fn parse_type(words: &[&str]) -> Option<FieldType> {
+    match words {
+        ["string"] => Some(FieldType::String),
+        ["bool"] => Some(FieldType::Bool),
+        ["float"] => Some(FieldType::Float),
+        ["f32"] => Some(FieldType::F32),
+        ["f64"] => Some(FieldType::F64),
+        ["uint32" | "u32"] => Some(FieldType::U32),
+        ["uint64" | "u64"] => Some(FieldType::U64),
+        ["int64" | "i64" | "int"] => Some(FieldType::I64),
+        ["enum"] => Some(FieldType::Enum(Vec::new())),
+        ["list", inner @ ..] if !inner.is_empty() => Some(FieldType::List(Box::new(parse_type(inner)?))),
+        _ => None,
+    }
+}
+
+/// The quoted text starting on `line`, which may span several lines.
+fn invariant_text(source: &str, line: Option<&(Line<'_>, &str, Option<&str>)>) -> Option<(String, Span)> {
+    let (line, content, _) = line?;
+    let open = content.trim_start();
+    if !open.starts_with('"') {
+        return None;
+    }
+    let start = open.as_ptr() as usize - source.as_ptr() as usize;
+    let close = source[start + 1..].find('"')? + start + 1;
+    let text = source[start + 1..close]
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let span = Span { start, end: close + 1, line: line.no, col: (start - line.start) as u32 + 1 };
+    Some((text, span))
+}
+
+fn span_in(lines: &[Line<'_>], start: usize, len: usize) -> Span {
+    let line = lines.iter().rev().find(|l| l.start <= start).expect("offset inside source");
+    Span::at(line.no, line.start, start - line.start, len)
+}
+
+fn bracket_delta(text: &str) -> i32 {
+    text.chars().map(|c| match c {
+        '[' => 1,
+        ']' => -1,
+        _ => 0,
+    })
+    .sum()
+}
+
+fn is_identifier(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_with_enum_list_and_invariant() {
+        let src = "aln particle demo.v1\n  id string ; e.g. \"v1\"\n  mode enum\n    - a\n    - b\n  stack list string ; [\"x\",\n     \"y\"]\n  invariant\n    \"Two\n     lines.\"\nend\n";
+        let (doc, diags) = parse(src);
+        assert!(diags.is_empty(), "{diags:?}");
+        let doc = doc.unwrap();
+        let fields: Vec<_> = doc.fields().collect();
+        assert_eq!(fields.len(), 3);
+        assert!(matches!(&fields[1].ty.value, FieldType::Enum(v) if v.len() == 2));
+        assert_eq!(fields[2].ty.value, FieldType::List(Box::new(FieldType::String)));
+        assert_eq!(doc.invariants[0].value, "Two lines.");
+    }
+
+    #[test]
+    fn unknown_type_and_missing_end() {
+        let (_, diags) = parse("aln particle demo.v1\n  x strng\n");
+        let found: Vec<_> = diags.iter().map(|d| (d.code, d.span.line, d.span.col)).collect();
+        assert_eq!(found, [("unknown-type", 2, 5), ("missing-end", 2, 1)]);
+    }
+}
@@ -0,0 +1,262 @@
+//! Cross-shard `*_ref` resolution over a set of parsed shards.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::ast::{AlnDocument, YamlEntry, YamlNode};
+use crate::diag::Diagnostic;
+use crate::span::Spanned;
+use crate::{parse, Parsed};
+
+/// One parsed `.aln` file.
+#[derive(Clone, Debug)]
+pub struct Shard {
+    pub path: PathBuf,
+    pub parsed: Parsed,
+}
+
+impl Shard {
+    /// File name without the `.aln` extension.
+    pub fn stem(&self) -> &str {
+        let name = self.path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        name.strip_suffix(".aln").unwrap_or(name)
+    }
+}
+
+/// A `*_ref` occurrence: `target` names a shard, optionally followed by
+/// `:path` into it (`asset.chat.stake.v1:min_stake.council`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reference {
+    pub key: Spanned<String>,
+    pub target: Spanned<String>,
+    pub path: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Resolution {
+    /// Index of the target shard in [`AlnWorkspace::shards`].
+    Resolved(usize),
+    /// The shard exists but does not contain the referenced path.
+    MissingPath(usize),
+    /// No shard in the workspace matches the target.
+    Unresolved,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolvedRef {
+    /// Index of the referring shard.
+    pub from: usize,
+    pub reference: Reference,
+    pub resolution: Resolution,
+}
+
+impl ResolvedRef {
+    /// Missing paths are errors; unknown shards are warnings, since
+    /// several refs name shards kept outside this repository.
+    pub fn diagnostic(&self) -> Option<Diagnostic> {
+        let r = &self.reference;
+        match self.resolution {
+            Resolution::Resolved(_) => None,
+            Resolution::MissingPath(_) => Some(Diagnostic::error(
+                "ref-path-not-found",
+                r.target.span,
+                format!(
+                    "`{}` has no `{}`",
+                    r.target.value.split(':').next().unwrap_or_default(),
+                    r.path.as_deref().unwrap_or_default()
+                ),
+            )),
+            Resolution::Unresolved => Some(Diagnostic::warning(
+                "unresolved-ref",
+                r.target.span,
+                format!("`{}` does not match any shard", r.target.value),
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AlnWorkspace {
+    shards: Vec<Shard>,
+}
+
+impl AlnWorkspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, path: impl Into<PathBuf>, source: &str) -> &Shard {
+        self.shards.push(Shard { path: path.into(), parsed: parse(source) });
+        self.shards.last().expect("just pushed")
+    }
+
+    /// Parse every `.aln` file under `root`, skipping `target` and hidden
+    /// directories. Paths are stored relative to `root`, in sorted order.
+    pub fn load_dir(root: &Path) -> io::Result<Self> {
+        let mut files = Vec::new();
+        collect(root, &mut files)?;
+        files.sort();
+        let mut workspace = Self::new();
+        for file in files {
+            let source = fs::read_to_string(&file)?;
+            let rel = file.strip_prefix(root).unwrap_or(&file).to_path_buf();
+            workspace.add(rel, &source);
+        }
+        Ok(workspace)
+    }
+
+    pub fn shards(&self) -> &[Shard] {
+        &self.shards
+    }
+
+    /// Shard named by `target`: its id, its file name (with or without
+    /// `.aln`), or, for targets with a `/`, a suffix of its path.
+    pub fn find(&self, target: &str) -> Option<usize> {
+        let id = target.strip_suffix(".aln").unwrap_or(target);
+        self.shards.iter().position(|shard| {
+            let doc_id = shard.parsed.document.as_ref().and_then(|d| d.id());
+            doc_id.is_some_and(|d| d.value == id)
+                || shard.stem() == id
+                || (target.contains('/') && shard.path.ends_with(target))
+        })
+    }
+
+    /// Every reference in every shard, with how it resolved.
+    pub fn resolve(&self) -> Vec<ResolvedRef> {
+        let mut out = Vec::new();
+        for (from, shard) in self.shards.iter().enumerate() {
+            let Some(doc) = &shard.parsed.document else {
+                continue;
+            };
+            for reference in references(doc) {
+                let resolution = match self.find(&reference.target.value) {
+                    None => Resolution::Unresolved,
+                    Some(i) => match &reference.path {
+                        Some(path) if !self.has_path(i, path) => Resolution::MissingPath(i),
+                        _ => Resolution::Resolved(i),
+                    },
+                };
+                out.push(ResolvedRef { from, reference, resolution });
+            }
+        }
+        out
+    }
+
+    /// YAML shards are searched from the root and then under `fields`;
+    /// other syntaxes match a field name.
+    fn has_path(&self, shard: usize, path: &str) -> bool {
+        match &self.shards[shard].parsed.document {
+            Some(AlnDocument::Yaml(doc)) => doc.get(path).is_some() || doc.get(&format!("fields.{path}")).is_some(),
+            Some(AlnDocument::Particle(doc)) => doc.fields().any(|f| f.name.value == path),
+            _ => false,
+        }
+    }
+}
+
+/// References declared by one document.
+///
+/// YAML keys ending in `_ref` hold a target, a list of targets, or a map
+/// with a `shard` key. Particle fields ending in `_ref` name their target
+/// in the trailing comment (`; -> cybostate.factor.citizen.v1`).
+pub fn references(doc: &AlnDocument) -> Vec<Reference> {
+    let mut out = Vec::new();
+    match doc {
+        AlnDocument::Yaml(doc) => yaml_refs(&doc.root, &mut out),
+        AlnDocument::Particle(doc) => {
+            for field in doc.fields().filter(|f| f.name.value.ends_with("_ref")) {
+                let Some(comment) = &field.comment else {
+                    continue;
+                };
+                let Some(target) = comment.value.split_whitespace().find(|w| looks_like_id(w)) else {
+                    continue;
+                };
+                out.push(Reference {
+                    key: field.name.clone(),
+                    target: Spanned::new(target.to_string(), comment.span),
+                    path: None,
+                });
+            }
+        }
+        AlnDocument::Metric(_) | AlnDocument::Csv(_) => {}
+    }
+    out
+}
+
+fn yaml_refs(entries: &[YamlEntry], out: &mut Vec<Reference>) {
+    for entry in entries {
+        if entry.key.value.ends_with("_ref") {
+            let targets: Vec<&YamlNode> = match &entry.value {
+                YamlNode::Seq { items, .. } => items.iter().collect(),
+                YamlNode::Map { entries, .. } => entries.iter().filter(|e| e.key.value == "shard").map(|e| &e.value).collect(),
+                scalar => vec![scalar],
+            };
+            for node in targets {
+                if let YamlNode::Scalar(s) = node {
+                    if let Some(raw) = s.as_str() {
+                        let (target, path) = match raw.split_once(':') {
+                            Some((t, p)) => (t, Some(p.to_string())),
+                            None => (raw, None),
+                        };
+                        out.push(Reference {
+                            key: entry.key.clone(),
+                            target: Spanned::new(target.to_string(), s.span),
+                            path,
+                        });
+                    }
+                }
+            }
+        }
+        match &entry.value {
+            YamlNode::Map { entries, .. } => yaml_refs(entries, out),
+            YamlNode::Seq { items, .. } => {
+                for item in items {
+                    if let YamlNode::Map { entries, .. } = item {
+                        yaml_refs(entries, out);
+                    }
+                }
+            }
+            YamlNode::Scalar(_) => {}
+        }
+    }
+}
+
+/// `a.b.vN`-shaped shard identifiers.
+fn looks_like_id(word: &str) -> bool {
+    let parts: Vec<&str> = word.split('.').collect();
+    parts.len() >= 2
+        && parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+        && parts.last().is_some_and(|v| v.starts_with('v') && v[1..].chars().all(|c| c.is_ascii_digit()) && v.len() > 1)
+}
+
+fn collect(dir: &Path, out: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if path.is_dir() {
+            if name != "target" && !name.starts_with('.') {
+                collect(&path, out)?;
+            }
+        } else if name.ends_with(".aln") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_path_into_fields_and_reports_missing() {
+        let mut ws = AlnWorkspace::new();
+        ws.add("aln/asset.aln", "id: asset.v1\nfields:\n  min_stake:\n    council: \"10\"\n");
+        ws.add("aln/gov.aln", "id: gov.v1\nroles:\n  - ok_ref: asset.v1:min_stake.council\n    bad_ref: asset.v1:min_stake.chair\n    far_ref: elsewhere.v1\n");
+        let resolved = ws.resolve();
+        let outcomes: Vec<_> = resolved.iter().map(|r| r.resolution.clone()).collect();
+        assert_eq!(outcomes, [Resolution::Resolved(0), Resolution::MissingPath(0), Resolution::Unresolved]);
+        let diag = resolved[1].diagnostic().unwrap();
+        assert_eq!((diag.code, diag.span.line, diag.span.col), ("ref-path-not-found", 4, 14));
+    }
+}
@@ -0,0 +1,96 @@
+/// Byte range in the source plus the 1-based line and column of its start.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: u32,
+    pub col: u32,
+}
+
+impl Span {
+    /// `len` bytes starting at byte column `col0` of the line beginning at `line_start`.
+    pub fn at(line: u32, line_start: usize, col0: usize, len: usize) -> Self {
+        Span {
+            start: line_start + col0,
+            end: line_start + col0 + len,
+            line,
+            col: col0 as u32 + 1,
+        }
+    }
+
+    /// From the start of `self` to the end of `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span { end: other.end.max(self.end), ..self }
+    }
+}
+
+/// A value with the span it was parsed from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(value: T, span: Span) -> Self {
+        Spanned { value, span }
+    }
+}
+
+/// One physical source line.
+#[derive(Clone, Debug)]
+pub(crate) struct Line<'a> {
+    pub no: u32,
+    pub start: usize,
+    pub text: &'a str,
+}
+
+impl Line<'_> {
+    pub fn indent(&self) -> usize {
+        self.text.len() - self.text.trim_start_matches(' ').len()
+    }
+
+    /// Span of `part`, which must be a subslice of `self.text`.
+    pub fn span_of(&self, part: &str) -> Span {
+        let col0 = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        Span::at(self.no, self.start, col0, part.len())
+    }
+
+    pub fn span(&self) -> Span {
+        self.span_of(self.text)
+    }
+}
+
+pub(crate) fn lines(source: &str) -> Vec<Line<'_>> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    for (i, raw) in source.split('\n').enumerate() {
+        out.push(Line {
+            no: i as u32 + 1,
+            start,
+            text: raw.strip_suffix('\r').unwrap_or(raw),
+        });
+        start += raw.len() + 1;
+    }
+    // A trailing newline does not start another line.
+    if source.ends_with('\n') {
+        out.pop();
+    }
+    out
+}
+
+/// Cut a trailing comment introduced by any of `markers`, which must sit at
+/// the start of the line or after whitespace, outside double quotes.
+pub(crate) fn strip_comment<'a>(text: &'a str, markers: &[char]) -> (&'a str, Option<&'a str>) {
+    let mut in_quotes = false;
+    let mut prev_space = true;
+    for (i, c) in text.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if !in_quotes && prev_space && markers.contains(&c) {
+            return (text[..i].trim_end(), Some(&text[i..]));
+        }
+        prev_space = c.is_whitespace();
+    }
+    (text.trim_end(), None)
+}
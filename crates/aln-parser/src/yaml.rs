@@ -0,0 +1,369 @@
+//! The YAML subset ALN shards use: block mappings and sequences by
+//! indentation, `|` / `>` block scalars, single-line flow sequences, quoted
+//! and plain scalars, `#` comments. Anchors, tags, flow mappings and
+//! multi-document streams are rejected with a diagnostic.
+
+use crate::ast::{Scalar, ScalarValue, YamlDocument, YamlEntry, YamlNode};
+use crate::diag::Diagnostic;
+use crate::span::{lines, strip_comment, Line, Span, Spanned};
+
+/// A non-blank line after comment removal. `indent` / `content` are
+/// rewritten in place when a `- key: value` item opens a mapping.
+struct Item<'a> {
+    line: Line<'a>,
+    /// Index into the physical lines, for block scalars.
+    index: usize,
+    indent: usize,
+    content: &'a str,
+}
+
+struct Parser<'a> {
+    physical: Vec<Line<'a>>,
+    items: Vec<Item<'a>>,
+    pos: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+pub(crate) fn parse(source: &str) -> (YamlDocument, Vec<Diagnostic>) {
+    let physical = lines(source);
+    let mut diagnostics = Vec::new();
+    let mut items = Vec::new();
+    for (index, line) in physical.iter().enumerate() {
+        if let Some(col) = line.text.find('\t').filter(|c| *c < line.indent() + 1) {
+            diagnostics.push(Diagnostic::error(
+                "tab-indent",
+                Span::at(line.no, line.start, col, 1),
+                "tabs are not allowed in indentation",
+            ));
+        }
+        let (content, _) = strip_comment(line.text, &['#']);
+        let content = content.trim_start();
+        if content.is_empty() {
+            continue;
+        }
+        items.push(Item { line: line.clone(), index, indent: line.indent(), content });
+    }
+
+    let mut parser = Parser { physical, items, pos: 0, diagnostics };
+    let mut root = Vec::new();
+    while parser.pos < parser.items.len() {
+        let indent = parser.items[parser.pos].indent;
+        if indent != 0 {
+            let span = parser.items[parser.pos].line.span_of(parser.items[parser.pos].content);
+            parser.error("unexpected-indent", span, "unexpected indentation at top level");
+            parser.pos += 1;
+            continue;
+        }
+        if parser.is_seq_item() {
+            let span = parser.items[parser.pos].line.span_of(parser.items[parser.pos].content);
+            parser.error("expected-mapping", span, "a shard must be a mapping at top level");
+            parser.skip_deeper_than(0);
+            continue;
+        }
+        root.extend(parser.mapping(0));
+    }
+    (YamlDocument { root }, parser.diagnostics)
+}
+
+impl<'a> Parser<'a> {
+    fn error(&mut self, code: &'static str, span: Span, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic::error(code, span, message));
+    }
+
+    fn current(&self) -> Option<&Item<'a>> {
+        self.items.get(self.pos)
+    }
+
+    fn is_seq_item(&self) -> bool {
+        self.current().is_some_and(|i| i.content == "-" || i.content.starts_with("- "))
+    }
+
+    fn skip_deeper_than(&mut self, indent: usize) {
+        self.pos += 1;
+        while self.current().is_some_and(|i| i.indent > indent) {
+            self.pos += 1;
+        }
+    }
+
+    fn mapping(&mut self, indent: usize) -> Vec<YamlEntry> {
+        let mut entries = Vec::new();
+        while let Some(item) = self.current() {
+            if item.indent < indent || (item.indent == indent && self.is_seq_item()) {
+                break;
+            }
+            let (line, content, item_indent) = (item.line.clone(), item.content, item.indent);
+            if item_indent > indent {
+                self.error("unexpected-indent", line.span_of(content), "unexpected indentation");
+                self.skip_deeper_than(indent);
+                continue;
+            }
+            let Some((key, rest)) = split_key(content) else {
+                self.error("expected-key", line.span_of(content), "expected `key: value`");
+                self.skip_deeper_than(indent);
+                continue;
+            };
+            let key = Spanned::new(unquote(key), line.span_of(key));
+            self.pos += 1;
+            let value = self.value_after_key(indent, &line, rest);
+            entries.push(YamlEntry { key, value });
+        }
+        entries
+    }
+
+    /// The value of a `key:` whose inline remainder is `rest`.
+    fn value_after_key(&mut self, indent: usize, line: &Line<'a>, rest: &'a str) -> YamlNode {
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return match self.current() {
+                Some(next) if next.indent > indent => {
+                    let child = next.indent;
+                    self.block(child)
+                }
+                // `key:` followed by `- item` at the same indentation.
+                Some(next) if next.indent == indent && self.is_seq_item() => self.sequence(indent),
+                _ => YamlNode::Scalar(Scalar { value: ScalarValue::Null, span: line.span_of(rest) }),
+            };
+        }
+        if matches!(rest.trim_end_matches(['-', '+']), "|" | ">") {
+            return self.block_scalar(indent, line, rest);
+        }
+        self.inline(line, rest)
+    }
+
+    fn block(&mut self, indent: usize) -> YamlNode {
+        if self.is_seq_item() {
+            self.sequence(indent)
+        } else {
+            let start = self.current().map(|i| i.line.span_of(i.content)).unwrap_or_default();
+            let entries = self.mapping(indent);
+            let span = entries.last().map_or(start, |e| start.to(e.value.span()));
+            YamlNode::Map { entries, span }
+        }
+    }
+
+    fn sequence(&mut self, indent: usize) -> YamlNode {
+        let start = self.current().map(|i| i.line.span_of(i.content)).unwrap_or_default();
+        let mut items = Vec::new();
+        while self.current().is_some_and(|i| i.indent == indent) && self.is_seq_item() {
+            let (line, content) = (self.items[self.pos].line.clone(), self.items[self.pos].content);
+            let rest = content[1..].trim_start();
+            if rest.is_empty() {
+                self.pos += 1;
+                match self.current() {
+                    Some(next) if next.indent > indent => {
+                        let child = next.indent;
+                        items.push(self.block(child));
+                    }
+                    _ => items.push(YamlNode::Scalar(Scalar { value: ScalarValue::Null, span: line.span_of(&content[..1]) })),
+                }
+            } else if split_key(rest).is_some() && !rest.starts_with(['"', '\'', '[']) {
+                // `- key: value` opens a mapping at the column of `key`.
+                let col = rest.as_ptr() as usize - line.text.as_ptr() as usize;
+                let item = &mut self.items[self.pos];
+                item.indent = col;
+                item.content = rest;
+                items.push(self.block(col));
+            } else {
+                self.pos += 1;
+                items.push(self.inline(&line, rest));
+            }
+        }
+        let span = items.last().map_or(start, |i| start.to(i.span()));
+        YamlNode::Seq { items, span }
+    }
+
+    fn block_scalar(&mut self, indent: usize, line: &Line<'a>, header: &str) -> YamlNode {
+        let folded = header.starts_with('>');
+        let keep_trailing = header.ends_with('+');
+        let strip_trailing = header.ends_with('-');
+
+        // Every physical line deeper than the key (or blank) belongs to the scalar.
+        let first = line.no as usize; // index of the next physical line
+        let mut end = first;
+        while let Some(next) = self.physical.get(end) {
+            if !next.text.trim().is_empty() && next.indent() <= indent {
+                break;
+            }
+            end += 1;
+        }
+        let body = &self.physical[first..end];
+        let block_indent = body.iter().find(|l| !l.text.trim().is_empty()).map_or(0, |l| l.indent());
+
+        let mut text = String::new();
+        for (i, l) in body.iter().enumerate() {
+            let content = l.text.get(block_indent..).unwrap_or("");
+            if folded && i > 0 && !content.is_empty() && !text.ends_with('\n') {
+                text.push(' ');
+            } else if i > 0 {
+                text.push('\n');
+            }
+            text.push_str(content);
+        }
+        let trimmed = text.trim_end_matches('\n').len();
+        if !keep_trailing {
+            text.truncate(trimmed);
+            if !strip_trailing {
+                text.push('\n');
+            }
+        }
+
+        // Skip the logical items the scalar swallowed.
+        while self.current().is_some_and(|i| i.index < end) {
+            self.pos += 1;
+        }
+        let span = match body.last() {
+            Some(last) => line.span_of(header).to(last.span()),
+            None => line.span_of(header),
+        };
+        YamlNode::Scalar(Scalar { value: ScalarValue::Str(text), span })
+    }
+
+    /// A scalar or single-line flow sequence on the current line.
+    fn inline(&mut self, line: &Line<'a>, text: &'a str) -> YamlNode {
+        let span = line.span_of(text);
+        if let Some(inner) = text.strip_prefix('[') {
+            let Some(inner) = inner.strip_suffix(']') else {
+                self.error("unclosed-flow", span, "flow sequence must close on the same line");
+                return YamlNode::Seq { items: Vec::new(), span };
+            };
+            let items = split_flow(inner)
+                .into_iter()
+                .map(|part| YamlNode::Scalar(self.scalar(line, part)))
+                .collect();
+            return YamlNode::Seq { items, span };
+        }
+        if text.starts_with('{') {
+            self.error("unsupported", span, "flow mappings are not supported in ALN shards");
+        } else if text.starts_with(['&', '*', '!']) {
+            self.error("unsupported", span, "anchors, aliases and tags are not supported in ALN shards");
+        }
+        YamlNode::Scalar(self.scalar(line, text))
+    }
+
+    fn scalar(&mut self, line: &Line<'a>, text: &'a str) -> Scalar {
+        let span = line.span_of(text);
+        let quote = text.chars().next().filter(|c| *c == '"' || *c == '\'');
+        let value = match quote {
+            Some(q) if text.len() >= 2 && text.ends_with(q) => ScalarValue::Str(unquote(text)),
+            Some(_) => {
+                self.error("unterminated-string", span, "unterminated quoted string");
+                ScalarValue::Str(text[1..].to_string())
+            }
+            None => plain_scalar(text),
+        };
+        Scalar { value, span }
+    }
+}
+
+/// `key` and the text after `key:`, when the line is a mapping entry.
+fn split_key(content: &str) -> Option<(&str, &str)> {
+    let mut in_quotes = None;
+    for (i, c) in content.char_indices() {
+        match (c, in_quotes) {
+            ('"' | '\'', None) if i == 0 => in_quotes = Some(c),
+            (q, Some(open)) if q == open => in_quotes = None,
+            (':', None) => {
+                let rest = &content[i + 1..];
+                if rest.is_empty() || rest.starts_with(' ') {
+                    let key = content[..i].trim_end();
+                    return (!key.is_empty()).then_some((key, rest));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn split_flow(inner: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut start, mut in_quotes) = (0, None);
+    for (i, c) in inner.char_indices() {
+        match (c, in_quotes) {
+            ('"' | '\'', None) => in_quotes = Some(c),
+            (q, Some(open)) if q == open => in_quotes = None,
+            (',', None) => {
+                parts.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = inner[start..].trim();
+    if !last.is_empty() || !parts.is_empty() {
+        parts.push(last);
+    }
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+fn unquote(text: &str) -> String {
+    if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+        let mut out = String::new();
+        let mut chars = text[1..text.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some(other) => out.push(other),
+                None => out.push('\\'),
+            }
+        }
+        out
+    } else if text.len() >= 2 && text.starts_with('\'') && text.ends_with('\'') {
+        text[1..text.len() - 1].replace("''", "'")
+    } else {
+        text.to_string()
+    }
+}
+
+fn plain_scalar(text: &str) -> ScalarValue {
+    match text {
+        "~" | "null" => return ScalarValue::Null,
+        "true" => return ScalarValue::Bool(true),
+        "false" => return ScalarValue::Bool(false),
+        _ => {}
+    }
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    if digits.starts_with(|c: char| c.is_ascii_digit()) && !digits.starts_with("0x") {
+        if let Ok(i) = text.parse::<i64>() {
+            return ScalarValue::Int(i);
+        }
+        if let Ok(f) = text.parse::<f64>() {
+            return ScalarValue::Float(f);
+        }
+    }
+    ScalarValue::Str(text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_maps_sequences_and_block_scalars() {
+        let src = "id: demo.v1\nroles:\n  council:\n    permissions:\n      - a.b\n      - c.d\nnote: >\n  folded\n  text\nitems:\n- name: x\n  size: 2\nflags: [ \"A\", 'B' ]\n";
+        let (doc, diags) = parse(src);
+        assert!(diags.is_empty(), "{diags:?}");
+        assert_eq!(doc.id().unwrap().value, "demo.v1");
+
+        let Some(YamlNode::Seq { items, .. }) = doc.get("roles.council.permissions") else { panic!() };
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].span().line, 6);
+        assert!(matches!(doc.get("note"), Some(YamlNode::Scalar(Scalar { value: ScalarValue::Str(s), .. })) if s == "folded text\n"));
+        let Some(YamlNode::Seq { items, .. }) = doc.get("items") else { panic!() };
+        assert!(matches!(&items[0], YamlNode::Map { entries, .. } if entries.len() == 2));
+        assert!(matches!(doc.get("flags"), Some(YamlNode::Seq { items, .. }) if items.len() == 2));
+    }
+
+    #[test]
+    fn reports_precise_locations() {
+        let (_, diags) = parse("a: 1\n   b: 2\nc \"oops\nd: [1, 2\n");
+        let found: Vec<_> = diags.iter().map(|d| (d.code, d.span.line, d.span.col)).collect();
+        assert_eq!(found, [("unexpected-indent", 2, 4), ("expected-key", 3, 1), ("unclosed-flow", 4, 4)]);
+    }
+}
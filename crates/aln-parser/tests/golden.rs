@@ -0,0 +1,201 @@
+//! Parses every `.aln` file in the repository and compares an outline of
+//! its AST plus diagnostics against `tests/golden/`. Run with
+//! `UPDATE_GOLDEN=1` to rewrite the expected files after an intended change.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aln_parser::{AlnDocument, AlnWorkspace, FieldType, Resolution, ScalarValue, Shard, YamlEntry, YamlNode};
+
+fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn check(name: &str, actual: &str) -> Option<String> {
+    let path = golden_dir().join(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(golden_dir()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return None;
+    }
+    match fs::read_to_string(&path) {
+        Ok(expected) if expected == actual => None,
+        Ok(_) => Some(format!("{name}: output differs from {}", path.display())),
+        Err(_) => Some(format!("{name}: missing {}", path.display())),
+    }
+}
+
+#[test]
+fn every_repository_shard_matches_its_golden() {
+    let workspace = AlnWorkspace::load_dir(&repo_root()).unwrap();
+    assert!(workspace.shards().len() >= 12, "expected the repository's .aln files");
+
+    let mut failures = Vec::new();
+    for shard in workspace.shards() {
+        let name = shard.path.to_string_lossy().replace(['/', '\\'], "__");
+        failures.extend(check(&format!("{name}.golden"), &outline(shard)));
+    }
+    failures.extend(check("workspace.refs.golden", &refs(&workspace)));
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn repository_shards_parse_without_errors() {
+    let workspace = AlnWorkspace::load_dir(&repo_root()).unwrap();
+    for shard in workspace.shards() {
+        let file = shard.path.display().to_string();
+        let errors: Vec<_> = shard.parsed.diagnostics.iter().filter(|d| d.is_error()).map(|d| d.render(&file)).collect();
+        assert!(errors.is_empty(), "{}", errors.join("\n"));
+    }
+}
+
+fn outline(shard: &Shard) -> String {
+    let mut out = String::new();
+    let Some(doc) = &shard.parsed.document else {
+        out.push_str("<no document>\n");
+        return diagnostics(shard, out);
+    };
+    let id = doc.id().map(|id| id.value.to_string()).unwrap_or_else(|| "-".into());
+    writeln!(out, "{:?} {id}", doc.syntax()).unwrap();
+    match doc {
+        AlnDocument::Yaml(doc) => yaml(&doc.root, 1, &mut out),
+        AlnDocument::Particle(doc) => {
+            for section in &doc.sections {
+                let indent = match &section.name {
+                    Some(name) => {
+                        writeln!(out, "  [{}] @{}", name.value, name.span.line).unwrap();
+                        "    "
+                    }
+                    None => "  ",
+                };
+                for meta in &section.meta {
+                    writeln!(out, "{indent}{} = {:?}", meta.key.value, meta.value.value).unwrap();
+                }
+                for field in &section.fields {
+                    writeln!(out, "{indent}{}: {} @{}:{}", field.name.value, ty(&field.ty.value), field.name.span.line, field.name.span.col)
+                        .unwrap();
+                }
+            }
+            for invariant in &doc.invariants {
+                writeln!(out, "  invariant @{}: {:?}", invariant.span.line, invariant.value).unwrap();
+            }
+        }
+        AlnDocument::Metric(doc) => {
+            for m in &doc.metrics {
+                writeln!(
+                    out,
+                    "  metric {} {}/{} {:?} {} {} evidence={} @{}",
+                    m.name.value,
+                    m.domain.value,
+                    m.module.value,
+                    m.op.value,
+                    m.bound.value,
+                    m.unit.value,
+                    m.evidence.len(),
+                    m.name.span.line
+                )
+                .unwrap();
+            }
+            for c in &doc.contracts {
+                writeln!(out, "  contract {}", c.name.value).unwrap();
+                for clause in &c.clauses {
+                    let args: Vec<_> = clause.args.iter().map(|a| a.value.as_str()).collect();
+                    writeln!(out, "    {} {}", clause.keyword.value, args.join(" ")).unwrap();
+                }
+            }
+        }
+        AlnDocument::Csv(doc) => {
+            writeln!(out, "  {} {}", doc.kind.value, doc.name.value).unwrap();
+            let header: Vec<_> = doc.header.iter().map(|h| h.value.as_str()).collect();
+            writeln!(out, "  header {}", header.join("|")).unwrap();
+            for row in &doc.rows {
+                let cells: Vec<_> = row.cells.iter().map(|c| c.value.as_str()).collect();
+                writeln!(out, "  {} @{} {}", row.group, row.span.line, cells.join("|")).unwrap();
+            }
+        }
+    }
+    diagnostics(shard, out)
+}
+
+fn diagnostics(shard: &Shard, mut out: String) -> String {
+    let file = shard.path.to_string_lossy().replace('\\', "/");
+    for d in &shard.parsed.diagnostics {
+        writeln!(out, "{}", d.render(&file)).unwrap();
+    }
+    out
+}
+
+fn yaml(entries: &[YamlEntry], depth: usize, out: &mut String) {
+    for entry in entries {
+        let pad = "  ".repeat(depth);
+        write!(out, "{pad}{} @{}:{}", entry.key.value, entry.key.span.line, entry.key.span.col).unwrap();
+        node(&entry.value, depth, out);
+    }
+}
+
+fn node(node: &YamlNode, depth: usize, out: &mut String) {
+    match node {
+        YamlNode::Scalar(s) => writeln!(out, " = {}", scalar(&s.value)).unwrap(),
+        YamlNode::Map { entries, .. } => {
+            out.push('\n');
+            yaml(entries, depth + 1, out);
+        }
+        YamlNode::Seq { items, .. } => {
+            writeln!(out, " [{}]", items.len()).unwrap();
+            for item in items {
+                write!(out, "{}-", "  ".repeat(depth + 1)).unwrap();
+                self::node(item, depth + 1, out);
+            }
+        }
+    }
+}
+
+fn scalar(value: &ScalarValue) -> String {
+    match value {
+        ScalarValue::Null => "null".into(),
+        ScalarValue::Bool(b) => b.to_string(),
+        ScalarValue::Int(i) => format!("int {i}"),
+        ScalarValue::Float(f) => format!("float {f}"),
+        ScalarValue::Str(s) if s.len() > 60 || s.contains('\n') => {
+            format!("str({} bytes, {} lines)", s.len(), s.lines().count())
+        }
+        ScalarValue::Str(s) => format!("{s:?}"),
+    }
+}
+
+fn ty(ty: &FieldType) -> String {
+    match ty {
+        FieldType::Enum(variants) => {
+            let names: Vec<_> = variants.iter().map(|v| v.value.as_str()).collect();
+            format!("enum({})", names.join("|"))
+        }
+        FieldType::List(inner) => format!("list {}", self::ty(inner)),
+        other => format!("{other:?}").to_lowercase(),
+    }
+}
+
+fn refs(workspace: &AlnWorkspace) -> String {
+    let shards = workspace.shards();
+    let mut out = String::new();
+    for r in workspace.resolve() {
+        let from = shards[r.from].path.to_string_lossy().replace('\\', "/");
+        let target = match r.resolution {
+            Resolution::Resolved(i) => format!("-> {}", shards[i].path.to_string_lossy().replace('\\', "/")),
+            Resolution::MissingPath(i) => format!("missing path in {}", shards[i].path.to_string_lossy().replace('\\', "/")),
+            Resolution::Unresolved => "unresolved".into(),
+        };
+        let path = r.reference.path.as_deref().map(|p| format!(":{p}")).unwrap_or_default();
+        writeln!(
+            out,
+            "{from}:{}:{} {} = {}{path} {target}",
+            r.reference.target.span.line, r.reference.target.span.col, r.reference.key.value, r.reference.target.value
+        )
+        .unwrap();
+    }
+    out
+}
@@ -1,9 +1,12 @@
-use neurorights_firewall::{NeurorightsBound, NeurorightsEnvelope, NeurorightsProfile};
+use neurorights_firewall::{ConsentLedger, NeurorightsBound, NeurorightsEnvelope, NeurorightsProfile};
 use cyber_retrieval_types::{PromptEnvelope, normalize_prompt, Identity, Governance};
 use crate::CyberRetrievalRouter;
 
+/// `ledger` must hold the citizen's unrevoked consent for the prompt's
+/// intent; binding fails otherwise and nothing is routed.
 pub async fn entry_from_http(
     router: &CyberRetrievalRouter,
+    ledger: &ConsentLedger,
     raw_text: String,
     user_did: String,
     aln: String,
@@ -23,13 +26,12 @@ pub async fn entry_from_http(
         "did:web:cybercore-brain.org#neurorights",
     );
 
-    // If needed, adjust profile anchor before binding.
-    env.neurorights_profile = NeurorightsProfile::citizen_v1(
-        "did:web:cybercore-brain.org#neurorights",
-    );
+    // Bind under the compiled profile and the consent ledger; a mismatch or
+    // revoked consent is an error, not a panic.
+    env.neurorights_profile = NeurorightsProfile::current();
 
     let bound: NeurorightsBound<PromptEnvelope, NeurorightsEnvelope> =
-        NeurorightsBound::new(env);
+        NeurorightsBound::try_bind_with_consent(env, ledger)?;
 
     router.handle_citizen_request(bound).await
 }
@@ -44,4 +44,6 @@ impl CyberRetrievalRouter {
 pub enum RouterError {
     #[error("unknown intent {0}")]
     UnknownIntent(String),
+    #[error("neurorights violation: {0}")]
+    Neurorights(#[from] neurorights_firewall::NeurorightsViolation),
 }
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use neurorights_firewall::{NeurorightsProfile, HasNeurorightsProfile};
+use neurorights_firewall::{NeurorightsProfile, HasConsentSubject, HasNeurorightsProfile, HasTraceId, PolicyMigration};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Identity {
@@ -33,10 +33,35 @@ pub struct PromptEnvelope {
     pub neurorights_profile: NeurorightsProfile,
 }
 
+impl HasTraceId for PromptEnvelope {
+    fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+}
+
+/// Consent is held per citizen DID and intent.
+impl HasConsentSubject for PromptEnvelope {
+    fn subject_did(&self) -> &str {
+        &self.identity.user_did
+    }
+
+    fn processing_purpose(&self) -> &str {
+        &self.intent
+    }
+}
+
 impl HasNeurorightsProfile for PromptEnvelope {
+    fn neurorights_profile(&self) -> &NeurorightsProfile {
+        &self.neurorights_profile
+    }
+
     fn set_neurorights_profile(&mut self, profile: NeurorightsProfile) {
         self.neurorights_profile = profile;
     }
+
+    fn record_policy_migration(&mut self, migration: &PolicyMigration) {
+        self.provenance.trace_chain.push(migration.to_string());
+    }
 }
 
 /// Deterministic normalization into a PromptEnvelope.
@@ -0,0 +1,511 @@
+#![forbid(unsafe_code)]
+
+use std::time::{Duration, SystemTime};
+
+use governance_core::roles::{GovernanceRole, RoleAssignment};
+
+use crate::governance_shard::SuccessionRules;
+
+const DAY: Duration = Duration::from_secs(24 * 3600);
+
+/// Term and succession policy for one governance seat, mirroring
+/// `term_length.days` and `succession_rules` of `governance.totem.superposition.v1`.
+#[derive(Clone, Debug)]
+pub struct ContinuityPolicy {
+    pub seat: GovernanceRole,
+    pub term_length_days: u64,
+    pub max_consecutive_terms: u32,
+    pub succession: SuccessionRules,
+}
+
+impl ContinuityPolicy {
+    pub fn default_superchair_policy() -> Self {
+        Self {
+            seat: GovernanceRole::Superchair,
+            term_length_days: 365,
+            max_consecutive_terms: 2,
+            succession: SuccessionRules::default_superchair_rules(),
+        }
+    }
+
+    /// The interim limit and term length. Both must be at least a day: a
+    /// zero interim limit would reschedule a lapsed election forever.
+    fn windows(&self) -> Result<(Duration, Duration), ContinuityError> {
+        let days = |field: &'static str, n: u64| match n.checked_mul(DAY.as_secs()) {
+            Some(0) => Err(ContinuityError::InvalidPolicy { field, reason: "must be at least one day" }),
+            Some(secs) => Ok(Duration::from_secs(secs)),
+            None => Err(ContinuityError::InvalidPolicy { field, reason: "is too long" }),
+        };
+        Ok((
+            days("succession.interimlimit_days", self.succession.interimlimit_days)?,
+            days("term_length_days", self.term_length_days)?,
+        ))
+    }
+}
+
+/// `at + window`, or an error where the clock cannot represent it.
+fn after(at: SystemTime, window: Duration) -> Result<SystemTime, ContinuityError> {
+    at.checked_add(window).ok_or(ContinuityError::TimeOverflow { at, window })
+}
+
+/// Who takes a seat; the rest of the `RoleAssignment` comes from the policy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Appointee {
+    pub holder_did: String,
+    pub aln_scope: String,
+    pub bostrom_address: String,
+    pub hex_stamp: String,
+}
+
+/// Typed inputs to the engine; the clock is the other input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContinuityEvent {
+    Resigned { holder_did: String, at: SystemTime },
+    /// `interim` takes over as `succession.on_misconduct` until the election.
+    Misconduct { holder_did: String, interim: Option<Appointee>, at: SystemTime },
+    ElectionCompleted { winner: Appointee, at: SystemTime },
+}
+
+impl ContinuityEvent {
+    pub fn at(&self) -> SystemTime {
+        match self {
+            ContinuityEvent::Resigned { at, .. }
+            | ContinuityEvent::Misconduct { at, .. }
+            | ContinuityEvent::ElectionCompleted { at, .. } => *at,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TermEndReason {
+    Expired,
+    InterimExpired,
+    Resigned,
+    Misconduct,
+    /// Interim term ended because the election filled the seat.
+    Succeeded,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VacancyReason {
+    TermExpired,
+    Resigned,
+    Misconduct,
+    /// The interim lapsed before an election completed.
+    InterimExpired,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Election {
+    pub seat: GovernanceRole,
+    pub led_by: GovernanceRole,
+    pub opens_at: SystemTime,
+    pub deadline: SystemTime,
+    pub reason: VacancyReason,
+}
+
+/// Output of the engine, in the order it happened. Replaying the same
+/// history and events yields the same sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContinuityStep {
+    TermEnded { role: GovernanceRole, holder_did: String, at: SystemTime, reason: TermEndReason },
+    Assigned { assignment: RoleAssignment, interim: bool },
+    ElectionScheduled(Election),
+    /// The deadline passed with the seat still vacant; a new election is
+    /// scheduled from the deadline.
+    ElectionLapsed(Election),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContinuityError {
+    #[error("event at {at:?} is before the engine clock {clock:?}")]
+    ClockWentBackwards { at: SystemTime, clock: SystemTime },
+
+    #[error("{holder_did} does not hold the seat or its interim")]
+    NotHolder { holder_did: String },
+
+    #[error("no election is pending")]
+    NoElectionPending,
+
+    #[error("{holder_did} has served {terms} consecutive terms, the limit")]
+    TermLimitReached { holder_did: String, terms: u32 },
+
+    #[error("policy `{field}` {reason}")]
+    InvalidPolicy { field: &'static str, reason: &'static str },
+
+    #[error("{at:?} plus {window:?} is past the end of the clock")]
+    TimeOverflow { at: SystemTime, window: Duration },
+}
+
+/// Tracks one seat through terms, vacancies, interims and elections.
+#[derive(Clone, Debug)]
+pub struct ContinuityEngine {
+    policy: ContinuityPolicy,
+    interim_limit: Duration,
+    term_length: Duration,
+    clock: SystemTime,
+    holder: Option<RoleAssignment>,
+    interim: Option<RoleAssignment>,
+    election: Option<Election>,
+    /// Holder of the latest elected term and how many in a row they served.
+    streak: Option<(String, u32)>,
+    log: Vec<ContinuityStep>,
+}
+
+impl ContinuityEngine {
+    /// Rebuild state from assignment history. An `on_misconduct`
+    /// assignment that starts after the latest seat term and lasts no
+    /// longer than the interim limit is read as an interim, which means
+    /// the seat holder was removed and an election is pending.
+    pub fn from_history(policy: ContinuityPolicy, history: &[RoleAssignment]) -> Result<Self, ContinuityError> {
+        let (interim_limit, term_length) = policy.windows()?;
+        let mut sorted: Vec<&RoleAssignment> = history.iter().collect();
+        sorted.sort_by_key(|a| a.term_start);
+
+        let seat_terms: Vec<&RoleAssignment> = sorted.iter().copied().filter(|a| a.role == policy.seat).collect();
+        let mut holder = seat_terms.last().map(|a| (*a).clone());
+        let streak = holder.as_ref().map(|h| {
+            let n = seat_terms.iter().rev().take_while(|a| a.holder_did == h.holder_did).count();
+            (h.holder_did.clone(), n as u32)
+        });
+
+        let interim = sorted
+            .iter()
+            .rev()
+            .find(|a| {
+                a.role == policy.succession.on_misconduct
+                    && holder.as_ref().is_none_or(|h| a.term_start > h.term_start)
+                    && a.term_end.is_some_and(|end| a.term_start.checked_add(interim_limit).is_none_or(|limit| end <= limit))
+            })
+            .map(|a| (*a).clone());
+        let election = interim.as_ref().map(|i| Election {
+            seat: policy.seat.clone(),
+            led_by: policy.succession.on_misconduct.clone(),
+            opens_at: i.term_start,
+            deadline: i.term_end.expect("interim terms are bounded"),
+            reason: VacancyReason::Misconduct,
+        });
+        if interim.is_some() {
+            holder = None;
+        }
+
+        let clock = sorted.last().map_or(SystemTime::UNIX_EPOCH, |a| a.term_start);
+        Ok(Self { policy, interim_limit, term_length, clock, holder, interim, election, streak, log: Vec::new() })
+    }
+
+    pub fn policy(&self) -> &ContinuityPolicy {
+        &self.policy
+    }
+
+    pub fn holder(&self) -> Option<&RoleAssignment> {
+        self.holder.as_ref()
+    }
+
+    pub fn interim(&self) -> Option<&RoleAssignment> {
+        self.interim.as_ref()
+    }
+
+    pub fn pending_election(&self) -> Option<&Election> {
+        self.election.as_ref()
+    }
+
+    /// Every step emitted so far.
+    pub fn log(&self) -> &[ContinuityStep] {
+        &self.log
+    }
+
+    /// Apply term, interim and election deadlines up to `now`.
+    pub fn advance_to(&mut self, now: SystemTime) -> Result<Vec<ContinuityStep>, ContinuityError> {
+        if now < self.clock {
+            return Err(ContinuityError::ClockWentBackwards { at: now, clock: self.clock });
+        }
+        let start = self.log.len();
+        loop {
+            let holder_end = self.holder.as_ref().and_then(|h| h.term_end).filter(|end| *end <= now);
+            let interim_end = self.interim.as_ref().and_then(|i| i.term_end).filter(|end| *end <= now);
+            let election_due = self.election.as_ref().map(|e| e.deadline).filter(|deadline| *deadline <= now);
+            match (holder_end, interim_end) {
+                // Earliest first; a seat term before an interim before an
+                // election deadline at the same instant.
+                (Some(h), i) if i.is_none_or(|i| h <= i) && election_due.is_none_or(|d| h <= d) => {
+                    let holder = self.holder.take().expect("holder_end implies holder");
+                    self.end_term(&holder, h, TermEndReason::Expired);
+                    self.schedule_election(h, self.policy.succession.on_resign.clone(), VacancyReason::TermExpired)?;
+                }
+                (_, Some(i)) if election_due.is_none_or(|d| i <= d) => {
+                    let interim = self.interim.take().expect("interim_end implies interim");
+                    self.end_term(&interim, i, TermEndReason::InterimExpired);
+                    self.election = None;
+                    self.schedule_election(i, self.policy.succession.on_resign.clone(), VacancyReason::InterimExpired)?;
+                }
+                _ => match election_due {
+                    Some(deadline) => {
+                        let lapsed = self.election.take().expect("election_due implies election");
+                        let (led_by, reason) = (lapsed.led_by.clone(), lapsed.reason);
+                        self.log.push(ContinuityStep::ElectionLapsed(lapsed));
+                        self.schedule_election(deadline, led_by, reason)?;
+                    }
+                    None => break,
+                },
+            }
+        }
+        self.clock = now;
+        Ok(self.log[start..].to_vec())
+    }
+
+    pub fn apply(&mut self, event: &ContinuityEvent) -> Result<Vec<ContinuityStep>, ContinuityError> {
+        let at = event.at();
+        let start = self.log.len();
+        self.advance_to(at)?;
+
+        match event {
+            ContinuityEvent::Resigned { holder_did, .. } => {
+                let (term, was_interim) = self.take_term(holder_did)?;
+                self.end_term(&term, at, TermEndReason::Resigned);
+                if !was_interim {
+                    self.schedule_election(at, self.policy.succession.on_resign.clone(), VacancyReason::Resigned)?;
+                }
+            }
+            ContinuityEvent::Misconduct { holder_did, interim, .. } => {
+                let (term, was_interim) = self.take_term(holder_did)?;
+                self.end_term(&term, at, TermEndReason::Misconduct);
+                if !was_interim {
+                    if let Some(appointee) = interim {
+                        let assignment = self.assignment(
+                            self.policy.succession.on_misconduct.clone(),
+                            appointee,
+                            at,
+                            self.interim_limit,
+                        )?;
+                        self.interim = Some(assignment.clone());
+                        self.log.push(ContinuityStep::Assigned { assignment, interim: true });
+                    }
+                    self.schedule_election(at, self.policy.succession.on_misconduct.clone(), VacancyReason::Misconduct)?;
+                }
+            }
+            ContinuityEvent::ElectionCompleted { winner, .. } => {
+                if self.election.is_none() {
+                    return Err(ContinuityError::NoElectionPending);
+                }
+                let consecutive = match &self.streak {
+                    Some((did, n)) if *did == winner.holder_did => *n,
+                    _ => 0,
+                };
+                if consecutive >= self.policy.max_consecutive_terms {
+                    return Err(ContinuityError::TermLimitReached {
+                        holder_did: winner.holder_did.clone(),
+                        terms: consecutive,
+                    });
+                }
+                let assignment = self.assignment(self.policy.seat.clone(), winner, at, self.term_length)?;
+                if let Some(interim) = self.interim.take() {
+                    self.end_term(&interim, at, TermEndReason::Succeeded);
+                }
+                self.election = None;
+                self.holder = Some(assignment.clone());
+                self.streak = Some((winner.holder_did.clone(), consecutive + 1));
+                self.log.push(ContinuityStep::Assigned { assignment, interim: false });
+            }
+        }
+        Ok(self.log[start..].to_vec())
+    }
+
+    fn take_term(&mut self, holder_did: &str) -> Result<(RoleAssignment, bool), ContinuityError> {
+        if self.holder.as_ref().is_some_and(|h| h.holder_did == holder_did) {
+            return Ok((self.holder.take().expect("checked"), false));
+        }
+        if self.interim.as_ref().is_some_and(|i| i.holder_did == holder_did) {
+            return Ok((self.interim.take().expect("checked"), true));
+        }
+        Err(ContinuityError::NotHolder { holder_did: holder_did.to_string() })
+    }
+
+    fn end_term(&mut self, term: &RoleAssignment, at: SystemTime, reason: TermEndReason) {
+        self.log.push(ContinuityStep::TermEnded {
+            role: term.role.clone(),
+            holder_did: term.holder_did.clone(),
+            at,
+            reason,
+        });
+    }
+
+    /// At most one election is pending; a new vacancy keeps the existing one.
+    fn schedule_election(&mut self, at: SystemTime, led_by: GovernanceRole, reason: VacancyReason) -> Result<(), ContinuityError> {
+        if self.election.is_some() {
+            return Ok(());
+        }
+        let election = Election {
+            seat: self.policy.seat.clone(),
+            led_by,
+            opens_at: at,
+            deadline: after(at, self.interim_limit)?,
+            reason,
+        };
+        self.election = Some(election.clone());
+        self.log.push(ContinuityStep::ElectionScheduled(election));
+        Ok(())
+    }
+
+    fn assignment(&self, role: GovernanceRole, who: &Appointee, at: SystemTime, term: Duration) -> Result<RoleAssignment, ContinuityError> {
+        Ok(RoleAssignment {
+            role,
+            holder_did: who.holder_did.clone(),
+            aln_scope: who.aln_scope.clone(),
+            bostrom_address: who.bostrom_address.clone(),
+            term_start: at,
+            term_end: Some(after(at, term)?),
+            hex_stamp: who.hex_stamp.clone(),
+        })
+    }
+}
+
+/// Rebuild from `history`, apply `events` in time order (ties keep their
+/// given order), then advance the clock to `now`.
+pub fn replay(
+    policy: ContinuityPolicy,
+    history: &[RoleAssignment],
+    events: &[ContinuityEvent],
+    now: SystemTime,
+) -> Result<Vec<ContinuityStep>, ContinuityError> {
+    let mut engine = ContinuityEngine::from_history(policy, history)?;
+    let mut ordered: Vec<&ContinuityEvent> = events.iter().collect();
+    ordered.sort_by_key(|e| e.at());
+    for event in ordered {
+        engine.apply(event)?;
+    }
+    engine.advance_to(now)?;
+    Ok(engine.log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t(days: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(days * DAY.as_secs())
+    }
+
+    fn who(did: &str) -> Appointee {
+        Appointee {
+            holder_did: did.into(),
+            aln_scope: "governance.chat.website.v1".into(),
+            bostrom_address: "bostrom1test".into(),
+            hex_stamp: "0x1a2f".into(),
+        }
+    }
+
+    #[test]
+    fn misconduct_interim_lapses_then_election_and_term_limit() {
+        let policy = ContinuityPolicy::default_superchair_policy();
+        let history = [RoleAssignment {
+            role: GovernanceRole::Superchair,
+            holder_did: "did:chair:a".into(),
+            aln_scope: "governance.chat.website.v1".into(),
+            bostrom_address: "bostrom1a".into(),
+            term_start: t(0),
+            term_end: Some(t(365)),
+            hex_stamp: "0x01".into(),
+        }];
+        let events = [
+            ContinuityEvent::Misconduct { holder_did: "did:chair:a".into(), interim: Some(who("did:council:b")), at: t(10) },
+            ContinuityEvent::ElectionCompleted { winner: who("did:chair:c"), at: t(30) },
+        ];
+        let steps = replay(policy.clone(), &history, &events, t(400)).unwrap();
+        assert_eq!(steps, replay(policy.clone(), &history, &events, t(400)).unwrap());
+
+        let summary: Vec<String> = steps
+            .iter()
+            .map(|s| match s {
+                ContinuityStep::TermEnded { holder_did, reason, .. } => format!("end {holder_did} {reason:?}"),
+                ContinuityStep::Assigned { assignment, interim } => format!("assign {} interim={interim}", assignment.holder_did),
+                ContinuityStep::ElectionScheduled(e) => format!("election {:?} by {:?}", e.reason, e.led_by),
+                ContinuityStep::ElectionLapsed(e) => format!("lapsed {:?}", e.reason),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                "end did:chair:a Misconduct",
+                "assign did:council:b interim=true",
+                "election Misconduct by Council",
+                "end did:council:b InterimExpired",
+                "election InterimExpired by Council",
+                "assign did:chair:c interim=false",
+                "end did:chair:c Expired",
+                "election TermExpired by Council",
+            ]
+        );
+
+        let mut engine = ContinuityEngine::from_history(policy, &history).unwrap();
+        engine.apply(&ContinuityEvent::Resigned { holder_did: "did:chair:a".into(), at: t(1) }).unwrap();
+        engine.apply(&ContinuityEvent::ElectionCompleted { winner: who("did:chair:a"), at: t(2) }).unwrap();
+        assert!(matches!(
+            engine.apply(&ContinuityEvent::Resigned { holder_did: "did:chair:z".into(), at: t(3) }),
+            Err(ContinuityError::NotHolder { .. })
+        ));
+        engine.apply(&ContinuityEvent::Resigned { holder_did: "did:chair:a".into(), at: t(3) }).unwrap();
+        assert!(matches!(
+            engine.apply(&ContinuityEvent::ElectionCompleted { winner: who("did:chair:a"), at: t(4) }),
+            Err(ContinuityError::TermLimitReached { terms: 2, .. })
+        ));
+        assert!(matches!(engine.advance_to(t(0)), Err(ContinuityError::ClockWentBackwards { .. })));
+    }
+
+    #[test]
+    fn overdue_election_lapses_and_is_rescheduled() {
+        let history = [RoleAssignment {
+            role: GovernanceRole::Superchair,
+            holder_did: "did:chair:a".into(),
+            aln_scope: "governance.chat.website.v1".into(),
+            bostrom_address: "bostrom1a".into(),
+            term_start: t(0),
+            term_end: Some(t(365)),
+            hex_stamp: "0x01".into(),
+        }];
+        let mut engine = ContinuityEngine::from_history(ContinuityPolicy::default_superchair_policy(), &history).unwrap();
+        engine.apply(&ContinuityEvent::Resigned { holder_did: "did:chair:a".into(), at: t(1) }).unwrap();
+        let steps = engine.advance_to(t(30)).unwrap();
+        let deadlines: Vec<(bool, SystemTime)> = steps
+            .iter()
+            .map(|s| match s {
+                ContinuityStep::ElectionLapsed(e) => (true, e.deadline),
+                ContinuityStep::ElectionScheduled(e) => (false, e.deadline),
+                other => panic!("unexpected step {other:?}"),
+            })
+            .collect();
+        assert_eq!(deadlines, [(true, t(15)), (false, t(29)), (true, t(29)), (false, t(43))]);
+        assert_eq!(engine.pending_election().unwrap().opens_at, t(29));
+
+        engine.apply(&ContinuityEvent::ElectionCompleted { winner: who("did:chair:c"), at: t(31) }).unwrap();
+        assert!(engine.pending_election().is_none());
+        assert_eq!(engine.holder().unwrap().holder_did, "did:chair:c");
+    }
+
+    #[test]
+    fn zero_and_unrepresentable_windows_are_errors() {
+        let mut policy = ContinuityPolicy::default_superchair_policy();
+        policy.succession.interimlimit_days = 0;
+        assert!(matches!(
+            ContinuityEngine::from_history(policy, &[]),
+            Err(ContinuityError::InvalidPolicy { field: "succession.interimlimit_days", .. })
+        ));
+
+        let mut policy = ContinuityPolicy::default_superchair_policy();
+        policy.term_length_days = u64::MAX;
+        assert!(matches!(
+            ContinuityEngine::from_history(policy, &[]),
+            Err(ContinuityError::InvalidPolicy { field: "term_length_days", .. })
+        ));
+
+        // Fits a Duration, but not the clock.
+        let mut policy = ContinuityPolicy::default_superchair_policy();
+        policy.term_length_days = u64::MAX / DAY.as_secs();
+        let mut engine = ContinuityEngine::from_history(policy, &[]).unwrap();
+        engine.schedule_election(t(1), GovernanceRole::Council, VacancyReason::Resigned).unwrap();
+        assert!(matches!(
+            engine.apply(&ContinuityEvent::ElectionCompleted { winner: who("did:chair:c"), at: t(2) }),
+            Err(ContinuityError::TimeOverflow { .. })
+        ));
+        assert!(engine.pending_election().is_some());
+    }
+}
@@ -1,11 +1,12 @@
 #![forbid(unsafe_code)]
 
-use std::time::{Duration, SystemTime};
+use std::time::SystemTime;
 
+use crate::continuity::{ContinuityEngine, ContinuityError, ContinuityEvent, ContinuityStep};
 use crate::prompt::{PromptEnvelope, GovernanceActionKind};
 use crate::router::NeurorightsBoundEnvelope;
 use governance_core::roles::{GovernanceRole, RoleAssignment, StakeSnapshot};
-use governance_core::policy::{AlnGovernanceShard, RoleThresholds};
+use governance_core::policy::AlnGovernanceShard;
 use governance_core::eligibility::{compute_eligibility, GovernanceSafetyProfile, GovernanceIndices};
 use neurorights_core::{NeurorightsBound, NeurorightsEnvelope};
 use neurorights_firewall::audit::{Authorship, EvidenceStamp};
@@ -17,6 +18,8 @@ pub struct ContinuityResolution {
     pub fallback_role: Option<GovernanceRole>,
     pub disqualified_roles: Vec<GovernanceRole>,
     pub new_assignments: Vec<RoleAssignment>,
+    /// Everything the continuity engine emitted while resolving.
+    pub steps: Vec<ContinuityStep>,
     pub indices: GovernanceIndices,
 }
 
@@ -61,26 +64,35 @@ pub struct GovernanceAuditHop {
 }
 
 /// High‑level continuity dataplan:
-/// 1. Compute eligible roles from ALN thresholds.
-/// 2. Apply auto‑disqualification: stake / term / neurorights.
-/// 3. Choose acting role in order: Superchair → Council → Proposer.
-/// 4. If Superchair lost, apply successionrules deterministically.
-/// 5. Emit a GovernanceAuditHop with hex‑stamped PromptEnvelope context.
+/// 1. Advance `engine` to `ctx.current_time`, lapsing terms, interims and
+///    overdue elections.
+/// 2. Compute eligible roles from ALN thresholds.
+/// 3. Apply auto‑disqualification: stake / term / neurorights.
+/// 4. Choose acting role in order: Superchair → Council → Proposer; the
+///    engine's seat is only acted in by its current holder.
+/// 5. If the seat holder lost the seat role, remove them through the
+///    engine's `on_misconduct` path.
+/// 6. Emit a GovernanceAuditHop with hex‑stamped PromptEnvelope context.
 pub fn resolve_continuity(
     bound_env: NeurorightsBoundEnvelope,
     ctx: GovernanceContext,
-    succession: &SuccessionRules,
+    engine: &mut ContinuityEngine,
     safety: &GovernanceSafetyProfile,
-) -> (ContinuityResolution, GovernanceAuditHop) {
+) -> Result<(ContinuityResolution, GovernanceAuditHop), ContinuityError> {
     // 0. Neurorights firewall: if this compiled and we have a NeurorightsBound,
     //    then noscorefrominnerstate / noneurocoercion / revocability are already satisfied.
     let inner: &NeurorightsBound<PromptEnvelope, NeurorightsEnvelope> = &bound_env.bound;
 
-    // 1. Eligibility from ALN shard + stake.
+    // 1. Lapses up to now.
+    let mut steps = engine.advance_to(ctx.current_time)?;
+    let seat = engine.policy().seat.clone();
+    let seated = engine.holder().is_some_and(|h| h.holder_did == ctx.holder_did);
+
+    // 2. Eligibility from ALN shard + stake.
     let (eligible_roles, indices) =
         compute_eligibility(&ctx.stake, &ctx.aln_governance_shard, safety);
 
-    // 2. Auto‑disqualification predicates (stake/term/profile) at runtime boundary.
+    // 3. Auto‑disqualification predicates (stake/term/profile) at runtime boundary.
     let mut disqualified = Vec::new();
     let mut still_eligible: Vec<GovernanceRole> = Vec::new();
 
@@ -92,41 +104,46 @@ pub fn resolve_continuity(
         }
     }
 
-    // 3. Continuity order: Superchair → Council → Proposer.
+    // 4. Continuity order: Superchair → Council → Proposer. The seat is
+    //    held, not merely earned by stake.
+    if !seated {
+        still_eligible.retain(|role| *role != seat);
+    }
     let acting_role = pick_acting_role(&still_eligible);
     let mut fallback_role: Option<GovernanceRole> = None;
-    let mut new_assignments: Vec<RoleAssignment> = Vec::new();
-
-    // 4. Succession logic for lost Superchair.
-    if disqualified.contains(&GovernanceRole::Superchair) {
-        fallback_role = Some(succession.on_misconduct.clone());
-        if let Some(role) = &fallback_role {
-            let assignment = RoleAssignment {
-                role: role.clone(),
-                holder_did: ctx.holder_did.clone(),
-                aln_scope: ctx.aln_scope.clone(),
-                bostrom_address: ctx.bostrom_address.clone(),
-                term_start: ctx.current_time,
-                term_end: Some(ctx.current_time + Duration::from_secs(
-                    succession.interimlimit_days * 24 * 3600,
-                )),
-                hex_stamp: inner.envelope().hex_stamp.clone(),
-            };
-            new_assignments.push(assignment);
+
+    // 5. Succession for a holder who lost the seat role.
+    if seated && !still_eligible.contains(&seat) {
+        if !disqualified.contains(&seat) {
+            disqualified.push(seat.clone());
         }
+        steps.extend(engine.apply(&ContinuityEvent::Misconduct {
+            holder_did: ctx.holder_did.clone(),
+            interim: None,
+            at: ctx.current_time,
+        })?);
+        fallback_role = Some(engine.policy().succession.on_misconduct.clone());
     }
 
+    let new_assignments = steps
+        .iter()
+        .filter_map(|step| match step {
+            ContinuityStep::Assigned { assignment, .. } => Some(assignment.clone()),
+            _ => None,
+        })
+        .collect();
     let continuity = ContinuityResolution {
         acting_role,
         fallback_role,
         disqualified_roles: disqualified,
         new_assignments,
+        steps,
         indices: indices.clone(),
     };
 
     let audit = build_audit_hop(inner, &ctx, &continuity);
 
-    (continuity, audit)
+    Ok((continuity, audit))
 }
 
 /// Check disqualification predicates for a role:
@@ -138,18 +155,16 @@ fn is_role_valid(
     inner: &NeurorightsBound<PromptEnvelope, NeurorightsEnvelope>,
 ) -> bool {
     // Term validity: if PromptEnvelope encodes a term_end for this role, enforce it.
-    if let Some(term) = inner.envelope().role_term_end(role) {
+    if let Some(term) = inner.payload().role_term_end(role) {
         if ctx.current_time > term {
             return false;
         }
     }
 
-    // Neurorights profile validity: delegate to neurorights_envelope guard.
-    if !inner.neurorights_envelope().profile_is_strong_enough(role) {
-        return false;
-    }
-
-    true
+    // Neurorights profile validity: no governance role acts under an
+    // envelope that allows inner-state scoring or neurocoercion.
+    let envelope = inner.neurorights_envelope();
+    !envelope.allow_inner_state_scoring && !envelope.allow_neurocoercion && envelope.consent_revocable_at_will
 }
 
 /// Prioritize acting role in fixed order: Superchair > Council > Proposer.
@@ -172,13 +187,13 @@ fn build_audit_hop(
     ctx: &GovernanceContext,
     continuity: &ContinuityResolution,
 ) -> GovernanceAuditHop {
-    let env = bound.envelope();
+    let env = bound.payload();
     let authorship = Authorship {
-        userdid: ctx.holder_did.clone(),
+        user_did: ctx.holder_did.clone(),
         aln: ctx.aln_scope.clone(),
-        bostromaddress: ctx.bostrom_address.clone(),
-        eibonlabel: env.eibon_label.clone(),
-        neurorightsversion: bound.neurorights_envelope().policyversion.to_string(),
+        bostrom_address: ctx.bostrom_address.clone(),
+        eibon_label: env.eibon_label.clone(),
+        neurorights_version: bound.neurorights_envelope().policy_version.to_string(),
     };
 
     let evidence = EvidenceStamp::default_hex();
@@ -190,3 +205,77 @@ fn build_audit_hop(
         continuity: continuity.clone(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::continuity::{ContinuityPolicy, TermEndReason};
+    use std::time::Duration;
+
+    const DAY: Duration = Duration::from_secs(24 * 3600);
+
+    fn request(did: &str, chat_units: u128, at: SystemTime) -> (NeurorightsBoundEnvelope, GovernanceContext) {
+        let prompt = PromptEnvelope {
+            did: did.into(),
+            aln_scope: "governance.chat.website.v1".into(),
+            bostrom_address: "bostrom1a".into(),
+            eibon_label: "eibon".into(),
+            hex_stamp: "0x01".into(),
+            captured_at: at,
+            term_end_superchair: None,
+            term_end_council: None,
+        };
+        // The local PromptEnvelope carries no profile to bind against.
+        #[allow(deprecated)]
+        let bound = NeurorightsBound::new(prompt, NeurorightsEnvelope::compiled());
+        let ctx = GovernanceContext {
+            holder_did: did.into(),
+            aln_scope: "governance.chat.website.v1".into(),
+            bostrom_address: "bostrom1a".into(),
+            stake: StakeSnapshot { chat_units, cy_units: 0, zen_units: 0, lifeforce_units: 0, contrib_index_milli: 1 },
+            current_time: at,
+            action: GovernanceActionKind::RoleSuccessionCheck,
+            aln_governance_shard: AlnGovernanceShard::testing_defaults(),
+        };
+        (NeurorightsBoundEnvelope { bound }, ctx)
+    }
+
+    #[test]
+    fn only_the_seat_holder_acts_and_losing_the_role_starts_succession() {
+        let history = [RoleAssignment {
+            role: GovernanceRole::Superchair,
+            holder_did: "did:chair:a".into(),
+            aln_scope: "governance.chat.website.v1".into(),
+            bostrom_address: "bostrom1a".into(),
+            term_start: SystemTime::UNIX_EPOCH,
+            term_end: Some(SystemTime::UNIX_EPOCH + DAY * 365),
+            hex_stamp: "0x01".into(),
+        }];
+        let mut engine = ContinuityEngine::from_history(ContinuityPolicy::default_superchair_policy(), &history).unwrap();
+        let safety = GovernanceSafetyProfile::default_profile();
+        let at = SystemTime::UNIX_EPOCH + DAY * 10;
+
+        let (env, ctx) = request("did:chair:b", 20_000, at);
+        let (resolution, _) = resolve_continuity(env, ctx, &mut engine, &safety).unwrap();
+        assert_eq!(resolution.acting_role, Some(GovernanceRole::Council));
+        assert!(resolution.steps.is_empty());
+
+        let (env, ctx) = request("did:chair:a", 2_000, at);
+        let (resolution, audit) = resolve_continuity(env, ctx, &mut engine, &safety).unwrap();
+        assert_eq!(resolution.acting_role, Some(GovernanceRole::Council));
+        assert_eq!(resolution.fallback_role, Some(GovernanceRole::Council));
+        assert_eq!(resolution.disqualified_roles, [GovernanceRole::Superchair]);
+        assert!(matches!(
+            resolution.steps[..],
+            [ContinuityStep::TermEnded { reason: TermEndReason::Misconduct, .. }, ContinuityStep::ElectionScheduled(_)]
+        ));
+        assert!(engine.holder().is_none() && engine.pending_election().is_some());
+        assert_eq!(audit.continuity.steps, resolution.steps);
+
+        let (env, ctx) = request("did:chair:a", 20_000, at - DAY);
+        assert!(matches!(
+            resolve_continuity(env, ctx, &mut engine, &safety),
+            Err(ContinuityError::ClockWentBackwards { .. })
+        ));
+    }
+}
@@ -0,0 +1,11 @@
+//! Governance continuity for neurorights-bound requests: which role acts
+//! now ([`governance_shard::resolve_continuity`]) and how a seat moves
+//! through terms, interims and elections over time
+//! ([`continuity::ContinuityEngine`]).
+
+#![forbid(unsafe_code)]
+
+pub mod continuity;
+pub mod governance_shard;
+pub mod prompt;
+pub mod router;
@@ -1,8 +1,54 @@
-use std::{env, fs, path::PathBuf};
+use std::{collections::BTreeMap, env, fs, path::PathBuf};
+
+/// Every permission string the website handlers know how to enforce. A
+/// role in the ALN shard granting anything else fails the build.
+const KNOWN_PERMISSIONS: &[&str] = &[
+    "website.page.propose",
+    "website.page.review",
+    "website.page.publish",
+    "website.section.review",
+    "website.section.publish",
+    "website.comment.propose",
+    "website.comment.moderate",
+    "website.sitewide.policy.update",
+    "website.sitewide.rollback",
+    "website.governance.veto",
+];
+
+/// `10^decimals` must fit comfortably in the u128 behind `ChatAmount`.
+const MAX_CHAT_DECIMALS: u32 = 18;
+
+const ROLE_NAMES: [&str; 3] = ["stakeholder", "council", "superchair"];
+
+/// `risk_weights` keys and the Rust paths they generate match arms for.
+const ACTION_WEIGHTS: [(&str, &str); 8] = [
+    ("propose_page", "WebsiteAction::ProposePage"),
+    ("review_page", "WebsiteAction::ReviewPage"),
+    ("publish_page", "WebsiteAction::PublishPage"),
+    ("review_section", "WebsiteAction::ReviewSection"),
+    ("publish_section", "WebsiteAction::PublishSection"),
+    ("update_site_policy", "WebsiteAction::UpdateSitePolicy"),
+    ("rollback_site_policy", "WebsiteAction::RollbackSitePolicy"),
+    ("veto_change", "WebsiteAction::VetoChange"),
+];
+const SCOPE_WEIGHTS: [(&str, &str); 3] = [
+    ("section", "RiskScope::Section"),
+    ("page", "RiskScope::Page"),
+    ("sitewide", "RiskScope::Sitewide"),
+];
+const ROLE_WEIGHTS: [(&str, &str); 3] = [
+    ("stakeholder", "GovernanceRole::Stakeholder"),
+    ("council", "GovernanceRole::Council"),
+    ("superchair", "GovernanceRole::Superchair"),
+];
 
 fn main() {
-    // Location of ALN shards (configurable via env var)
-    let aln_dir = env::var("ALN_DIR").unwrap_or_else(|_| "aln".to_string());
+    // Location of ALN shards (configurable via env var); the repository's
+    // `aln/` by default.
+    let aln_dir = env::var("ALN_DIR").unwrap_or_else(|_| {
+        let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR must be set");
+        format!("{manifest_dir}/../../aln")
+    });
 
     let asset_path = PathBuf::from(&aln_dir).join("asset.chat.stake.v1.aln");
     let gov_path = PathBuf::from(&aln_dir).join("governance.chat.website.v1.aln");
@@ -35,7 +81,12 @@ fn generate_code(asset_yaml: &str, gov_yaml: &str) -> Result<String, String> {
     #[derive(serde::Deserialize)]
     struct AssetRoot {
         id: String,
-        version: String,
+        fields: AssetFields,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct AssetFields {
+        decimals: u32,
         min_stake: MinStake,
         risk: RiskSection,
     }
@@ -57,6 +108,8 @@ fn generate_code(asset_yaml: &str, gov_yaml: &str) -> Result<String, String> {
 
     #[derive(serde::Deserialize)]
     struct RoleDef {
+        #[serde(default)]
+        inherits: Option<String>,
         permissions: Vec<String>,
     }
 
@@ -67,11 +120,46 @@ fn generate_code(asset_yaml: &str, gov_yaml: &str) -> Result<String, String> {
         retrieval_only_for_generation: bool,
     }
 
+    #[derive(serde::Deserialize)]
+    struct QuorumDef {
+        council_approval_fraction: f64,
+        /// Share of council seats that must cast a vote; defaults to the approval fraction.
+        #[serde(default)]
+        participation_fraction: Option<f64>,
+        superchair_required: bool,
+        #[serde(default)]
+        veto_window_hours: u64,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct QuorumRoot {
+        page_publish: QuorumDef,
+        section_publish: QuorumDef,
+        sitewide_policy_update: QuorumDef,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct ChurnDef {
+        per_rollback: f64,
+        window_hours: u64,
+        #[serde(default)]
+        exempt_actions: Vec<String>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct RiskWeightsRoot {
+        action: BTreeMap<String, f64>,
+        scope: BTreeMap<String, f64>,
+        role: BTreeMap<String, f64>,
+        rollback_churn: ChurnDef,
+    }
+
     #[derive(serde::Deserialize)]
     struct GovernanceRoot {
         id: String,
-        version: String,
         roles: RolesRoot,
+        quorum: QuorumRoot,
+        risk_weights: RiskWeightsRoot,
         constraints: Constraints,
     }
 
@@ -80,13 +168,153 @@ fn generate_code(asset_yaml: &str, gov_yaml: &str) -> Result<String, String> {
     let gov: GovernanceRoot = serde_yaml::from_str(gov_yaml)
         .map_err(|e| format!("governance yaml parse error: {e}"))?;
 
-    let s_min = &asset.min_stake.stakeholder;
-    let c_min = &asset.min_stake.council;
-    let sc_min = &asset.min_stake.superchair;
+    let roles = [&gov.roles.stakeholder, &gov.roles.council, &gov.roles.superchair];
+    let mut errors = Vec::new();
+    for (name, role) in ROLE_NAMES.iter().zip(roles) {
+        for perm in &role.permissions {
+            if !KNOWN_PERMISSIONS.contains(&perm.as_str()) {
+                errors.push(format!("roles.{name}: unknown permission `{perm}`"));
+            }
+        }
+        if let Some(parent) = &role.inherits {
+            if !ROLE_NAMES.contains(&parent.as_str()) || parent == name {
+                errors.push(format!("roles.{name}: cannot inherit from `{parent}`"));
+            }
+        }
+    }
+    let quorums = [
+        ("page_publish", &gov.quorum.page_publish),
+        ("section_publish", &gov.quorum.section_publish),
+        ("sitewide_policy_update", &gov.quorum.sitewide_policy_update),
+    ];
+    for (name, q) in quorums {
+        let participation = q.participation_fraction.unwrap_or(q.council_approval_fraction);
+        let fractions = [
+            ("council_approval_fraction", q.council_approval_fraction),
+            ("participation_fraction", participation),
+        ];
+        for (key, fraction) in fractions {
+            if !(fraction > 0.0 && fraction <= 1.0) {
+                errors.push(format!("quorum.{name}: {key} must be in (0, 1]"));
+            }
+        }
+    }
+    let weights = &gov.risk_weights;
+    let weight_tables = [
+        ("action", &weights.action, &ACTION_WEIGHTS[..]),
+        ("scope", &weights.scope, &SCOPE_WEIGHTS[..]),
+        ("role", &weights.role, &ROLE_WEIGHTS[..]),
+    ];
+    for (table, values, known) in weight_tables {
+        for (key, weight) in values {
+            if !known.iter().any(|(k, _)| k == key) {
+                errors.push(format!("risk_weights.{table}: unknown key `{key}`"));
+            } else if !(weight.is_finite() && weight.abs() <= 1.0) {
+                errors.push(format!("risk_weights.{table}.{key}: must be in [-1, 1]"));
+            }
+        }
+        for (key, _) in known {
+            if !values.contains_key(*key) {
+                errors.push(format!("risk_weights.{table}: missing `{key}`"));
+            }
+        }
+    }
+    let churn = &weights.rollback_churn;
+    if !(churn.per_rollback.is_finite() && (0.0..=1.0).contains(&churn.per_rollback)) {
+        errors.push("risk_weights.rollback_churn.per_rollback: must be in [0, 1]".to_string());
+    }
+    for action in &churn.exempt_actions {
+        if !ACTION_WEIGHTS.iter().any(|(k, _)| k == action) {
+            errors.push(format!("risk_weights.rollback_churn.exempt_actions: unknown action `{action}`"));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
 
-    let risk = &asset.risk;
+    // Resolve `inherits` chains; three roles means a longer chain is a cycle.
+    let mut matrix = Vec::new();
+    for (name, role) in ROLE_NAMES.iter().zip(roles) {
+        let mut perms: Vec<&str> = Vec::new();
+        let mut current = Some((*name, role));
+        let mut depth = 0;
+        while let Some((current_name, def)) = current {
+            depth += 1;
+            if depth > ROLE_NAMES.len() {
+                return Err(format!("roles.{name}: `inherits` cycle through `{current_name}`"));
+            }
+            for perm in &def.permissions {
+                if !perms.contains(&perm.as_str()) {
+                    perms.push(perm);
+                }
+            }
+            current = def.inherits.as_deref().and_then(|parent| {
+                let i = ROLE_NAMES.iter().position(|n| *n == parent)?;
+                Some((ROLE_NAMES[i], roles[i]))
+            });
+        }
+        perms.sort_unstable_by_key(|p| KNOWN_PERMISSIONS.iter().position(|k| k == p));
+        matrix.push(perms);
+    }
+
+    let permission_consts: String = KNOWN_PERMISSIONS
+        .iter()
+        .map(|p| format!("    pub const {}: &str = \"{p}\";\n", p.replace('.', "_").to_uppercase()))
+        .collect();
+    let role_list = |perms: &[&str]| {
+        perms.iter().map(|p| format!("\"{p}\"")).collect::<Vec<_>>().join(", ")
+    };
+
+    let decimals = asset.fields.decimals;
+    if decimals > MAX_CHAT_DECIMALS {
+        return Err(format!("fields.decimals: {decimals} exceeds the supported {MAX_CHAT_DECIMALS}"));
+    }
+    let min_stake = &asset.fields.min_stake;
+    let quorum_consts: String = quorums
+        .iter()
+        .map(|(name, q)| {
+            format!(
+                "    pub const {}: QuorumRule = QuorumRule {{ council_approval_milli: {}, participation_milli: {}, \
+                 superchair_required: {}, veto_window_secs: {} }};\n",
+                name.to_uppercase(),
+                (q.council_approval_fraction * 1000.0).round() as u32,
+                (q.participation_fraction.unwrap_or(q.council_approval_fraction) * 1000.0).round() as u32,
+                q.superchair_required,
+                q.veto_window_hours * 3600,
+            )
+        })
+        .collect();
+
+    let s_min = chat_units(&min_stake.stakeholder, decimals)
+        .map_err(|e| format!("min_stake.stakeholder: {e}"))?;
+    let c_min = chat_units(&min_stake.council, decimals)
+        .map_err(|e| format!("min_stake.council: {e}"))?;
+    let sc_min = chat_units(&min_stake.superchair, decimals)
+        .map_err(|e| format!("min_stake.superchair: {e}"))?;
+    if !(s_min <= c_min && c_min <= sc_min) {
+        return Err("min_stake: thresholds must not decrease from stakeholder to superchair".into());
+    }
+
+    let risk = &asset.fields.risk;
     let constraints = &gov.constraints;
 
+    let weight_arms = |values: &BTreeMap<String, f64>, known: &[(&str, &str)]| -> String {
+        known
+            .iter()
+            .map(|(key, path)| format!("            {path} => {:?},\n", values[*key]))
+            .collect()
+    };
+    let exempt_actions = if churn.exempt_actions.is_empty() {
+        "false".to_string()
+    } else {
+        let paths: Vec<&str> = ACTION_WEIGHTS
+            .iter()
+            .filter(|(key, _)| churn.exempt_actions.iter().any(|a| a == key))
+            .map(|(_, path)| *path)
+            .collect();
+        format!("matches!(action, {})", paths.join(" | "))
+    };
+
     let code = format!(
         r#"
 /// ALN id for CHAT stake asset shard.
@@ -100,14 +328,19 @@ pub const DEFAULT_RISK_OF_HARM: f64 = {roh_default};
 pub const DEFAULT_KNOWLEDGE_FACTOR: f64 = {kf_default};
 pub const DEFAULT_CYBOSTATE_FACTOR: f64 = {cs_default};
 
-/// Minimum CHAT stake thresholds for each governance role, in decimal-string form.
+/// Decimal places of the CHAT asset (`fields.decimals`).
+pub const CHAT_DECIMALS: u32 = {decimals};
+
+/// Minimum CHAT stake thresholds for each governance role.
 pub mod stake_thresholds {{
+    use crate::amount::ChatAmount;
+
     /// Stake required to be a stakeholder.
-    pub const STAKEHOLDER_MIN: &str = "{stakeholder_min}";
+    pub const STAKEHOLDER_MIN: ChatAmount = ChatAmount::from_units({stakeholder_min});
     /// Stake required to be a council member.
-    pub const COUNCIL_MIN: &str = "{council_min}";
+    pub const COUNCIL_MIN: ChatAmount = ChatAmount::from_units({council_min});
     /// Stake required to be a superchair.
-    pub const SUPERCHAIR_MIN: &str = "{superchair_min}";
+    pub const SUPERCHAIR_MIN: ChatAmount = ChatAmount::from_units({superchair_min});
 }}
 
 /// Governance constraints derived from governance.chat.website.v1.
@@ -116,6 +349,57 @@ pub mod governance_constraints {{
     pub const RISK_OF_HARM_CEILING: f64 = {gov_roh_ceiling};
     pub const RETRIEVAL_ONLY_FOR_GENERATION: bool = {retrieval_only};
 }}
+
+/// Permission strings enforced by the website handlers.
+pub mod permissions {{
+{permission_consts}}}
+
+/// Approval quorums from governance.chat.website.v1 (`quorum`), with
+/// fractions in thousandths and the veto window in seconds.
+pub mod quorum {{
+    use crate::proposal::QuorumRule;
+
+{quorum_consts}}}
+
+/// Risk-of-harm weights from governance.chat.website.v1 (`risk_weights`),
+/// added to `DEFAULT_RISK_OF_HARM`.
+pub mod risk_weights {{
+    use crate::handlers::WebsiteAction;
+    use crate::risk::RiskScope;
+    use crate::roles::GovernanceRole;
+
+    pub const fn action(action: WebsiteAction) -> f64 {{
+        match action {{
+{action_arms}        }}
+    }}
+
+    pub const fn scope(scope: RiskScope) -> f64 {{
+        match scope {{
+{scope_arms}        }}
+    }}
+
+    pub const fn role(role: GovernanceRole) -> f64 {{
+        match role {{
+{role_arms}        }}
+    }}
+
+    /// Added per site-policy rollback within `ROLLBACK_CHURN_WINDOW_SECS`.
+    pub const ROLLBACK_CHURN_PER_ROLLBACK: f64 = {churn_per_rollback:?};
+    pub const ROLLBACK_CHURN_WINDOW_SECS: u64 = {churn_window_secs};
+
+    /// Actions rollback churn does not apply to.
+    pub const fn churn_exempt(action: WebsiteAction) -> bool {{
+        {exempt_actions}
+    }}
+}}
+
+/// Role -> granted permissions from governance.chat.website.v1, with
+/// `inherits` resolved.
+pub mod permission_matrix {{
+    pub const STAKEHOLDER: &[&str] = &[{stakeholder_perms}];
+    pub const COUNCIL: &[&str] = &[{council_perms}];
+    pub const SUPERCHAIR: &[&str] = &[{superchair_perms}];
+}}
 "#,
         asset_id = asset.id,
         gov_id = gov.id,
@@ -123,13 +407,39 @@ pub mod governance_constraints {{
         roh_default = risk.default_risk_of_harm,
         kf_default = risk.default_knowledge_factor,
         cs_default = risk.default_cybostate_factor,
+        decimals = decimals,
         stakeholder_min = s_min,
         council_min = c_min,
         superchair_min = sc_min,
         neurorights_required = constraints.neurorights_required,
         gov_roh_ceiling = constraints.risk_of_harm_ceiling,
         retrieval_only = constraints.retrieval_only_for_generation,
+        permission_consts = permission_consts,
+        quorum_consts = quorum_consts,
+        action_arms = weight_arms(&weights.action, &ACTION_WEIGHTS),
+        scope_arms = weight_arms(&weights.scope, &SCOPE_WEIGHTS),
+        role_arms = weight_arms(&weights.role, &ROLE_WEIGHTS),
+        churn_per_rollback = churn.per_rollback,
+        churn_window_secs = churn.window_hours * 3600,
+        exempt_actions = exempt_actions,
+        stakeholder_perms = role_list(&matrix[0]),
+        council_perms = role_list(&matrix[1]),
+        superchair_perms = role_list(&matrix[2]),
     );
 
     Ok(code)
 }
+
+/// Same strict grammar as `ChatAmount::from_str`, in smallest units.
+fn chat_units(s: &str, decimals: u32) -> Result<u128, String> {
+    let (whole, frac) = s.split_once('.').unwrap_or((s, ""));
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !digits(whole) || (s.contains('.') && !digits(frac)) {
+        return Err(format!("`{s}` is not a plain decimal amount"));
+    }
+    if frac.len() > decimals as usize {
+        return Err(format!("`{s}` has more than {decimals} decimal places"));
+    }
+    let padded = format!("{whole}{frac:0<width$}", width = decimals as usize);
+    padded.parse::<u128>().map_err(|_| format!("`{s}` is out of range"))
+}
@@ -1,7 +1,13 @@
 #![forbid(unsafe_code)]
 
+pub mod amount;
 pub mod roles;
 pub mod risk;
 pub mod handlers;
+pub mod proposal;
+pub mod site_policy;
+pub mod voting;
+
+pub use amount::{ChatAmount, ParseChatAmountError};
 
 include!(concat!(env!("OUT_DIR"), "/aln_generated.rs"));
@@ -0,0 +1,70 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use cyberretrieval_website_governance::roles::{role_for_stake, GovernanceRole};
+use cyberretrieval_website_governance::{stake_thresholds, ChatAmount, CHAT_DECIMALS};
+use proptest::prelude::*;
+
+fn reference(amount: ChatAmount) -> BigDecimal {
+    BigDecimal::from_str(&amount.to_string()).unwrap()
+}
+
+/// Decimal strings in the accepted grammar: `digits[.digits]`, at most
+/// `CHAT_DECIMALS` fractional digits, leading zeros allowed.
+fn amount_string() -> impl Strategy<Value = String> {
+    let frac = format!("(\\.[0-9]{{1,{}}})?", CHAT_DECIMALS);
+    (0u64..=u64::MAX, "0{0,3}", proptest::string::string_regex(&frac).unwrap())
+        .prop_map(|(whole, zeros, frac)| format!("{zeros}{whole}{frac}"))
+}
+
+proptest! {
+    #[test]
+    fn parse_matches_big_decimal(s in amount_string()) {
+        let amount: ChatAmount = s.parse().unwrap();
+        prop_assert_eq!(reference(amount), BigDecimal::from_str(&s).unwrap());
+        prop_assert_eq!(amount.to_string().parse::<ChatAmount>().unwrap(), amount);
+    }
+
+    #[test]
+    fn ordering_matches_big_decimal(a in amount_string(), b in amount_string()) {
+        let (x, y) = (a.parse::<ChatAmount>().unwrap(), b.parse::<ChatAmount>().unwrap());
+        prop_assert_eq!(x.cmp(&y), reference(x).cmp(&reference(y)));
+    }
+
+    #[test]
+    fn arithmetic_matches_big_decimal(a in amount_string(), b in amount_string()) {
+        let (x, y) = (a.parse::<ChatAmount>().unwrap(), b.parse::<ChatAmount>().unwrap());
+        prop_assert_eq!(reference(x.checked_add(y).unwrap()), reference(x) + reference(y));
+        match x.checked_sub(y) {
+            Some(d) => prop_assert_eq!(reference(d), reference(x) - reference(y)),
+            None => prop_assert!(x < y),
+        }
+    }
+
+    #[test]
+    fn rejects_signs_whitespace_and_extra_precision(s in amount_string(), junk in "[+ \t\n-]") {
+        // `prop_assert!` formats its own expression, so keep braces out of it.
+        let prefixed = format!("{junk}{s}");
+        let suffixed = format!("{s}{junk}");
+        let too_precise = format!("{s}{}", if s.contains('.') { "0000000" } else { ".0000001" });
+        prop_assert!(prefixed.parse::<ChatAmount>().is_err());
+        prop_assert!(suffixed.parse::<ChatAmount>().is_err());
+        prop_assert!(too_precise.parse::<ChatAmount>().is_err());
+    }
+}
+
+#[test]
+fn roles_follow_thresholds_regardless_of_spelling() {
+    let parse = |s: &str| s.parse::<ChatAmount>().unwrap();
+    assert_eq!(role_for_stake(parse("999.999999")), None);
+    assert_eq!(role_for_stake(parse("1000")), Some(GovernanceRole::Stakeholder));
+    assert_eq!(role_for_stake(parse("01000.5")), Some(GovernanceRole::Stakeholder));
+    assert_eq!(role_for_stake(parse("9999.9")), Some(GovernanceRole::Stakeholder));
+    assert_eq!(role_for_stake(parse("10000.0")), Some(GovernanceRole::Council));
+    assert_eq!(role_for_stake(stake_thresholds::SUPERCHAIR_MIN), Some(GovernanceRole::Superchair));
+    assert_eq!(stake_thresholds::STAKEHOLDER_MIN.to_string(), "1000.000000");
+
+    for bad in ["", "+1000", " 1000", "1000 ", ".5", "5.", "1e3", "1,000", "1.2.3", "-1"] {
+        assert!(bad.parse::<ChatAmount>().is_err(), "{bad:?} should not parse");
+    }
+}
@@ -1,7 +1,9 @@
 #![forbid(unsafe_code)]
 
+use std::time::SystemTime;
+
 use crate::policy::{AlnGovernanceShard, RoleThresholds};
-use crate::roles::{GovernanceRole, StakeSnapshot};
+use crate::roles::{GovernanceRole, RoleAssignment, StakeSnapshot};
 
 /// Safety profile stays aligned with neurorights.envelope.citizen.v1.
 #[derive(Clone, Debug)]
@@ -35,27 +37,82 @@ pub struct GovernanceIndices {
 }
 
 impl GovernanceIndices {
-    pub fn aln_bound_profile() -> Self {
+    /// The `risk` defaults and `hexstamp` declared by the shard.
+    pub fn from_aln_shard(shard: &AlnGovernanceShard) -> Self {
         Self {
-            knowledge_factor: 0.92,
-            risk_of_harm: 0.08, // stays under the 0.3 ceiling
-            cybostate_factor: 0.87,
-            hex_stamp: "0x4F91C7AB39D62E11".to_string(),
+            knowledge_factor: shard.default_knowledge_factor,
+            risk_of_harm: shard.default_risk_of_harm, // loader keeps the ceiling ≤ 0.3
+            cybostate_factor: shard.default_cybostate_factor,
+            hex_stamp: shard.hex_stamp.clone(),
         }
     }
+
+    /// Compatibility shim: indices from the repository's asset.chat.stake.v1.
+    pub fn aln_bound_profile() -> Self {
+        Self::from_aln_shard(&AlnGovernanceShard::asset_chat_stake_v1())
+    }
+
+    /// Compatibility shim, same as [`GovernanceIndices::aln_bound_profile`].
+    pub fn governance_module_profile() -> Self {
+        Self::aln_bound_profile()
+    }
 }
 
 /// Pure, retrieval-only eligibility computation with ALN-sourced thresholds.
-pub fn compute_eligibility(
+/// Accepts an [`AlnGovernanceShard`] or, for older callers, [`RoleThresholds`].
+pub fn compute_eligibility<S: AsRef<AlnGovernanceShard>>(
     stake: &StakeSnapshot,
-    aln_shard: &AlnGovernanceShard,
+    aln_shard: &S,
     safety: &GovernanceSafetyProfile,
 ) -> (Vec<GovernanceRole>, GovernanceIndices) {
     // Global Risk-of-Harm ceiling enforced structurally.
     assert!(safety.risk_of_harm_ceiling <= 0.3);
 
-    let thresholds = RoleThresholds::from_aln_shard(aln_shard.clone());
-    let roles = thresholds.eligible_roles(stake);
+    let shard = aln_shard.as_ref();
+    let roles = RoleThresholds::from_aln_shard(shard.clone()).eligible_roles(stake);
+
+    (roles, GovernanceIndices::from_aln_shard(shard))
+}
 
-    (roles, GovernanceIndices::aln_bound_profile())
+/// Build a RoleAssignment without side effects (no writes, no network).
+pub fn assign_role_snapshot(
+    role: GovernanceRole,
+    holder_did: String,
+    aln_scope: String,
+    bostrom_address: String,
+) -> RoleAssignment {
+    RoleAssignment {
+        role,
+        holder_did,
+        aln_scope,
+        bostrom_address,
+        term_start: SystemTime::now(),
+        term_end: None,
+        hex_stamp: "0x6AF08C5D3B917E24D0C42EB1F39A8C72".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compatibility_shims_follow_the_repository_shard() {
+        let shard = AlnGovernanceShard::asset_chat_stake_v1();
+        let safety = GovernanceSafetyProfile::default_profile();
+        let stake = StakeSnapshot { chat_units: 10_000_000_000, cy_units: 0, zen_units: 0, lifeforce_units: 0, contrib_index_milli: 250 };
+
+        let (roles, indices) = compute_eligibility(&stake, &shard, &safety);
+        let (shim_roles, shim_indices) = compute_eligibility(&stake, &RoleThresholds::default_thresholds(), &safety);
+        assert_eq!(roles, [GovernanceRole::Council, GovernanceRole::Proposer]);
+        assert_eq!(shim_roles, roles);
+
+        for profile in [indices, shim_indices, GovernanceIndices::aln_bound_profile(), GovernanceIndices::governance_module_profile()] {
+            assert_eq!(
+                (profile.knowledge_factor, profile.risk_of_harm, profile.cybostate_factor),
+                (shard.default_knowledge_factor, shard.default_risk_of_harm, shard.default_cybostate_factor)
+            );
+            assert_eq!(profile.hex_stamp, shard.hex_stamp);
+        }
+    }
 }
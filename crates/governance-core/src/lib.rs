@@ -0,0 +1,9 @@
+//! Governance roles (Superchair / Council / Proposer) and pure,
+//! retrieval-only eligibility checks. Thresholds come from the
+//! `asset.chat.stake.v1` ALN shard, see [`policy::AlnGovernanceShard`].
+
+#![forbid(unsafe_code)]
+
+pub mod eligibility;
+pub mod policy;
+pub mod roles;
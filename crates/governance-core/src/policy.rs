@@ -1,21 +1,31 @@
 #![forbid(unsafe_code)]
 
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use aln_parser::{AlnDocument, ScalarValue, YamlDocument, YamlNode};
+
 use crate::roles::{GovernanceRole, StakeSnapshot};
 
+pub use crate::eligibility::GovernanceSafetyProfile;
+
 /// Canonical ALN ids for governance bindings.
 pub const ALN_ASSET_CHAT_STAKE_V1: &str = "asset.chat.stake.v1";
 pub const ALN_GOV_CHAT_WEBSITE_V1: &str = "governance.chat.website.v1";
 pub const ALN_GOV_TOTEM_SUPERPOSITION_V1: &str = "governance.totem.superposition.v1";
 
+/// The repository's asset.chat.stake.v1, compiled in.
+const ASSET_CHAT_STAKE_V1_SOURCE: &str = include_str!("../../../aln/asset.chat.stake.v1.aln");
+
 /// Raw, ALN-derived governance thresholds for one policy scope.
-/// These fields are meant to be generated from ALN, not hand-edited.
+/// These fields are loaded from ALN, not hand-edited.
 #[derive(Clone, Debug)]
 pub struct AlnGovernanceShard {
     pub id: String,
     pub version: String,
     pub anchor: String, // DID / registry-chain pointer
 
-    // asset.chat.stake.v1: stake thresholds in CHAT, CY, ZEN, LIFEFORCE units.
+    // asset.chat.stake.v1: stake thresholds in smallest CHAT, CY, ZEN, LIFEFORCE units.
     pub min_chat_for_superchair: u128,
     pub min_chat_for_council: u128,
     pub min_chat_for_proposer: u128,
@@ -24,8 +34,19 @@ pub struct AlnGovernanceShard {
     pub min_zen_for_council: u128,
     pub min_lifeforce_for_superchair: u128,
 
+    // asset.chat.stake.v1 `min_contrib_index`, in thousandths.
+    pub min_contrib_index_for_superchair: u32,
+    pub min_contrib_index_for_council: u32,
+    pub min_contrib_index_for_proposer: u32,
+
+    // asset.chat.stake.v1 `risk`: defaults for GovernanceIndices.
+    pub risk_of_harm_ceiling: f32,
+    pub default_risk_of_harm: f32,
+    pub default_knowledge_factor: f32,
+    pub default_cybostate_factor: f32,
+    pub hex_stamp: String,
+
     // governance.chat.website.v1: contribution / authorship indices.
-    pub min_contribution_index: u32,
     pub min_audit_trail_depth: u32,
 
     // governance.totem.superposition.v1: extra constraints for superchair seats.
@@ -33,9 +54,107 @@ pub struct AlnGovernanceShard {
     pub require_hex_stamped_profile: bool,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ShardLoadError {
+    #[error("cannot read {path}: {source}")]
+    Io { path: PathBuf, source: std::io::Error },
+
+    #[error("ALN parse error: {0}")]
+    Parse(String),
+
+    #[error("expected shard `{expected}`, found `{found}`")]
+    WrongShard { expected: &'static str, found: String },
+
+    #[error("missing `{0}`")]
+    Missing(String),
+
+    #[error("`{key}`: {reason}")]
+    Invalid { key: String, reason: String },
+}
+
 impl AlnGovernanceShard {
-    /// Fallback profile for local testing; in production this should be
-    /// fully generated from the ALN registry via build.rs.
+    /// The repository's asset.chat.stake.v1, parsed once.
+    pub fn asset_chat_stake_v1() -> Self {
+        static SHARD: OnceLock<AlnGovernanceShard> = OnceLock::new();
+        SHARD
+            .get_or_init(|| {
+                Self::from_aln_source(ASSET_CHAT_STAKE_V1_SOURCE)
+                    .expect("aln/asset.chat.stake.v1.aln is a valid stake shard")
+            })
+            .clone()
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ShardLoadError> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|source| ShardLoadError::Io { path: path.to_path_buf(), source })?;
+        Self::from_aln_source(&source)
+    }
+
+    /// Parse an asset.chat.stake.v1 shard. CHAT minimums must not decrease
+    /// from proposer (`stakeholder`) to council to superchair.
+    pub fn from_aln_source(source: &str) -> Result<Self, ShardLoadError> {
+        let parsed = aln_parser::parse(source);
+        if let Some(d) = parsed.diagnostics.iter().find(|d| d.is_error()) {
+            return Err(ShardLoadError::Parse(d.render(ALN_ASSET_CHAT_STAKE_V1)));
+        }
+        let Some(AlnDocument::Yaml(doc)) = parsed.document else {
+            return Err(ShardLoadError::Parse("not a YAML-style shard".into()));
+        };
+        let id = text(&doc, "id")?;
+        if id != ALN_ASSET_CHAT_STAKE_V1 {
+            return Err(ShardLoadError::WrongShard { expected: ALN_ASSET_CHAT_STAKE_V1, found: id });
+        }
+
+        let decimals = number(&doc, "fields.decimals")?;
+        if decimals.fract() != 0.0 || !(0.0..=18.0).contains(&decimals) {
+            return Err(invalid("fields.decimals", "must be a whole number of at most 18"));
+        }
+        let decimals = decimals as u32;
+        let amount = |key: &str| units(&doc, key, decimals);
+        let optional = |key: &str| match doc.get(key) {
+            Some(_) => units(&doc, key, decimals),
+            None => Ok(0),
+        };
+
+        let min_chat_for_proposer = amount("fields.min_stake.stakeholder")?;
+        let min_chat_for_council = amount("fields.min_stake.council")?;
+        let min_chat_for_superchair = amount("fields.min_stake.superchair")?;
+        if !(min_chat_for_proposer <= min_chat_for_council && min_chat_for_council <= min_chat_for_superchair) {
+            return Err(invalid("fields.min_stake", "thresholds must not decrease from stakeholder to superchair"));
+        }
+
+        let risk_of_harm_ceiling = number(&doc, "fields.risk.risk_of_harm_ceiling")? as f32;
+        if risk_of_harm_ceiling > 0.3 {
+            return Err(invalid("fields.risk.risk_of_harm_ceiling", "must stay ≤ 0.3"));
+        }
+
+        Ok(Self {
+            version: text(&doc, "version")?,
+            anchor: text(&doc, "anchors.governance_root")?,
+            id,
+            min_chat_for_superchair,
+            min_chat_for_council,
+            min_chat_for_proposer,
+            min_cy_for_superchair: optional("fields.min_cy_stake.superchair")?,
+            min_zen_for_council: optional("fields.min_zen_stake.council")?,
+            min_lifeforce_for_superchair: optional("fields.min_lifeforce_stake.superchair")?,
+            min_contrib_index_for_superchair: milli(&doc, "fields.min_contrib_index.superchair")?,
+            min_contrib_index_for_council: milli(&doc, "fields.min_contrib_index.council")?,
+            min_contrib_index_for_proposer: milli(&doc, "fields.min_contrib_index.stakeholder")?,
+            risk_of_harm_ceiling,
+            default_risk_of_harm: number(&doc, "fields.risk.default_risk_of_harm")? as f32,
+            default_knowledge_factor: number(&doc, "fields.risk.default_knowledge_factor")? as f32,
+            default_cybostate_factor: number(&doc, "fields.risk.default_cybostate_factor")? as f32,
+            hex_stamp: text(&doc, "hexstamp")?,
+            // Not declared by the stake asset; strictest settings until the
+            // website and totem shards are loaded alongside it.
+            min_audit_trail_depth: 1,
+            require_registry_chain_presence: true,
+            require_hex_stamped_profile: true,
+        })
+    }
+
+    /// Fixed profile for local testing, independent of the ALN file.
     pub fn testing_defaults() -> Self {
         Self {
             id: ALN_ASSET_CHAT_STAKE_V1.to_string(),
@@ -47,7 +166,14 @@ impl AlnGovernanceShard {
             min_cy_for_superchair: 0,
             min_zen_for_council: 0,
             min_lifeforce_for_superchair: 0,
-            min_contribution_index: 1,
+            min_contrib_index_for_superchair: 1,
+            min_contrib_index_for_council: 1,
+            min_contrib_index_for_proposer: 1,
+            risk_of_harm_ceiling: 0.3,
+            default_risk_of_harm: 0.08,
+            default_knowledge_factor: 0.92,
+            default_cybostate_factor: 0.87,
+            hex_stamp: "0x4F91C7AB39D62E11".to_string(),
             min_audit_trail_depth: 1,
             require_registry_chain_presence: true,
             require_hex_stamped_profile: true,
@@ -55,7 +181,70 @@ impl AlnGovernanceShard {
     }
 }
 
-/// Role thresholds now act as a pure adapter over an ALN shard.
+impl AsRef<AlnGovernanceShard> for AlnGovernanceShard {
+    fn as_ref(&self) -> &AlnGovernanceShard {
+        self
+    }
+}
+
+fn invalid(key: &str, reason: impl Into<String>) -> ShardLoadError {
+    ShardLoadError::Invalid { key: key.to_string(), reason: reason.into() }
+}
+
+fn scalar<'a>(doc: &'a YamlDocument, key: &str) -> Result<&'a ScalarValue, ShardLoadError> {
+    match doc.get(key) {
+        Some(YamlNode::Scalar(s)) => Ok(&s.value),
+        Some(_) => Err(invalid(key, "expected a scalar")),
+        None => Err(ShardLoadError::Missing(key.to_string())),
+    }
+}
+
+fn text(doc: &YamlDocument, key: &str) -> Result<String, ShardLoadError> {
+    match scalar(doc, key)? {
+        ScalarValue::Str(s) => Ok(s.clone()),
+        _ => Err(invalid(key, "expected text")),
+    }
+}
+
+fn number(doc: &YamlDocument, key: &str) -> Result<f64, ShardLoadError> {
+    match scalar(doc, key)? {
+        ScalarValue::Int(i) => Ok(*i as f64),
+        ScalarValue::Float(f) => Ok(*f),
+        _ => Err(invalid(key, "expected a number")),
+    }
+}
+
+/// A normalized [0,1] index as thousandths.
+fn milli(doc: &YamlDocument, key: &str) -> Result<u32, ShardLoadError> {
+    let value = number(doc, key)?;
+    if !(0.0..=1.0).contains(&value) {
+        return Err(invalid(key, "must be in [0, 1]"));
+    }
+    Ok((value * 1000.0).round() as u32)
+}
+
+/// A quoted decimal amount (`"1000.000000"`) in smallest units. Amounts
+/// are strings in the shard so no precision is lost to floats.
+fn units(doc: &YamlDocument, key: &str, decimals: u32) -> Result<u128, ShardLoadError> {
+    let s = match scalar(doc, key)? {
+        ScalarValue::Str(s) => s.as_str(),
+        ScalarValue::Int(0) => "0",
+        _ => return Err(invalid(key, "amounts must be quoted decimal strings")),
+    };
+    let (whole, frac) = s.split_once('.').unwrap_or((s, ""));
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !digits(whole) || (s.contains('.') && !digits(frac)) {
+        return Err(invalid(key, format!("`{s}` is not a plain decimal amount")));
+    }
+    if frac.len() > decimals as usize {
+        return Err(invalid(key, format!("`{s}` has more than {decimals} decimal places")));
+    }
+    format!("{whole}{frac:0<width$}", width = decimals as usize)
+        .parse()
+        .map_err(|_| invalid(key, format!("`{s}` is out of range")))
+}
+
+/// Role thresholds act as a pure adapter over an ALN shard.
 #[derive(Clone, Debug)]
 pub struct RoleThresholds {
     pub shard: AlnGovernanceShard,
@@ -66,30 +255,38 @@ impl RoleThresholds {
         Self { shard }
     }
 
+    /// Compatibility shim: thresholds from the repository's asset.chat.stake.v1.
+    pub fn default_thresholds() -> Self {
+        Self::from_aln_shard(AlnGovernanceShard::asset_chat_stake_v1())
+    }
+
+    /// Each role needs its CHAT minimum and contribution index; a zero
+    /// companion-asset minimum (CY, ZEN, LIFEFORCE) is no requirement.
+    /// Council is the exception: a nonzero ZEN minimum met stands in for
+    /// the contribution index.
     pub fn eligible_roles(&self, stake: &StakeSnapshot) -> Vec<GovernanceRole> {
         let mut roles = Vec::new();
         let s = &self.shard;
 
-        // Superchair: high CHAT stake + contribution index, plus optional CY / Lifeforce.
-        if stake.chat >= s.min_chat_for_superchair
-            && stake.contribution_index >= s.min_contribution_index
-            && stake.cy >= s.min_cy_for_superchair
-            && stake.lifeforce >= s.min_lifeforce_for_superchair
+        if stake.chat_units >= s.min_chat_for_superchair
+            && stake.contrib_index_milli >= s.min_contrib_index_for_superchair
+            && stake.cy_units >= s.min_cy_for_superchair
+            && stake.lifeforce_units >= s.min_lifeforce_for_superchair
         {
             roles.push(GovernanceRole::Superchair);
         }
 
-        // Council: mid-tier CHAT + either ZEN stake or contribution index.
-        if stake.chat >= s.min_chat_for_council
-            && (stake.contribution_index >= s.min_contribution_index
-                || stake.zen >= s.min_zen_for_council)
+        // Council: mid-tier CHAT + either contribution index or ZEN stake.
+        if stake.chat_units >= s.min_chat_for_council
+            && (stake.contrib_index_milli >= s.min_contrib_index_for_council
+                || (s.min_zen_for_council > 0 && stake.zen_units >= s.min_zen_for_council))
         {
             roles.push(GovernanceRole::Council);
         }
 
         // Proposer: lowest threshold, essentially “can open an Eibon proposal”.
-        if stake.chat >= s.min_chat_for_proposer
-            && stake.contribution_index >= s.min_contribution_index
+        if stake.chat_units >= s.min_chat_for_proposer
+            && stake.contrib_index_milli >= s.min_contrib_index_for_proposer
         {
             roles.push(GovernanceRole::Proposer);
         }
@@ -97,3 +294,60 @@ impl RoleThresholds {
         roles
     }
 }
+
+impl AsRef<AlnGovernanceShard> for RoleThresholds {
+    fn as_ref(&self) -> &AlnGovernanceShard {
+        &self.shard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake(chat_units: u128, zen_units: u128, contrib_index_milli: u32) -> StakeSnapshot {
+        StakeSnapshot { chat_units, cy_units: 0, zen_units, lifeforce_units: 0, contrib_index_milli }
+    }
+
+    #[test]
+    fn loads_the_repository_stake_shard() {
+        let shard = AlnGovernanceShard::asset_chat_stake_v1();
+        assert_eq!(shard.min_chat_for_proposer, 1_000_000_000);
+        assert_eq!(shard.min_chat_for_council, 10_000_000_000);
+        assert_eq!(shard.min_chat_for_superchair, 100_000_000_000);
+        assert_eq!(
+            (shard.min_contrib_index_for_proposer, shard.min_contrib_index_for_council, shard.min_contrib_index_for_superchair),
+            (0, 250, 500)
+        );
+        assert_eq!((shard.min_cy_for_superchair, shard.min_zen_for_council, shard.min_lifeforce_for_superchair), (0, 0, 0));
+        assert_eq!(shard.hex_stamp, "0x4F91C7AB39D62E11");
+
+        let council = stake(10_000_000_000, 0, 250);
+        let roles = RoleThresholds::default_thresholds().eligible_roles(&council);
+        assert_eq!(roles, [GovernanceRole::Council, GovernanceRole::Proposer]);
+
+        let loose = ASSET_CHAT_STAKE_V1_SOURCE.replace("council:     \"10000.000000\"", "council:     \"100.000000\"");
+        assert!(matches!(
+            AlnGovernanceShard::from_aln_source(&loose),
+            Err(ShardLoadError::Invalid { key, .. }) if key == "fields.min_stake"
+        ));
+    }
+
+    #[test]
+    fn default_thresholds_shim_counts_smallest_units_and_council_takes_index_or_zen() {
+        use GovernanceRole::*;
+        let thresholds = RoleThresholds::default_thresholds();
+        // The old shim's whole-CHAT superchair minimum is a dust stake now.
+        assert!(thresholds.eligible_roles(&stake(10_000, 0, 1)).is_empty());
+        assert_eq!(thresholds.eligible_roles(&stake(100_000_000_000, 0, 500)), [Superchair, Council, Proposer]);
+        // A zero ZEN minimum is no path onto the council.
+        assert_eq!(thresholds.eligible_roles(&stake(10_000_000_000, 0, 249)), [Proposer]);
+
+        let mut shard = AlnGovernanceShard::asset_chat_stake_v1();
+        shard.min_zen_for_council = 5_000_000;
+        let thresholds = RoleThresholds::from_aln_shard(shard);
+        assert_eq!(thresholds.eligible_roles(&stake(10_000_000_000, 5_000_000, 0)), [Council, Proposer]);
+        assert_eq!(thresholds.eligible_roles(&stake(10_000_000_000, 4_999_999, 0)), [Proposer]);
+        assert_eq!(thresholds.eligible_roles(&stake(10_000_000_000, 0, 250)), [Council, Proposer]);
+    }
+}
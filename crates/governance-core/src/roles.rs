@@ -14,17 +14,21 @@ pub enum GovernanceRole {
 }
 
 /// Minimal stake + contribution snapshot used for eligibility checks.
+/// Stakes are in smallest units (`10^decimals` per whole token, as
+/// declared by asset.chat.stake.v1). The field names carry the unit so
+/// callers written against the old whole-token fields fail to compile.
 #[derive(Clone, Debug)]
 pub struct StakeSnapshot {
-    pub chat: u128,
-    pub cy: u128,
-    pub zen: u128,
-    pub lifeforce: u128,
-    pub contribution_index: u32,
+    pub chat_units: u128,
+    pub cy_units: u128,
+    pub zen_units: u128,
+    pub lifeforce_units: u128,
+    /// Normalized [0,1] contribution index, in thousandths.
+    pub contrib_index_milli: u32,
 }
 
 /// Immutable role assignment with authorship and time bounds.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RoleAssignment {
     pub role: GovernanceRole,
     pub holder_did: String,
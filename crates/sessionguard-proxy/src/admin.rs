@@ -0,0 +1,134 @@
+#![forbid(unsafe_code)]
+
+use crate::audit::{AccessLog, AuditEvent, SessionRevokeEvent};
+use crate::store::{RevokeTarget, SessionStore};
+use http::{Method, Request, Response, StatusCode};
+use hyper::Body;
+use sha2::{Digest, Sha256};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// Environment variable the CLI reads the admin bearer token from.
+pub const ADMIN_TOKEN_ENV: &str = "SESSIONGUARD_ADMIN_TOKEN";
+
+/// Handles for the loopback admin listener.
+///
+/// `GET /sessions[?host_did=<did>]` lists active sessions;
+/// `POST /revoke` with a `RevokeTarget` body (`{"session": id}`,
+/// `{"device": fingerprint}` or `{"host_did": did}`) revokes them.
+#[derive(Clone)]
+pub struct AdminState {
+    pub sessions: Arc<SessionStore>,
+    pub access_log: Arc<AccessLog>,
+    pub token_sha256: Option<String>,
+}
+
+pub async fn handle(
+    req: Request<Body>,
+    remote_addr: SocketAddr,
+    state: AdminState,
+    now_utc: &str,
+) -> Response<Body> {
+    if !authorized(req.headers(), state.token_sha256.as_deref()) {
+        return json(StatusCode::UNAUTHORIZED, serde_json::json!({ "error": "admin_unauthorized" }));
+    }
+
+    match (req.method().clone(), req.uri().path()) {
+        (Method::GET, "/sessions") => {
+            let host_did = req
+                .uri()
+                .query()
+                .and_then(|q| q.split('&').find_map(|kv| kv.strip_prefix("host_did=")));
+            json(StatusCode::OK, serde_json::json!(state.sessions.list(host_did)))
+        }
+        (Method::POST, "/revoke") => {
+            let raw = match hyper::body::to_bytes(req.into_body()).await {
+                Ok(raw) => raw,
+                Err(_) => return json(StatusCode::BAD_REQUEST, serde_json::json!({ "error": "body_read" })),
+            };
+            let target: RevokeTarget = match serde_json::from_slice(&raw) {
+                Ok(t) => t,
+                Err(_) => {
+                    return json(StatusCode::BAD_REQUEST, serde_json::json!({ "error": "invalid_revoke_target" }))
+                }
+            };
+
+            let ended_sessions = state.sessions.revoke(&target, now_utc);
+            let event = SessionRevokeEvent {
+                target,
+                ended_sessions: ended_sessions.clone(),
+                admin_addr: Some(remote_addr.to_string()),
+            };
+            if let Err(e) = state.access_log.append_event(AuditEvent::SessionRevoke(event), now_utc) {
+                eprintln!("sessionguard: failed to append audit entry: {}", e);
+            }
+            json(StatusCode::OK, serde_json::json!({ "ended_sessions": ended_sessions }))
+        }
+        _ => json(StatusCode::NOT_FOUND, serde_json::json!({ "error": "not_found" })),
+    }
+}
+
+fn authorized(headers: &http::HeaderMap, token_sha256: Option<&str>) -> bool {
+    let Some(expected) = token_sha256 else {
+        return true;
+    };
+    headers
+        .get(http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|token| hex::encode(Sha256::digest(token.trim().as_bytes())))
+        .is_some_and(|digest| digest.eq_ignore_ascii_case(expected))
+}
+
+fn json(status: StatusCode, body: serde_json::Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// One admin call from the CLI; returns the status and response body.
+pub async fn call(
+    admin_addr: &str,
+    method: Method,
+    path: &str,
+    body: Option<serde_json::Value>,
+) -> anyhow::Result<(StatusCode, String)> {
+    let mut req = Request::builder()
+        .method(method)
+        .uri(format!("http://{}{}", admin_addr, path));
+    if let Ok(token) = std::env::var(ADMIN_TOKEN_ENV) {
+        req = req.header(http::header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = match body {
+        Some(v) => {
+            req = req.header("content-type", "application/json");
+            Body::from(v.to_string())
+        }
+        None => Body::empty(),
+    };
+
+    let resp = hyper::Client::new().request(req.body(body)?).await?;
+    let status = resp.status();
+    let raw = hyper::body::to_bytes(resp.into_body()).await?;
+    Ok((status, String::from_utf8_lossy(&raw).into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bearer_token_is_checked_against_its_digest() {
+        let expected = hex::encode(Sha256::digest(b"s3cret"));
+        let mut headers = http::HeaderMap::new();
+        assert!(authorized(&headers, None));
+        assert!(!authorized(&headers, Some(&expected)));
+
+        headers.insert(http::header::AUTHORIZATION, "Bearer wrong".parse().unwrap());
+        assert!(!authorized(&headers, Some(&expected)));
+        headers.insert(http::header::AUTHORIZATION, "Bearer s3cret".parse().unwrap());
+        assert!(authorized(&headers, Some(&expected)));
+    }
+}
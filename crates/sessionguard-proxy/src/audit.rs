@@ -0,0 +1,334 @@
+#![forbid(unsafe_code)]
+
+use crate::session::Role;
+use crate::store::RevokeTarget;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// `prev_hash` of the first entry in a fresh log.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Outcome of the SessionGuard check for one request.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessDecision {
+    /// Guard passed; the request was (or was attempted to be) forwarded.
+    Allow,
+    /// Guard or role check failed; nothing reached the backend.
+    Deny,
+}
+
+/// One line of the proxy audit log (JSONL).
+///
+/// Entries are hash-chained: `entry_hash = sha256(prev_hash | canonical_json)`,
+/// where `canonical_json` is the entry serialized with `entry_hash` empty.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccessLogEntry {
+    pub seq: u64,
+    pub timestamp_utc: String,
+    #[serde(flatten)]
+    pub event: AuditEvent,
+    pub prev_hash: String,
+    pub entry_hash: String,
+}
+
+/// What an audit entry records; serialized as an `"event"` tag plus fields.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditEvent {
+    Access(AccessEvent),
+    ConfigReload(ConfigReloadEvent),
+    SessionRevoke(SessionRevokeEvent),
+}
+
+/// One proxied (or refused) request.
+///
+/// `trace_id` uses the same folding as the router's `make_trace_id`, and is
+/// forwarded to the backend as `x-trace-id` so both logs can be joined.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccessEvent {
+    pub trace_id: String,
+    pub host_did: Option<String>,
+    pub roles: Vec<Role>,
+    pub remote_addr: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub decision: AccessDecision,
+    /// `SessionGuardError` reason code or proxy error code, if any.
+    pub reason: Option<String>,
+    pub backend_status: Option<u16>,
+    pub latency_ms: u64,
+}
+
+/// One attempt to reload `ProxyConfig` (SIGHUP or file change).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigReloadEvent {
+    pub config_path: String,
+    /// Hex SHA-256 of the config file bytes before and after.
+    pub old_digest: String,
+    pub new_digest: String,
+    pub applied: bool,
+    /// Validation errors when `applied` is false.
+    pub errors: Vec<String>,
+    /// Changed settings that only take effect after a restart.
+    pub restart_required: Vec<String>,
+}
+
+/// A revocation applied through the admin endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionRevokeEvent {
+    pub target: RevokeTarget,
+    /// Session ids that were active and are now refused.
+    pub ended_sessions: Vec<String>,
+    pub admin_addr: Option<String>,
+}
+
+/// Request-level facts collected while the proxy handles a request.
+#[derive(Debug, Clone, Default)]
+pub struct AccessRecord {
+    pub trace_id: String,
+    pub host_did: Option<String>,
+    pub roles: Vec<Role>,
+    pub remote_addr: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub reason: Option<&'static str>,
+    pub backend_status: Option<u16>,
+    pub guard_passed: bool,
+}
+
+#[derive(Debug)]
+pub enum AuditError {
+    Io(std::io::Error),
+    Serialization(serde_json::Error),
+    /// Entry at `seq` does not link to its predecessor or its hash is wrong.
+    BrokenChain { seq: u64 },
+}
+
+impl std::fmt::Display for AuditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditError::Io(e) => write!(f, "audit log io error: {}", e),
+            AuditError::Serialization(e) => write!(f, "audit log serialization error: {}", e),
+            AuditError::BrokenChain { seq } => write!(f, "audit log chain broken at seq {}", seq),
+        }
+    }
+}
+
+impl std::error::Error for AuditError {}
+
+struct ChainHead {
+    next_seq: u64,
+    last_hash: String,
+}
+
+/// Append-only, hash-chained JSONL access log.
+pub struct AccessLog {
+    path: PathBuf,
+    head: Mutex<ChainHead>,
+}
+
+impl AccessLog {
+    /// Open (or create) the log, resuming the chain from its last entry.
+    pub fn open<P: Into<PathBuf>>(path: P) -> Result<Self, AuditError> {
+        let path = path.into();
+        let head = match fs::read_to_string(&path) {
+            Ok(raw) => match raw.lines().rev().find(|l| !l.trim().is_empty()) {
+                Some(last) => {
+                    let entry: AccessLogEntry =
+                        serde_json::from_str(last).map_err(AuditError::Serialization)?;
+                    ChainHead {
+                        next_seq: entry.seq + 1,
+                        last_hash: entry.entry_hash,
+                    }
+                }
+                None => ChainHead::genesis(),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => ChainHead::genesis(),
+            Err(e) => return Err(AuditError::Io(e)),
+        };
+
+        Ok(Self {
+            path,
+            head: Mutex::new(head),
+        })
+    }
+
+    /// Seal `record` into the chain as an `access` event.
+    pub fn append(
+        &self,
+        record: &AccessRecord,
+        timestamp_utc: &str,
+        latency_ms: u64,
+    ) -> Result<AccessLogEntry, AuditError> {
+        let event = AccessEvent {
+            trace_id: record.trace_id.clone(),
+            host_did: record.host_did.clone(),
+            roles: record.roles.clone(),
+            remote_addr: record.remote_addr.clone(),
+            method: record.method.clone(),
+            route: record.route.clone(),
+            decision: if record.guard_passed {
+                AccessDecision::Allow
+            } else {
+                AccessDecision::Deny
+            },
+            reason: record.reason.map(str::to_string),
+            backend_status: record.backend_status,
+            latency_ms,
+        };
+        self.append_event(AuditEvent::Access(event), timestamp_utc)
+    }
+
+    /// Seal any event into the chain and append it as one JSON line.
+    pub fn append_event(
+        &self,
+        event: AuditEvent,
+        timestamp_utc: &str,
+    ) -> Result<AccessLogEntry, AuditError> {
+        let mut head = self.head.lock().unwrap_or_else(|p| p.into_inner());
+
+        let mut entry = AccessLogEntry {
+            seq: head.next_seq,
+            timestamp_utc: timestamp_utc.to_string(),
+            event,
+            prev_hash: head.last_hash.clone(),
+            entry_hash: String::new(),
+        };
+        entry.entry_hash = entry_hash(&entry)?;
+
+        let line = serde_json::to_string(&entry).map_err(AuditError::Serialization)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(AuditError::Io)?;
+        writeln!(file, "{}", line).map_err(AuditError::Io)?;
+
+        head.next_seq += 1;
+        head.last_hash = entry.entry_hash.clone();
+        Ok(entry)
+    }
+}
+
+impl ChainHead {
+    fn genesis() -> Self {
+        Self {
+            next_seq: 0,
+            last_hash: GENESIS_HASH.to_string(),
+        }
+    }
+}
+
+/// Compute the chained hash of `entry` (its own `entry_hash` is ignored).
+pub fn entry_hash(entry: &AccessLogEntry) -> Result<String, AuditError> {
+    let mut unsealed = entry.clone();
+    unsealed.entry_hash = String::new();
+    let canonical = serde_json::to_string(&unsealed).map_err(AuditError::Serialization)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(entry.prev_hash.as_bytes());
+    hasher.update(b"|");
+    hasher.update(canonical.as_bytes());
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Re-walk a JSONL access log and check every link; returns the entry count.
+pub fn verify_chain(raw: &str) -> Result<u64, AuditError> {
+    let mut expected_prev = GENESIS_HASH.to_string();
+    let mut count = 0u64;
+
+    for line in raw.lines().filter(|l| !l.trim().is_empty()) {
+        let entry: AccessLogEntry = serde_json::from_str(line).map_err(AuditError::Serialization)?;
+        if entry.seq != count
+            || entry.prev_hash != expected_prev
+            || entry.entry_hash != entry_hash(&entry)?
+        {
+            return Err(AuditError::BrokenChain { seq: entry.seq });
+        }
+        expected_prev = entry.entry_hash;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Deterministic trace id, folded the same way as the router's `make_trace_id`
+/// (`user_did|prompt|bucket`), with the route standing in for the prompt.
+pub fn make_trace_id(host_did: &str, route: &str, bucket: &str) -> String {
+    let input = format!("{}|{}|{}", host_did, route, bucket);
+    let mut acc: u64 = 0xcbf29ce484222325;
+    for b in input.as_bytes() {
+        acc = acc.wrapping_mul(1099511628211);
+        acc ^= *b as u64;
+    }
+    format!("0x{:016x}", acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(reason: Option<&'static str>) -> AccessRecord {
+        AccessRecord {
+            trace_id: make_trace_id("did:example:host", "/v1/chat", "2026-01-01T00:00:00Z"),
+            host_did: Some("did:example:host".into()),
+            roles: vec![Role::Chat],
+            remote_addr: Some("127.0.0.1:50000".into()),
+            method: "POST".into(),
+            route: "/v1/chat".into(),
+            reason,
+            backend_status: reason.is_none().then_some(200),
+            guard_passed: reason.is_none(),
+        }
+    }
+
+    #[test]
+    fn chain_resumes_across_reopen_and_detects_tampering() {
+        let path = std::env::temp_dir().join(format!("sg-access-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let log = AccessLog::open(&path).unwrap();
+        log.append(&record(None), "2026-01-01T00:00:00Z", 12).unwrap();
+        drop(log);
+
+        let log = AccessLog::open(&path).unwrap();
+        let second = log
+            .append(&record(Some("session_expired")), "2026-01-01T00:00:01Z", 1)
+            .unwrap();
+        assert_eq!(second.seq, 1);
+        assert!(matches!(
+            second.event,
+            AuditEvent::Access(AccessEvent { decision: AccessDecision::Deny, .. })
+        ));
+
+        log.append_event(
+            AuditEvent::ConfigReload(ConfigReloadEvent {
+                config_path: "sessionguard-proxy.toml".into(),
+                old_digest: "aa".into(),
+                new_digest: "bb".into(),
+                applied: true,
+                errors: Vec::new(),
+                restart_required: Vec::new(),
+            }),
+            "2026-01-01T00:00:02Z",
+        )
+        .unwrap();
+
+        let raw = fs::read_to_string(&path).unwrap();
+        assert_eq!(verify_chain(&raw).unwrap(), 3);
+        assert!(raw.lines().nth(2).unwrap().contains(r#""event":"config_reload""#));
+
+        let tampered = raw.replacen("session_expired", "none", 1);
+        assert!(matches!(
+            verify_chain(&tampered),
+            Err(AuditError::BrokenChain { seq: 1 })
+        ));
+
+        let _ = fs::remove_file(&path);
+    }
+}
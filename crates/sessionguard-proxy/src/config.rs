@@ -1,6 +1,10 @@
 #![forbid(unsafe_code)]
 
+use crate::session::Role;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::net::SocketAddr;
 use std::{fs, path::Path};
 
 #[derive(Debug, Deserialize, Clone)]
@@ -8,15 +12,375 @@ pub struct ProxyConfig {
     pub listen_addr: String,
     pub backend_base_url: String,
     pub expected_device_fingerprint: String,
-    pub dns_fail_closed: bool,
-    pub doh_pinned: bool,
-    pub tls_pinned: bool,
+    /// Host-written JSON attestation of BCI and resolver posture
+    /// (see `probe::PostureAttestation`); absent means fail closed.
+    #[serde(default)]
+    pub bci_attestation_path: Option<String>,
+    /// Inbound TLS termination; plaintext listener when absent.
+    #[serde(default)]
+    pub tls: Option<TlsTermination>,
+    #[serde(default)]
+    pub upstream: UpstreamConfig,
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    /// Longest-prefix route table; when empty every route requires `Chat`.
+    #[serde(default)]
+    pub routes: Vec<RoutePolicy>,
+    #[serde(default)]
+    pub revocation: RevocationList,
+    #[serde(default)]
+    pub sessions: SessionLimits,
+    #[serde(default)]
+    pub admin: AdminConfig,
+    /// Hash-chained JSONL access log (see `audit::AccessLogEntry`).
+    #[serde(default = "default_access_log_path")]
+    pub access_log_path: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct TlsTermination {
+    pub cert_path: String,
+    pub key_path: String,
+    /// PEM bundle of CAs for client certificates; enables mutual TLS.
+    #[serde(default)]
+    pub client_ca_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct UpstreamConfig {
+    /// Hex SHA-256 of the backend's SubjectPublicKeyInfo; required for https backends.
+    #[serde(default)]
+    pub spki_sha256_pins: Vec<String>,
+}
+
+/// Body caps and upstream timeouts; every field has a conservative default.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct LimitsConfig {
+    pub max_request_body_bytes: u64,
+    pub max_response_body_bytes: u64,
+    pub connect_timeout_ms: u64,
+    /// Request start to end of response body (non-streaming responses).
+    pub total_timeout_ms: u64,
+    /// Longest gap between chunks of a `text/event-stream` response.
+    pub stream_idle_timeout_ms: u64,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_request_body_bytes: 1024 * 1024,
+            max_response_body_bytes: 16 * 1024 * 1024,
+            connect_timeout_ms: 5_000,
+            total_timeout_ms: 60_000,
+            stream_idle_timeout_ms: 30_000,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct RoutePolicy {
+    pub prefix: String,
+    /// Every listed role must be present in the session token.
+    pub required_roles: Vec<Role>,
+}
+
+/// Statically revoked identities; checked before SessionGuard validation.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct RevocationList {
+    pub host_dids: Vec<String>,
+    pub device_fingerprints: Vec<String>,
+}
+
+/// Longest `sessions.idle_timeout_secs` accepted: one year.
+const MAX_SESSION_IDLE_TIMEOUT_SECS: u64 = 365 * 24 * 60 * 60;
+
+/// Concurrent-session cap and where the session store is persisted.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct SessionLimits {
+    /// Active sessions allowed per device fingerprint; 0 disables the cap.
+    pub max_per_device: usize,
+    /// A session unseen for this long stops counting against the device
+    /// cap; 0 keeps sessions until they expire or are revoked.
+    pub idle_timeout_secs: u64,
+    pub store_path: String,
+}
+
+impl Default for SessionLimits {
+    fn default() -> Self {
+        Self {
+            max_per_device: 3,
+            idle_timeout_secs: 1800,
+            store_path: "sessionguard-sessions.json".to_string(),
+        }
+    }
+}
+
+/// Loopback-only admin listener for listing and revoking sessions.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct AdminConfig {
+    pub listen_addr: String,
+    /// Hex SHA-256 of the bearer token the admin endpoint requires, if any.
+    pub token_sha256: Option<String>,
+}
+
+impl Default for AdminConfig {
+    fn default() -> Self {
+        Self {
+            listen_addr: "127.0.0.1:8081".to_string(),
+            token_sha256: None,
+        }
+    }
+}
+
+fn default_access_log_path() -> String {
+    "sessionguard-access.jsonl".to_string()
+}
+
+/// Every problem found in a config file, reported together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigErrors(pub Vec<String>);
+
+impl std::fmt::Display for ConfigErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid sessionguard-proxy config ({} errors)", self.0.len())?;
+        for e in &self.0 {
+            write!(f, "\n  - {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigErrors {}
+
+/// A validated config plus the digest of the bytes it came from.
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    pub cfg: ProxyConfig,
+    /// Hex SHA-256 of the config file contents.
+    pub digest: String,
 }
 
 impl ProxyConfig {
-    pub fn load_from_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
-        let raw = fs::read_to_string(path)?;
-        let cfg = toml::from_str(&raw)?;
-        Ok(cfg)
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<LoadedConfig, ConfigErrors> {
+        let path = path.as_ref();
+        let raw = fs::read(path)
+            .map_err(|e| ConfigErrors(vec![format!("{}: {}", path.display(), e)]))?;
+        let digest = hex::encode(Sha256::digest(&raw));
+
+        let text = std::str::from_utf8(&raw)
+            .map_err(|e| ConfigErrors(vec![format!("{}: not utf-8: {}", path.display(), e)]))?;
+        let cfg: ProxyConfig = toml::from_str(text)
+            .map_err(|e| ConfigErrors(vec![format!("{}: {}", path.display(), e)]))?;
+        cfg.validate()?;
+
+        Ok(LoadedConfig { cfg, digest })
+    }
+
+    /// Check every field and report all problems at once.
+    pub fn validate(&self) -> Result<(), ConfigErrors> {
+        let mut errors = Vec::new();
+
+        if self.listen_addr.parse::<SocketAddr>().is_err() {
+            errors.push(format!("listen_addr {:?} is not a socket address", self.listen_addr));
+        }
+
+        match self.backend_base_url.parse::<http::Uri>() {
+            Ok(uri) => {
+                let scheme = uri.scheme_str().unwrap_or("");
+                if scheme != "http" && scheme != "https" {
+                    errors.push(format!("backend_base_url scheme must be http or https, got {:?}", scheme));
+                }
+                if uri.authority().is_none() {
+                    errors.push("backend_base_url has no host".to_string());
+                }
+                if scheme == "https" && self.upstream.spki_sha256_pins.is_empty() {
+                    errors.push("https backend requires upstream.spki_sha256_pins".to_string());
+                }
+            }
+            Err(e) => errors.push(format!("backend_base_url: {}", e)),
+        }
+
+        for pin in &self.upstream.spki_sha256_pins {
+            let pin = pin.trim();
+            if pin.len() != 64 || !pin.chars().all(|c| c.is_ascii_hexdigit()) {
+                errors.push(format!("upstream pin {:?} is not 64 hex chars", pin));
+            }
+        }
+
+        if self.expected_device_fingerprint.trim().is_empty() {
+            errors.push("expected_device_fingerprint is empty".to_string());
+        }
+
+        if let Some(tls) = &self.tls {
+            let files = [Some(&tls.cert_path), Some(&tls.key_path), tls.client_ca_path.as_ref()];
+            for file in files.into_iter().flatten() {
+                if !Path::new(file).is_file() {
+                    errors.push(format!("tls file {:?} does not exist", file));
+                }
+            }
+        }
+
+        let limits = [
+            ("max_request_body_bytes", self.limits.max_request_body_bytes),
+            ("max_response_body_bytes", self.limits.max_response_body_bytes),
+            ("connect_timeout_ms", self.limits.connect_timeout_ms),
+            ("total_timeout_ms", self.limits.total_timeout_ms),
+            ("stream_idle_timeout_ms", self.limits.stream_idle_timeout_ms),
+        ];
+        for (name, value) in limits {
+            if value == 0 {
+                errors.push(format!("limits.{} must be > 0", name));
+            }
+        }
+
+        let mut prefixes = BTreeSet::new();
+        for route in &self.routes {
+            if !route.prefix.starts_with('/') {
+                errors.push(format!("route prefix {:?} must start with '/'", route.prefix));
+            }
+            if !prefixes.insert(route.prefix.as_str()) {
+                errors.push(format!("route prefix {:?} is listed twice", route.prefix));
+            }
+        }
+
+        if self.access_log_path.trim().is_empty() {
+            errors.push("access_log_path is empty".to_string());
+        }
+
+        if self.sessions.idle_timeout_secs > MAX_SESSION_IDLE_TIMEOUT_SECS {
+            errors.push(format!(
+                "sessions.idle_timeout_secs must be at most {}",
+                MAX_SESSION_IDLE_TIMEOUT_SECS
+            ));
+        }
+        if self.sessions.store_path.trim().is_empty() {
+            errors.push("sessions.store_path is empty".to_string());
+        }
+        match self.admin.listen_addr.parse::<SocketAddr>() {
+            Ok(addr) if !addr.ip().is_loopback() => {
+                errors.push(format!("admin.listen_addr {} must be a loopback address", addr));
+            }
+            Ok(_) => {}
+            Err(_) => errors.push(format!(
+                "admin.listen_addr {:?} is not a socket address",
+                self.admin.listen_addr
+            )),
+        }
+        if let Some(token) = &self.admin.token_sha256 {
+            if token.len() != 64 || !token.chars().all(|c| c.is_ascii_hexdigit()) {
+                errors.push("admin.token_sha256 is not 64 hex chars".to_string());
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigErrors(errors))
+        }
+    }
+
+    /// Roles required for `path`, or `None` when no route admits it.
+    pub fn required_roles(&self, path: &str) -> Option<Vec<Role>> {
+        if self.routes.is_empty() {
+            return Some(vec![Role::Chat]);
+        }
+        self.routes
+            .iter()
+            .filter(|r| path.starts_with(&r.prefix))
+            .max_by_key(|r| r.prefix.len())
+            .map(|r| r.required_roles.clone())
+    }
+
+    /// Settings in `next` that a live reload cannot apply.
+    pub fn restart_required(&self, next: &ProxyConfig) -> Vec<String> {
+        let mut changed = Vec::new();
+        if self.listen_addr != next.listen_addr {
+            changed.push("listen_addr".to_string());
+        }
+        if self.tls != next.tls {
+            changed.push("tls".to_string());
+        }
+        if self.access_log_path != next.access_log_path {
+            changed.push("access_log_path".to_string());
+        }
+        if self.sessions.store_path != next.sessions.store_path {
+            changed.push("sessions.store_path".to_string());
+        }
+        if self.admin != next.admin {
+            changed.push("admin".to_string());
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &str) -> ProxyConfig {
+        toml::from_str(raw).unwrap()
+    }
+
+    #[test]
+    fn validation_reports_every_error() {
+        let cfg = parse(
+            r#"
+            listen_addr = "not-an-addr"
+            backend_base_url = "https://backend.example"
+            expected_device_fingerprint = ""
+
+            [upstream]
+            spki_sha256_pins = ["xyz"]
+
+            [limits]
+            total_timeout_ms = 0
+
+            [sessions]
+            idle_timeout_secs = 9223372036854775807
+
+            [admin]
+            listen_addr = "0.0.0.0:8081"
+
+            [[routes]]
+            prefix = "v1"
+            required_roles = ["Chat"]
+            "#,
+        );
+        let errors = cfg.validate().unwrap_err().0;
+        assert_eq!(errors.len(), 7, "{:#?}", errors);
+    }
+
+    #[test]
+    fn shipped_example_config_is_valid() {
+        parse(include_str!("../sessionguard-proxy.toml")).validate().unwrap();
+    }
+
+    #[test]
+    fn longest_route_prefix_wins() {
+        let cfg = parse(
+            r#"
+            listen_addr = "127.0.0.1:8080"
+            backend_base_url = "http://127.0.0.1:9000"
+            expected_device_fingerprint = "fp"
+
+            [[routes]]
+            prefix = "/"
+            required_roles = ["Chat"]
+
+            [[routes]]
+            prefix = "/governance"
+            required_roles = ["Chat", "Governance"]
+            "#,
+        );
+        assert!(cfg.validate().is_ok());
+        assert_eq!(cfg.required_roles("/v1/chat"), Some(vec![Role::Chat]));
+        assert_eq!(
+            cfg.required_roles("/governance/vote"),
+            Some(vec![Role::Chat, Role::Governance])
+        );
     }
 }
@@ -0,0 +1,27 @@
+#![forbid(unsafe_code)]
+
+use std::error::Error;
+
+/// Every error reachable from `err`, outermost first.
+///
+/// hyper and tokio-rustls nest `io::Error`s whose `source()` skips the
+/// wrapped error, so both `source()` and `io::Error::get_ref()` are followed.
+pub fn cause_chain<'a>(err: &'a (dyn Error + 'static)) -> Vec<&'a (dyn Error + 'static)> {
+    let mut out = Vec::new();
+    let mut pending = vec![err];
+    while let Some(e) = pending.pop() {
+        out.push(e);
+        if let Some(source) = e.source() {
+            pending.push(source);
+        }
+        if let Some(inner) = e.downcast_ref::<std::io::Error>().and_then(|io| io.get_ref()) {
+            pending.push(inner);
+        }
+    }
+    out
+}
+
+/// First cause of type `T` in `err`'s chain.
+pub fn find_cause<'a, T: Error + 'static>(err: &'a (dyn Error + 'static)) -> Option<&'a T> {
+    cause_chain(err).into_iter().find_map(|e| e.downcast_ref::<T>())
+}
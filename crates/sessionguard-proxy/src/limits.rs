@@ -0,0 +1,254 @@
+#![forbid(unsafe_code)]
+
+use futures_util::Stream;
+use hyper::body::Bytes;
+use hyper::Body;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::{Instant, Sleep};
+
+/// Hop-by-hop headers (RFC 9110 §7.6.1) that must not cross the proxy.
+/// Framing is re-derived per hop, so chunked bodies are re-chunked by hyper.
+pub const HOP_BY_HOP: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+pub fn is_hop_by_hop(name: &str) -> bool {
+    HOP_BY_HOP.iter().any(|h| name.eq_ignore_ascii_case(h))
+}
+
+/// Why a proxied body or exchange was cut off; each maps to one error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamLimitError {
+    RequestBodyTooLarge,
+    ResponseBodyTooLarge,
+    UpstreamConnectTimeout,
+    UpstreamTotalTimeout,
+    UpstreamIdleTimeout,
+}
+
+impl StreamLimitError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            StreamLimitError::RequestBodyTooLarge => "request_body_too_large",
+            StreamLimitError::ResponseBodyTooLarge => "response_body_too_large",
+            StreamLimitError::UpstreamConnectTimeout => "upstream_connect_timeout",
+            StreamLimitError::UpstreamTotalTimeout => "upstream_total_timeout",
+            StreamLimitError::UpstreamIdleTimeout => "upstream_stream_idle_timeout",
+        }
+    }
+}
+
+impl StreamLimitError {
+    pub fn status(&self) -> http::StatusCode {
+        match self {
+            StreamLimitError::RequestBodyTooLarge => http::StatusCode::PAYLOAD_TOO_LARGE,
+            StreamLimitError::ResponseBodyTooLarge => http::StatusCode::BAD_GATEWAY,
+            StreamLimitError::UpstreamConnectTimeout
+            | StreamLimitError::UpstreamTotalTimeout
+            | StreamLimitError::UpstreamIdleTimeout => http::StatusCode::GATEWAY_TIMEOUT,
+        }
+    }
+}
+
+impl std::fmt::Display for StreamLimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl std::error::Error for StreamLimitError {}
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Called at most once, when a response body is cut off after headers were sent.
+pub type AbortHook = Box<dyn FnOnce(StreamLimitError) + Send>;
+
+/// Streams `inner` through unchanged while enforcing a byte cap, an optional
+/// absolute deadline and an optional idle gap between chunks.
+pub struct GuardedBody {
+    inner: Body,
+    limit: u64,
+    seen: u64,
+    overflow: StreamLimitError,
+    deadline: Option<Pin<Box<Sleep>>>,
+    idle: Option<(Duration, Pin<Box<Sleep>>)>,
+    on_abort: Option<AbortHook>,
+    done: bool,
+}
+
+impl GuardedBody {
+    pub fn new(inner: Body, limit: u64, overflow: StreamLimitError) -> Self {
+        Self {
+            inner,
+            limit,
+            seen: 0,
+            overflow,
+            deadline: None,
+            idle: None,
+            on_abort: None,
+            done: false,
+        }
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(Box::pin(tokio::time::sleep_until(deadline)));
+        self
+    }
+
+    pub fn with_idle_timeout(mut self, idle: Duration) -> Self {
+        self.idle = Some((idle, Box::pin(tokio::time::sleep(idle))));
+        self
+    }
+
+    pub fn on_abort(mut self, hook: AbortHook) -> Self {
+        self.on_abort = Some(hook);
+        self
+    }
+
+    pub fn into_body(self) -> Body {
+        Body::wrap_stream(self)
+    }
+
+    fn abort(&mut self, err: StreamLimitError) -> Poll<Option<Result<Bytes, BoxError>>> {
+        self.done = true;
+        if let Some(hook) = self.on_abort.take() {
+            hook(err);
+        }
+        Poll::Ready(Some(Err(Box::new(err))))
+    }
+}
+
+impl Stream for GuardedBody {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        if let Some(deadline) = this.deadline.as_mut() {
+            if deadline.as_mut().poll(cx).is_ready() {
+                return this.abort(StreamLimitError::UpstreamTotalTimeout);
+            }
+        }
+        if let Some((_, idle)) = this.idle.as_mut() {
+            if idle.as_mut().poll(cx).is_ready() {
+                return this.abort(StreamLimitError::UpstreamIdleTimeout);
+            }
+        }
+
+        match Pin::new(&mut this.inner).poll_next(cx) {
+            Poll::Ready(Some(Ok(chunk))) => {
+                this.seen += chunk.len() as u64;
+                if this.seen > this.limit {
+                    return this.abort(this.overflow);
+                }
+                if let Some((gap, idle)) = this.idle.as_mut() {
+                    idle.as_mut().reset(Instant::now() + *gap);
+                }
+                Poll::Ready(Some(Ok(chunk)))
+            }
+            Poll::Ready(Some(Err(e))) => {
+                // Propagate so the other side sees a broken body, not a clean end.
+                this.done = true;
+                Poll::Ready(Some(Err(Box::new(e))))
+            }
+            Poll::Ready(None) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// `text/event-stream` responses are long-lived: they get the idle gap
+/// instead of the total deadline.
+pub fn is_event_stream(headers: &http::HeaderMap) -> bool {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|ct| ct.trim_start().starts_with("text/event-stream"))
+        .unwrap_or(false)
+}
+
+/// Declared `content-length`, if present and well-formed.
+pub fn content_length(headers: &http::HeaderMap) -> Option<u64> {
+    headers
+        .get(http::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn over_limit_body_is_cut_and_reported_once() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+
+        let (mut tx, body) = Body::channel();
+        tokio::spawn(async move {
+            for _ in 0..4 {
+                if tx.send_data(Bytes::from_static(b"0123456789")).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let guarded = GuardedBody::new(body, 25, StreamLimitError::ResponseBodyTooLarge)
+            .on_abort(Box::new(move |e| sink.lock().unwrap().push(e)))
+            .into_body();
+        let err = hyper::body::to_bytes(guarded).await.unwrap_err();
+
+        assert_eq!(
+            crate::errors::find_cause::<StreamLimitError>(&err),
+            Some(&StreamLimitError::ResponseBodyTooLarge)
+        );
+        assert_eq!(*reported.lock().unwrap(), vec![StreamLimitError::ResponseBodyTooLarge]);
+    }
+
+    #[tokio::test]
+    async fn stalled_stream_hits_idle_timeout() {
+        let (_tx, body) = Body::channel();
+        let mut guarded = GuardedBody::new(body, 1024, StreamLimitError::ResponseBodyTooLarge)
+            .with_idle_timeout(Duration::from_millis(20));
+
+        let err = std::future::poll_fn(|cx| Pin::new(&mut guarded).poll_next(cx))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<StreamLimitError>(),
+            Some(&StreamLimitError::UpstreamIdleTimeout)
+        );
+    }
+
+    #[test]
+    fn hop_by_hop_and_sse_detection() {
+        assert!(is_hop_by_hop("Transfer-Encoding"));
+        assert!(!is_hop_by_hop("content-type"));
+
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("text/event-stream; charset=utf-8"),
+        );
+        assert!(is_event_stream(&headers));
+    }
+}
@@ -1,76 +1,467 @@
 #![forbid(unsafe_code)]
 
+mod admin;
+mod audit;
 mod config;
+mod errors;
+mod limits;
+mod probe;
+mod runtime;
 mod session;
+mod store;
+mod tls;
 
+use crate::audit::{make_trace_id, AccessLog, AccessRecord};
 use crate::config::ProxyConfig;
-use crate::session::{SecureChannelProfile, SessionGuard, SessionGuardError, SessionToken};
-use http::{Request, Response, StatusCode};
-use hyper::body::to_bytes;
-use hyper::client::HttpConnector;
-use hyper::service::{make_service_fn, service_fn};
-use hyper::{Body, Client, Server};
+use crate::limits::{content_length, is_event_stream, is_hop_by_hop, AbortHook, GuardedBody, StreamLimitError};
+use crate::probe::{ChannelProbe, InboundConnection, InboundTls};
+use crate::runtime::{Runtime, SharedRuntime};
+use crate::session::{SessionGuard, SessionToken};
+use crate::store::{RevokeTarget, SessionRejection, SessionStore};
+use http::{HeaderMap, Request, Response, StatusCode};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::Body;
 use std::convert::Infallible;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use time::OffsetDateTime;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
 
-type HttpClient = Client<HttpConnector>;
+const DEFAULT_CONFIG_PATH: &str = "sessionguard-proxy.toml";
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Rejection produced by `process_request`; rendered by `error_response`.
+#[derive(Debug, Clone, Copy)]
+struct ProxyError {
+    status: StatusCode,
+    code: &'static str,
+}
+
+impl ProxyError {
+    fn new(status: StatusCode, code: &'static str) -> Self {
+        Self { status, code }
+    }
+}
+
+impl From<StreamLimitError> for ProxyError {
+    fn from(e: StreamLimitError) -> Self {
+        Self::new(e.status(), e.code())
+    }
+}
+
+/// Long-lived handles shared by every connection.
+#[derive(Clone)]
+struct ProxyState {
+    runtime: Arc<SharedRuntime>,
+    access_log: Arc<AccessLog>,
+    sessions: Arc<SessionStore>,
+}
+
+enum Command {
+    Serve { config: PathBuf },
+    CheckConfig { config: PathBuf },
+    VerifyAccessLog { path: String },
+    SpkiPin { cert_path: String },
+    ListSessions { config: PathBuf, host_did: Option<String> },
+    Revoke { config: PathBuf, target: RevokeTarget },
+}
+
+fn parse_args(args: &[String]) -> anyhow::Result<Command> {
+    let mut config = PathBuf::from(DEFAULT_CONFIG_PATH);
+    let mut rest = Vec::new();
+    let mut it = args.iter().skip(1);
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--config" | "-c" => {
+                let path = it.next().ok_or_else(|| anyhow::anyhow!("--config needs a path"))?;
+                config = PathBuf::from(path);
+            }
+            _ => match arg.strip_prefix("--config=") {
+                Some(path) => config = PathBuf::from(path),
+                None => rest.push(arg.as_str()),
+            },
+        }
+    }
+
+    match rest.as_slice() {
+        [] => Ok(Command::Serve { config }),
+        ["check-config"] => Ok(Command::CheckConfig { config }),
+        ["verify-access-log", path] => Ok(Command::VerifyAccessLog { path: path.to_string() }),
+        ["spki-pin", cert] => Ok(Command::SpkiPin { cert_path: cert.to_string() }),
+        ["sessions"] => Ok(Command::ListSessions { config, host_did: None }),
+        ["sessions", did] => Ok(Command::ListSessions {
+            config,
+            host_did: Some(did.to_string()),
+        }),
+        ["revoke", kind, value] => {
+            let target = match *kind {
+                "session" => RevokeTarget::Session(value.to_string()),
+                "device" => RevokeTarget::Device(value.to_string()),
+                "did" => RevokeTarget::HostDid(value.to_string()),
+                other => anyhow::bail!("revoke: unknown target {:?} (session | device | did)", other),
+            };
+            Ok(Command::Revoke { config, target })
+        }
+        _ => anyhow::bail!(
+            "usage: sessionguard-proxy [--config <path>] [check-config | verify-access-log <log> | spki-pin <cert.pem> | sessions [<did>] | revoke session|device|did <value>]"
+        ),
+    }
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let cfg = ProxyConfig::load_from_file("sessionguard-proxy.toml")?;
+    let args: Vec<String> = std::env::args().collect();
+    let config_path = match parse_args(&args)? {
+        Command::Serve { config } => config,
+        Command::CheckConfig { config } => {
+            let loaded = ProxyConfig::load_from_file(&config)?;
+            println!("{}: ok (sha256 {})", config.display(), loaded.digest);
+            return Ok(());
+        }
+        Command::VerifyAccessLog { path } => {
+            let raw = std::fs::read_to_string(&path)?;
+            let count = audit::verify_chain(&raw)?;
+            println!("{}: {} entries, chain intact", path, count);
+            return Ok(());
+        }
+        Command::SpkiPin { cert_path } => {
+            for cert in tls::load_certs(&cert_path)? {
+                println!("{}", tls::spki_sha256(&cert.0)?);
+            }
+            return Ok(());
+        }
+        Command::ListSessions { config, host_did } => {
+            let cfg = ProxyConfig::load_from_file(&config)?.cfg;
+            let path = match host_did {
+                Some(did) => format!("/sessions?host_did={}", did),
+                None => "/sessions".to_string(),
+            };
+            return print_admin_call(&cfg, http::Method::GET, &path, None).await;
+        }
+        Command::Revoke { config, target } => {
+            let cfg = ProxyConfig::load_from_file(&config)?.cfg;
+            let body = serde_json::to_value(&target)?;
+            return print_admin_call(&cfg, http::Method::POST, "/revoke", Some(body)).await;
+        }
+    };
 
-    let client = Client::new();
-    let listen: SocketAddr = cfg.listen_addr.parse().expect("invalid listen_addr");
+    let loaded = ProxyConfig::load_from_file(&config_path)?;
+    let cfg = loaded.cfg.clone();
 
-    let shared_cfg = cfg.clone();
-    let make_svc = make_service_fn(move |_conn| {
-        let client = client.clone();
-        let cfg = shared_cfg.clone();
-        async move {
-            Ok::<_, Infallible>(service_fn(move |req| {
-                handle_request(req, client.clone(), cfg.clone())
-            }))
-        }
-    });
+    // Validated above, so the address parses.
+    let listen: SocketAddr = cfg.listen_addr.parse()?;
+    let acceptor = match &cfg.tls {
+        Some(t) => Some(TlsAcceptor::from(tls::server_config(t)?)),
+        None => None,
+    };
+
+    let state = ProxyState {
+        runtime: Arc::new(SharedRuntime::new(Runtime::build(loaded))),
+        access_log: Arc::new(AccessLog::open(&cfg.access_log_path)?),
+        sessions: Arc::new(SessionStore::open(Some(cfg.sessions.store_path.clone().into()))?),
+    };
+
+    tokio::spawn(runtime::watch(
+        config_path.clone(),
+        state.runtime.clone(),
+        state.access_log.clone(),
+        CONFIG_POLL_INTERVAL,
+        now_utc,
+    ));
+
+    let admin_listener = TcpListener::bind(&cfg.admin.listen_addr).await?;
+    tokio::spawn(serve_admin(
+        admin_listener,
+        admin::AdminState {
+            sessions: state.sessions.clone(),
+            access_log: state.access_log.clone(),
+            token_sha256: cfg.admin.token_sha256.clone(),
+        },
+    ));
+
+    let listener = TcpListener::bind(listen).await?;
+    println!(
+        "SessionGuard proxy listening on {} ({}, config: {}, access log: {}, admin: {})",
+        listen,
+        if acceptor.is_some() { "tls" } else { "plaintext" },
+        config_path.display(),
+        cfg.access_log_path,
+        cfg.admin.listen_addr
+    );
+
+    loop {
+        let (tcp, remote_addr) = listener.accept().await?;
+        let state = state.clone();
+        let acceptor = acceptor.clone();
+
+        tokio::spawn(async move {
+            let Some(acceptor) = acceptor else {
+                let inbound = InboundConnection { remote_addr, tls: None };
+                return serve_connection(tcp, inbound, state).await;
+            };
 
-    println!("SessionGuard proxy listening on {}", listen);
-    Server::bind(&listen).serve(make_svc).await?;
+            match acceptor.accept(tcp).await {
+                Ok(stream) => {
+                    let client_cert_sha256 = stream
+                        .get_ref()
+                        .1
+                        .peer_certificates()
+                        .and_then(|certs| certs.first())
+                        .map(|cert| tls::cert_sha256(&cert.0));
+                    let inbound = InboundConnection {
+                        remote_addr,
+                        tls: Some(InboundTls { client_cert_sha256 }),
+                    };
+                    serve_connection(stream, inbound, state).await;
+                }
+                Err(e) => eprintln!("sessionguard: tls handshake from {} failed: {}", remote_addr, e),
+            }
+        });
+    }
+}
 
+async fn serve_connection<S>(io: S, inbound: InboundConnection, state: ProxyState)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let svc = service_fn(move |req| handle_request(req, inbound.clone(), state.clone()));
+    if let Err(e) = Http::new().serve_connection(io, svc).await {
+        eprintln!("sessionguard: connection error: {}", e);
+    }
+}
+
+async fn serve_admin(listener: TcpListener, state: admin::AdminState) {
+    loop {
+        let (tcp, remote_addr) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("sessionguard: admin accept failed: {}", e);
+                continue;
+            }
+        };
+        let state = state.clone();
+        tokio::spawn(async move {
+            let svc = service_fn(move |req| {
+                let state = state.clone();
+                async move { Ok::<_, Infallible>(admin::handle(req, remote_addr, state, &now_utc()).await) }
+            });
+            if let Err(e) = Http::new().serve_connection(tcp, svc).await {
+                eprintln!("sessionguard: admin connection error: {}", e);
+            }
+        });
+    }
+}
+
+async fn print_admin_call(
+    cfg: &ProxyConfig,
+    method: http::Method,
+    path: &str,
+    body: Option<serde_json::Value>,
+) -> anyhow::Result<()> {
+    let (status, body) = admin::call(&cfg.admin.listen_addr, method, path, body).await?;
+    println!("{}", body);
+    anyhow::ensure!(status.is_success(), "admin endpoint answered {}", status);
     Ok(())
 }
 
 async fn handle_request(
     req: Request<Body>,
-    client: HttpClient,
-    cfg: ProxyConfig,
+    inbound: InboundConnection,
+    state: ProxyState,
 ) -> Result<Response<Body>, Infallible> {
-    match process_request(req, client, cfg).await {
-        Ok(resp) => Ok(resp),
-        Err(resp) => Ok(resp),
+    let started = Instant::now();
+    let now = now_utc();
+
+    let mut record = AccessRecord {
+        remote_addr: Some(inbound.remote_addr.to_string()),
+        method: req.method().to_string(),
+        route: req.uri().path().to_string(),
+        ..AccessRecord::default()
+    };
+
+    // One config generation for the whole request, even across a reload.
+    let rt = state.runtime.snapshot();
+    let resp = match process_request(req, &inbound, &rt, &state, &now, started, &mut record).await {
+        Ok(resp) => resp,
+        Err(err) => {
+            record.reason = Some(err.code);
+            error_response(err.status, err.code)
+        }
+    };
+
+    if record.trace_id.is_empty() {
+        record.trace_id = make_trace_id("anonymous", &record.route, &now);
+    }
+    let latency_ms = started.elapsed().as_millis() as u64;
+    if let Err(e) = state.access_log.append(&record, &now, latency_ms) {
+        eprintln!("sessionguard: failed to append access log entry: {}", e);
     }
+
+    Ok(resp)
 }
 
 async fn process_request(
     req: Request<Body>,
-    client: HttpClient,
-    cfg: ProxyConfig,
-) -> Result<Response<Body>, Response<Body>> {
+    inbound: &InboundConnection,
+    rt: &Runtime,
+    state: &ProxyState,
+    now: &str,
+    started: Instant,
+    record: &mut AccessRecord,
+) -> Result<Response<Body>, ProxyError> {
+    let cfg = &rt.cfg;
+    let limits = &cfg.limits;
+    let guard = admit(req.headers(), inbound, rt.probe.as_ref(), cfg, now, record)?;
+    // Checked on every request, so admin revocations apply immediately.
+    if let Err(rejection) = state.sessions.admit(guard.token(), now, &cfg.sessions) {
+        let status = match rejection {
+            SessionRejection::DeviceSessionLimit => StatusCode::TOO_MANY_REQUESTS,
+            _ => StatusCode::FORBIDDEN,
+        };
+        return Err(ProxyError::new(status, rejection.code()));
+    }
+    record.guard_passed = true;
+
+    // 5. Forward request to backend (strip original host, rewrite URI).
+    let backend_uri = format!(
+        "{}{}",
+        cfg.backend_base_url,
+        req.uri().path_and_query().map(|pq| pq.as_str()).unwrap_or("/")
+    );
+
+    let (parts, body) = req.into_parts();
+    if content_length(&parts.headers).is_some_and(|n| n > limits.max_request_body_bytes) {
+        return Err(StreamLimitError::RequestBodyTooLarge.into());
+    }
+
+    // The upstream hop negotiates its own HTTP version and framing.
+    let mut new_req = Request::builder().method(parts.method).uri(backend_uri);
+
+    // Copy end-to-end headers except Host and any client-supplied trace id.
+    for (k, v) in parts.headers.iter() {
+        let name = k.as_str();
+        if name.eq_ignore_ascii_case("host")
+            || name.eq_ignore_ascii_case("x-trace-id")
+            || is_hop_by_hop(name)
+        {
+            continue;
+        }
+        new_req = new_req.header(k, v);
+    }
+    new_req = new_req.header("x-trace-id", record.trace_id.as_str());
+
+    let body = GuardedBody::new(
+        body,
+        limits.max_request_body_bytes,
+        StreamLimitError::RequestBodyTooLarge,
+    )
+    .into_body();
+    let new_req = match new_req.body(body) {
+        Ok(r) => r,
+        Err(_) => return Err(ProxyError::new(StatusCode::INTERNAL_SERVER_ERROR, "build_error")),
+    };
+
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(limits.total_timeout_ms);
+    let resp = match tokio::time::timeout_at(deadline, rt.client.request(new_req)).await {
+        Ok(Ok(resp)) => resp,
+        Ok(Err(e)) => return Err(classify_upstream_error(&e)),
+        Err(_) => return Err(StreamLimitError::UpstreamTotalTimeout.into()),
+    };
+
+    // Optionally, strip or sanitize Set-Cookie headers here.
+    record.backend_status = Some(resp.status().as_u16());
+    if content_length(resp.headers()).is_some_and(|n| n > limits.max_response_body_bytes) {
+        return Err(StreamLimitError::ResponseBodyTooLarge.into());
+    }
+
+    let (mut parts, body) = resp.into_parts();
+    let hop_by_hop: Vec<_> = parts
+        .headers
+        .keys()
+        .filter(|k| is_hop_by_hop(k.as_str()))
+        .cloned()
+        .collect();
+    for k in hop_by_hop {
+        parts.headers.remove(k);
+    }
+
+    // Headers are already on their way once the body streams, so a cut-off
+    // is recorded as a follow-up access log entry instead of an error body.
+    let guarded = GuardedBody::new(
+        body,
+        limits.max_response_body_bytes,
+        StreamLimitError::ResponseBodyTooLarge,
+    );
+    let guarded = if is_event_stream(&parts.headers) {
+        guarded.with_idle_timeout(Duration::from_millis(limits.stream_idle_timeout_ms))
+    } else {
+        guarded.with_deadline(deadline)
+    };
+    let guarded = guarded.on_abort(abort_hook(state, record, started));
+
+    Ok(Response::from_parts(parts, guarded.into_body()))
+}
+
+/// Map a failed upstream exchange onto a distinct error code.
+fn classify_upstream_error(err: &hyper::Error) -> ProxyError {
+    if tls::is_pin_mismatch(err) {
+        return ProxyError::new(StatusCode::BAD_GATEWAY, "backend_spki_pin_mismatch");
+    }
+    if let Some(limit) = errors::find_cause::<StreamLimitError>(err) {
+        return (*limit).into();
+    }
+    let timed_out = errors::cause_chain(err).into_iter().any(|e| {
+        e.downcast_ref::<std::io::Error>()
+            .is_some_and(|io| io.kind() == std::io::ErrorKind::TimedOut)
+    });
+    if err.is_connect() && timed_out {
+        return StreamLimitError::UpstreamConnectTimeout.into();
+    }
+    ProxyError::new(StatusCode::BAD_GATEWAY, "backend_unreachable")
+}
+
+fn abort_hook(state: &ProxyState, record: &AccessRecord, started: Instant) -> AbortHook {
+    let access_log = state.access_log.clone();
+    let mut aborted = record.clone();
+    Box::new(move |e| {
+        aborted.reason = Some(e.code());
+        let latency_ms = started.elapsed().as_millis() as u64;
+        if let Err(err) = access_log.append(&aborted, &now_utc(), latency_ms) {
+            eprintln!("sessionguard: failed to append access log entry: {}", err);
+        }
+    })
+}
+
+/// Token extraction, channel observation and SessionGuard validation.
+/// Nothing is forwarded unless this returns `Ok`.
+fn admit(
+    headers: &HeaderMap,
+    inbound: &InboundConnection,
+    probe: &dyn ChannelProbe,
+    cfg: &ProxyConfig,
+    now: &str,
+    record: &mut AccessRecord,
+) -> Result<SessionGuard, ProxyError> {
     // 1. Extract token (simple header-based example; you can adjust).
-    let maybe_token_header = req.headers().get("x-session-token");
+    let maybe_token_header = headers.get("x-session-token");
 
     let token_json = match maybe_token_header {
         Some(hv) => match hv.to_str() {
             Ok(s) => s,
             Err(_) => {
-                return Err(error_response(
+                return Err(ProxyError::new(
                     StatusCode::BAD_REQUEST,
                     "invalid_header_encoding",
                 ))
             }
         },
         None => {
-            return Err(error_response(
+            return Err(ProxyError::new(
                 StatusCode::UNAUTHORIZED,
                 "missing_x-session-token_header",
             ))
@@ -80,92 +471,53 @@ async fn process_request(
     let token: SessionToken = match serde_json::from_str(token_json) {
         Ok(t) => t,
         Err(_) => {
-            return Err(error_response(
+            return Err(ProxyError::new(
                 StatusCode::BAD_REQUEST,
                 "invalid_session_token_json",
             ))
         }
     };
 
-    // 2. Build observed secure channel profile from config.
-    let observed_profile = SecureChannelProfile {
-        dns_fail_closed: cfg.dns_fail_closed,
-        doh_pinned: cfg.doh_pinned,
-        tls_pinned: cfg.tls_pinned,
-        browserless: false,
-    };
+    record.trace_id = make_trace_id(&token.host_did, &record.route, now);
+    record.host_did = Some(token.host_did.clone());
+    record.roles = token.roles.clone();
 
-    let now = OffsetDateTime::now_utc()
-        .format(&time::format_description::well_known::Rfc3339)
-        .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string());
+    if cfg.revocation.host_dids.contains(&token.host_did) {
+        return Err(ProxyError::new(StatusCode::FORBIDDEN, "host_did_revoked"));
+    }
+    if cfg.revocation.device_fingerprints.contains(&token.device_fingerprint) {
+        return Err(ProxyError::new(StatusCode::FORBIDDEN, "device_revoked"));
+    }
 
-    // For now, assume BCI is enabled when the proxy runs.
-    let bcienabled = true;
+    // 2. Observe the secure channel and BCI posture for this connection.
+    let observed = probe.observe(inbound, headers);
 
     // 3. Validate SessionGuard.
     let guard = match SessionGuard::new(
         token,
         &cfg.expected_device_fingerprint,
-        &observed_profile,
-        bcienabled,
-        &now,
+        &observed.profile,
+        observed.bcienabled,
+        now,
     ) {
         Ok(g) => g,
-        Err(e) => {
-            let msg = match e {
-                SessionGuardError::InvalidEnv(reason) => reason,
-                SessionGuardError::Expired => "session_expired",
-                SessionGuardError::RohViolation => "roh_violation",
-                SessionGuardError::NeurorightsViolation(reason) => reason,
-            };
-            return Err(error_response(StatusCode::FORBIDDEN, msg));
-        }
+        Err(e) => return Err(ProxyError::new(StatusCode::FORBIDDEN, e.reason_code())),
     };
 
-    // Optional: enforce roles
-    if !guard.has_role(&session::Role::Chat) {
-        return Err(error_response(
-            StatusCode::FORBIDDEN,
-            "missing_chat_role_in_token",
-        ));
-    }
-
-    // 4. Forward request to backend (strip original host, rewrite URI).
-    let backend_uri = format!(
-        "{}{}",
-        cfg.backend_base_url,
-        req.uri().path_and_query().map(|pq| pq.as_str()).unwrap_or("/")
-    );
-
-    let (parts, body) = req.into_parts();
-    let mut new_req = Request::builder()
-        .method(parts.method)
-        .uri(backend_uri)
-        .version(parts.version);
-
-    // Copy headers except the original Host; you can filter more later.
-    for (k, v) in parts.headers.iter() {
-        if k.as_str().eq_ignore_ascii_case("host") {
-            continue;
-        }
-        new_req = new_req.header(k, v);
-    }
-
-    let new_req = match new_req.body(body) {
-        Ok(r) => r,
-        Err(_) => return Err(error_response(StatusCode::INTERNAL_SERVER_ERROR, "build_error")),
+    // 4. Enforce the route's role policy.
+    let required = match cfg.required_roles(&record.route) {
+        Some(roles) => roles,
+        None => return Err(ProxyError::new(StatusCode::FORBIDDEN, "route_not_allowed")),
     };
-
-    match client.request(new_req).await {
-        Ok(resp) => {
-            // Optionally, strip or sanitize Set-Cookie headers here.
-            Ok(resp)
-        }
-        Err(_) => Err(error_response(
-            StatusCode::BAD_GATEWAY,
-            "backend_unreachable",
-        )),
+    if let Some(missing) = required.iter().find(|role| !guard.has_role(role)) {
+        let code = match missing {
+            session::Role::Chat => "missing_chat_role_in_token",
+            _ => "missing_route_role_in_token",
+        };
+        return Err(ProxyError::new(StatusCode::FORBIDDEN, code));
     }
+
+    Ok(guard)
 }
 
 fn error_response(status: StatusCode, code: &str) -> Response<Body> {
@@ -181,3 +533,157 @@ fn error_response(status: StatusCode, code: &str) -> Response<Body> {
         .body(Body::from(body_str))
         .unwrap()
 }
+
+fn now_utc() -> String {
+    OffsetDateTime::now_utc()
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_else(|_| "1970-01-01T00:00:00Z".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::probe::ChannelObservation;
+    use crate::session::{NeurorightsFlags, Role, SecureChannelProfile};
+    use http::HeaderValue;
+
+    struct FakeProbe(ChannelObservation);
+
+    impl ChannelProbe for FakeProbe {
+        fn observe(&self, _conn: &InboundConnection, _headers: &HeaderMap) -> ChannelObservation {
+            self.0.clone()
+        }
+    }
+
+    fn hardened() -> SecureChannelProfile {
+        SecureChannelProfile {
+            dns_fail_closed: true,
+            doh_pinned: true,
+            tls_pinned: true,
+            browserless: true,
+        }
+    }
+
+    fn cfg() -> ProxyConfig {
+        ProxyConfig {
+            listen_addr: "127.0.0.1:0".into(),
+            backend_base_url: "http://127.0.0.1:9".into(),
+            expected_device_fingerprint: "fp-1".into(),
+            bci_attestation_path: None,
+            tls: None,
+            upstream: Default::default(),
+            limits: Default::default(),
+            routes: Vec::new(),
+            revocation: Default::default(),
+            sessions: Default::default(),
+            admin: Default::default(),
+            access_log_path: "unused.jsonl".into(),
+        }
+    }
+
+    fn headers_with_token() -> HeaderMap {
+        let token = SessionToken {
+            host_did: "did:example:host".into(),
+            bostrom_addr_primary: "bostrom1example".into(),
+            roles: vec![Role::Chat],
+            roh_leq_03: true,
+            expiry_utc: "2999-01-01T00:00:00Z".into(),
+            issued_utc: None,
+            device_fingerprint: "fp-1".into(),
+            secure_channel: hardened(),
+            neurorights: NeurorightsFlags {
+                cognitive_liberty: true,
+                mental_privacy: true,
+                mental_integrity: true,
+                augmentation_continuity: true,
+            },
+            hex_stamp: "0x00".into(),
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-session-token",
+            HeaderValue::from_str(&serde_json::to_string(&token).unwrap()).unwrap(),
+        );
+        headers
+    }
+
+    fn admit_with(observed: ChannelObservation) -> Result<SessionGuard, ProxyError> {
+        admit_with_cfg(observed, &cfg())
+    }
+
+    fn admit_with_cfg(observed: ChannelObservation, cfg: &ProxyConfig) -> Result<SessionGuard, ProxyError> {
+        let inbound = InboundConnection {
+            remote_addr: "127.0.0.1:50000".parse().unwrap(),
+            tls: None,
+        };
+        let mut record = AccessRecord::default();
+        admit(
+            &headers_with_token(),
+            &inbound,
+            &FakeProbe(observed),
+            cfg,
+            "2026-01-01T00:00:00Z",
+            &mut record,
+        )
+    }
+
+    #[test]
+    fn admits_when_observed_channel_matches_token() {
+        let guard = admit_with(ChannelObservation {
+            profile: hardened(),
+            client_cert_sha256: Some("ab".repeat(32)),
+            bcienabled: true,
+        });
+        assert!(guard.is_ok());
+    }
+
+    #[test]
+    fn rejects_when_observed_channel_differs_from_token() {
+        let err = admit_with(ChannelObservation {
+            profile: SecureChannelProfile {
+                tls_pinned: false,
+                ..hardened()
+            },
+            client_cert_sha256: None,
+            bcienabled: true,
+        })
+        .unwrap_err();
+        assert_eq!(err.code, "secure_channel_mismatch");
+    }
+
+    #[test]
+    fn rejects_when_bci_posture_is_not_attested() {
+        let err = admit_with(ChannelObservation {
+            profile: hardened(),
+            client_cert_sha256: None,
+            bcienabled: false,
+        })
+        .unwrap_err();
+        assert_eq!(err.code, "bci_disabled");
+    }
+
+    fn attested() -> ChannelObservation {
+        ChannelObservation {
+            profile: hardened(),
+            client_cert_sha256: None,
+            bcienabled: true,
+        }
+    }
+
+    #[test]
+    fn revoked_did_and_unrouted_paths_are_refused() {
+        let mut revoked = cfg();
+        revoked.revocation.host_dids.push("did:example:host".into());
+        let err = admit_with_cfg(attested(), &revoked).unwrap_err();
+        assert_eq!(err.code, "host_did_revoked");
+
+        let mut routed = cfg();
+        routed.routes.push(config::RoutePolicy {
+            prefix: "/governance".into(),
+            required_roles: vec![Role::Governance],
+        });
+        // The test record's route is empty, so no prefix matches.
+        let err = admit_with_cfg(attested(), &routed).unwrap_err();
+        assert_eq!(err.code, "route_not_allowed");
+    }
+}
@@ -0,0 +1,170 @@
+#![forbid(unsafe_code)]
+
+use crate::session::SecureChannelProfile;
+use http::HeaderMap;
+use serde::Deserialize;
+use std::fs;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+/// Facts about the inbound connection, captured when it is accepted.
+#[derive(Debug, Clone)]
+pub struct InboundConnection {
+    pub remote_addr: SocketAddr,
+    /// `None` for plaintext listeners.
+    pub tls: Option<InboundTls>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InboundTls {
+    /// Hex SHA-256 of the client certificate (DER), when mutual TLS was used.
+    pub client_cert_sha256: Option<String>,
+}
+
+/// What the proxy actually observed for one request, compared against the token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelObservation {
+    pub profile: SecureChannelProfile,
+    pub client_cert_sha256: Option<String>,
+    pub bcienabled: bool,
+}
+
+/// Produces the observed channel posture for a request.
+///
+/// The live implementation inspects the connection and headers; tests plug
+/// in a fixed observation instead.
+pub trait ChannelProbe: Send + Sync {
+    fn observe(&self, conn: &InboundConnection, headers: &HeaderMap) -> ChannelObservation;
+}
+
+/// Host-local posture that cannot be read off a socket (resolver, BCI).
+///
+/// Written by the host's own tooling, e.g.:
+/// `{"bci_enabled": true, "dns_fail_closed": true, "doh_pinned": true}`
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct PostureAttestation {
+    #[serde(default)]
+    pub bci_enabled: bool,
+    #[serde(default)]
+    pub dns_fail_closed: bool,
+    #[serde(default)]
+    pub doh_pinned: bool,
+}
+
+impl PostureAttestation {
+    /// Missing or unreadable attestation yields the all-false (fail-closed) posture.
+    pub fn load(path: Option<&PathBuf>) -> Self {
+        path.and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Probe backed by the real connection, request headers and attestation file.
+#[derive(Debug, Clone, Default)]
+pub struct LiveChannelProbe {
+    pub attestation_path: Option<PathBuf>,
+}
+
+impl LiveChannelProbe {
+    pub fn new(attestation_path: Option<PathBuf>) -> Self {
+        Self { attestation_path }
+    }
+}
+
+impl ChannelProbe for LiveChannelProbe {
+    fn observe(&self, conn: &InboundConnection, headers: &HeaderMap) -> ChannelObservation {
+        let attestation = PostureAttestation::load(self.attestation_path.as_ref());
+        let client_cert_sha256 = conn.tls.as_ref().and_then(|t| t.client_cert_sha256.clone());
+
+        ChannelObservation {
+            profile: SecureChannelProfile {
+                dns_fail_closed: attestation.dns_fail_closed,
+                doh_pinned: attestation.doh_pinned,
+                tls_pinned: client_cert_sha256.is_some(),
+                browserless: looks_browserless(headers),
+            },
+            client_cert_sha256,
+            bcienabled: attestation.bci_enabled,
+        }
+    }
+}
+
+/// User-agent heuristic: browsers send a `Mozilla/` UA and fetch metadata
+/// (`sec-fetch-*` / `sec-ch-ua`); CLI and daemon clients send neither.
+pub fn looks_browserless(headers: &HeaderMap) -> bool {
+    let ua = headers
+        .get(http::header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+
+    let browser_ua = ua.starts_with("Mozilla/");
+    let fetch_metadata = headers.contains_key("sec-fetch-mode")
+        || headers.contains_key("sec-fetch-site")
+        || headers.contains_key("sec-ch-ua");
+
+    !browser_ua && !fetch_metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn plain_conn() -> InboundConnection {
+        InboundConnection {
+            remote_addr: "127.0.0.1:50000".parse().unwrap(),
+            tls: None,
+        }
+    }
+
+    #[test]
+    fn browser_headers_are_not_browserless() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::USER_AGENT,
+            HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64)"),
+        );
+        assert!(!looks_browserless(&headers));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(http::header::USER_AGENT, HeaderValue::from_static("curl/8.5.0"));
+        assert!(looks_browserless(&headers));
+
+        headers.insert("sec-fetch-mode", HeaderValue::from_static("cors"));
+        assert!(!looks_browserless(&headers));
+    }
+
+    #[test]
+    fn live_probe_fails_closed_without_tls_or_attestation() {
+        let probe = LiveChannelProbe::new(None);
+        let obs = probe.observe(&plain_conn(), &HeaderMap::new());
+        assert!(!obs.profile.tls_pinned);
+        assert!(!obs.profile.dns_fail_closed);
+        assert!(!obs.profile.doh_pinned);
+        assert!(!obs.bcienabled);
+    }
+
+    #[test]
+    fn live_probe_reads_attestation_and_client_cert() {
+        let path = std::env::temp_dir().join(format!("sg-attest-{}.json", std::process::id()));
+        fs::write(
+            &path,
+            r#"{"bci_enabled": true, "dns_fail_closed": true, "doh_pinned": true}"#,
+        )
+        .unwrap();
+
+        let conn = InboundConnection {
+            tls: Some(InboundTls {
+                client_cert_sha256: Some("ab".repeat(32)),
+            }),
+            ..plain_conn()
+        };
+        let obs = LiveChannelProbe::new(Some(path.clone())).observe(&conn, &HeaderMap::new());
+        assert!(obs.bcienabled);
+        assert!(obs.profile.tls_pinned && obs.profile.dns_fail_closed && obs.profile.doh_pinned);
+        assert!(obs.profile.browserless);
+
+        let _ = fs::remove_file(&path);
+    }
+}
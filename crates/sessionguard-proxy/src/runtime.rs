@@ -0,0 +1,207 @@
+#![forbid(unsafe_code)]
+
+use crate::audit::{AccessLog, AuditEvent, ConfigReloadEvent};
+use crate::config::{LoadedConfig, ProxyConfig};
+use crate::probe::{ChannelProbe, LiveChannelProbe};
+use crate::tls;
+use hyper::client::HttpConnector;
+use hyper::Client;
+use hyper_rustls::HttpsConnector;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+pub type HttpClient = Client<HttpsConnector<HttpConnector>>;
+
+/// Everything derived from one config generation.
+///
+/// Requests take an `Arc<Runtime>` snapshot when they start, so a reload
+/// never changes the rules under an in-flight request or drops a connection.
+pub struct Runtime {
+    pub cfg: ProxyConfig,
+    pub digest: String,
+    pub client: HttpClient,
+    pub probe: Arc<dyn ChannelProbe>,
+}
+
+impl Runtime {
+    pub fn build(loaded: LoadedConfig) -> Self {
+        let cfg = loaded.cfg;
+
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        http.set_connect_timeout(Some(Duration::from_millis(cfg.limits.connect_timeout_ms)));
+        let connector = hyper_rustls::HttpsConnectorBuilder::new()
+            .with_tls_config(tls::pinned_client_config(&cfg.upstream))
+            .https_or_http()
+            .enable_http1()
+            .wrap_connector(http);
+
+        Self {
+            client: Client::builder().build(connector),
+            probe: Arc::new(LiveChannelProbe::new(
+                cfg.bci_attestation_path.clone().map(Into::into),
+            )),
+            digest: loaded.digest,
+            cfg,
+        }
+    }
+}
+
+/// The current `Runtime`, swappable by the reload task.
+pub struct SharedRuntime {
+    current: RwLock<Arc<Runtime>>,
+}
+
+impl SharedRuntime {
+    pub fn new(runtime: Runtime) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(runtime)),
+        }
+    }
+
+    pub fn snapshot(&self) -> Arc<Runtime> {
+        self.current.read().unwrap_or_else(|p| p.into_inner()).clone()
+    }
+
+    fn replace(&self, runtime: Runtime) {
+        *self.current.write().unwrap_or_else(|p| p.into_inner()) = Arc::new(runtime);
+    }
+}
+
+/// Re-read `path`; apply it if valid, keep the old config otherwise.
+/// Every attempt with changed bytes is recorded in the audit log.
+pub fn reload(path: &Path, shared: &SharedRuntime, audit: &AccessLog, now_utc: &str) -> bool {
+    let current = shared.snapshot();
+    let config_path = path.display().to_string();
+
+    let event = match ProxyConfig::load_from_file(path) {
+        Ok(loaded) if loaded.digest == current.digest => return false,
+        Ok(loaded) => {
+            let event = ConfigReloadEvent {
+                config_path,
+                old_digest: current.digest.clone(),
+                new_digest: loaded.digest.clone(),
+                applied: true,
+                errors: Vec::new(),
+                restart_required: current.cfg.restart_required(&loaded.cfg),
+            };
+            shared.replace(Runtime::build(loaded));
+            event
+        }
+        Err(errors) => ConfigReloadEvent {
+            config_path,
+            old_digest: current.digest.clone(),
+            new_digest: file_digest(path).unwrap_or_default(),
+            applied: false,
+            errors: errors.0,
+            restart_required: Vec::new(),
+        },
+    };
+
+    let applied = event.applied;
+    if !applied {
+        eprintln!("sessionguard: config reload rejected: {:?}", event.errors);
+    } else if !event.restart_required.is_empty() {
+        eprintln!(
+            "sessionguard: config reloaded; restart needed for {:?}",
+            event.restart_required
+        );
+    }
+    if let Err(e) = audit.append_event(AuditEvent::ConfigReload(event), now_utc) {
+        eprintln!("sessionguard: failed to append audit entry: {}", e);
+    }
+    applied
+}
+
+fn file_digest(path: &Path) -> Option<String> {
+    use sha2::{Digest, Sha256};
+    std::fs::read(path).ok().map(|raw| hex::encode(Sha256::digest(raw)))
+}
+
+/// Reload on SIGHUP (unix) and whenever the file's mtime changes.
+pub async fn watch(
+    path: PathBuf,
+    shared: Arc<SharedRuntime>,
+    audit: Arc<AccessLog>,
+    poll_every: Duration,
+    now_utc: fn() -> String,
+) {
+    let mtime = |p: &Path| -> Option<SystemTime> { std::fs::metadata(p).and_then(|m| m.modified()).ok() };
+    let mut last_mtime = mtime(&path);
+    let mut ticker = tokio::time::interval(poll_every);
+
+    #[cfg(unix)]
+    let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()).ok();
+
+    loop {
+        #[cfg(unix)]
+        let forced = match hangup.as_mut() {
+            Some(sig) => tokio::select! {
+                _ = ticker.tick() => false,
+                _ = sig.recv() => true,
+            },
+            None => {
+                ticker.tick().await;
+                false
+            }
+        };
+        #[cfg(not(unix))]
+        let forced = {
+            ticker.tick().await;
+            false
+        };
+
+        let seen = mtime(&path);
+        if forced || seen != last_mtime {
+            last_mtime = seen;
+            reload(&path, &shared, &audit, &now_utc());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::verify_chain;
+
+    const BASE: &str = r#"
+listen_addr = "127.0.0.1:8080"
+backend_base_url = "http://127.0.0.1:9000"
+expected_device_fingerprint = "fp-1"
+"#;
+
+    #[test]
+    fn reload_applies_valid_config_and_keeps_old_on_error() {
+        let dir = std::env::temp_dir().join(format!("sg-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cfg_path = dir.join("proxy.toml");
+        let log_path = dir.join("audit.jsonl");
+        let _ = std::fs::remove_file(&log_path);
+
+        std::fs::write(&cfg_path, BASE).unwrap();
+        let shared = SharedRuntime::new(Runtime::build(ProxyConfig::load_from_file(&cfg_path).unwrap()));
+        let audit = AccessLog::open(&log_path).unwrap();
+        let first_digest = shared.snapshot().digest.clone();
+
+        // Unchanged bytes: nothing to do, nothing logged.
+        assert!(!reload(&cfg_path, &shared, &audit, "t0"));
+
+        std::fs::write(&cfg_path, format!("{}\n[revocation]\nhost_dids = [\"did:x\"]\n", BASE)).unwrap();
+        assert!(reload(&cfg_path, &shared, &audit, "t1"));
+        let held = shared.snapshot();
+        assert_eq!(held.cfg.revocation.host_dids, vec!["did:x".to_string()]);
+        assert_ne!(held.digest, first_digest);
+
+        std::fs::write(&cfg_path, BASE.replace("127.0.0.1:8080", "nope")).unwrap();
+        assert!(!reload(&cfg_path, &shared, &audit, "t2"));
+        assert_eq!(shared.snapshot().digest, held.digest);
+
+        let raw = std::fs::read_to_string(&log_path).unwrap();
+        assert_eq!(verify_chain(&raw).unwrap(), 2);
+        assert!(raw.contains(&first_digest));
+        assert!(raw.contains(r#""applied":false"#));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
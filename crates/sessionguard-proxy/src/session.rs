@@ -33,6 +33,10 @@ pub struct SessionToken {
     pub roles: Vec<Role>,
     pub roh_leq_03: bool,
     pub expiry_utc: String,
+    /// RFC 3339 issue time. Needed to pass a DID revocation: only tokens
+    /// issued after the revocation are admitted again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issued_utc: Option<String>,
     pub device_fingerprint: String,
     pub secure_channel: SecureChannelProfile,
     pub neurorights: NeurorightsFlags,
@@ -47,6 +51,18 @@ pub enum SessionGuardError {
     NeurorightsViolation(&'static str),
 }
 
+impl SessionGuardError {
+    /// Stable reason code used in error bodies and the access log.
+    pub fn reason_code(&self) -> &'static str {
+        match self {
+            SessionGuardError::InvalidEnv(reason) => reason,
+            SessionGuardError::Expired => "session_expired",
+            SessionGuardError::RohViolation => "roh_violation",
+            SessionGuardError::NeurorightsViolation(reason) => reason,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SessionGuard {
     token: SessionToken,
@@ -101,7 +117,7 @@ impl SessionGuard {
         }
 
         // 4. Expiry check (simple string compare placeholder; replace with real time parsing).
-        if token.expiry_utc <= now_utc {
+        if token.expiry_utc.as_str() <= now_utc {
             return Err(SessionGuardError::Expired);
         }
 
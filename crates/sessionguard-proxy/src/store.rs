@@ -0,0 +1,359 @@
+#![forbid(unsafe_code)]
+
+use crate::config::SessionLimits;
+use crate::session::SessionToken;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// One session seen by the proxy; `session_id` is the SHA-256 of the token.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SessionRecord {
+    pub session_id: String,
+    pub host_did: String,
+    pub device_fingerprint: String,
+    pub first_seen_utc: String,
+    /// Last admitted request; older stores fall back to `first_seen_utc`.
+    #[serde(default)]
+    pub last_seen_utc: String,
+    pub expiry_utc: String,
+}
+
+/// What a host asked to revoke ("revocable at will", asset.chat.stake.v1).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RevokeTarget {
+    /// A single session.
+    Session(String),
+    /// Every current and future session from this device fingerprint.
+    Device(String),
+    /// Every session for this DID, and any token issued before the revocation.
+    HostDid(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionRejection {
+    SessionRevoked,
+    DeviceRevoked,
+    HostDidRevoked,
+    DeviceSessionLimit,
+}
+
+impl SessionRejection {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SessionRejection::SessionRevoked => "session_revoked",
+            SessionRejection::DeviceRevoked => "device_revoked",
+            SessionRejection::HostDidRevoked => "host_did_revoked",
+            SessionRejection::DeviceSessionLimit => "device_session_limit",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct StoreState {
+    sessions: BTreeMap<String, SessionRecord>,
+    revoked_sessions: BTreeSet<String>,
+    revoked_devices: BTreeSet<String>,
+    /// DID -> RFC 3339 time of its revocation.
+    #[serde(default)]
+    revoked_dids: BTreeMap<String, String>,
+}
+
+/// Local store of active sessions and revocations, optionally persisted as JSON.
+///
+/// Every admitted request goes through `admit`, so a revocation takes
+/// effect on the next request that presents the session.
+pub struct SessionStore {
+    path: Option<PathBuf>,
+    state: Mutex<StoreState>,
+}
+
+impl SessionStore {
+    pub fn open(path: Option<PathBuf>) -> std::io::Result<Self> {
+        let state = match &path {
+            Some(p) => match fs::read_to_string(p) {
+                Ok(raw) => serde_json::from_str(&raw)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => StoreState::default(),
+                Err(e) => return Err(e),
+            },
+            None => StoreState::default(),
+        };
+        Ok(Self {
+            path,
+            state: Mutex::new(state),
+        })
+    }
+
+    pub fn session_id(token: &SessionToken) -> String {
+        let canonical = serde_json::to_vec(token).unwrap_or_default();
+        hex::encode(Sha256::digest(canonical))
+    }
+
+    /// Register or refresh the token's session, enforcing revocations and the
+    /// per-device cap (`max_per_device == 0` disables the cap). Sessions idle
+    /// for `idle_timeout_secs` are dropped first, so they free their slot.
+    pub fn admit(
+        &self,
+        token: &SessionToken,
+        now_utc: &str,
+        limits: &SessionLimits,
+    ) -> Result<String, SessionRejection> {
+        let session_id = Self::session_id(token);
+        let mut state = self.lock();
+
+        if state.revoked_devices.contains(&token.device_fingerprint) {
+            return Err(SessionRejection::DeviceRevoked);
+        }
+        if let Some(revoked_at) = state.revoked_dids.get(&token.host_did) {
+            // Fail closed: a token without a parseable issue time predates it.
+            let reissued = match (token.issued_utc.as_deref().and_then(parse_utc), parse_utc(revoked_at)) {
+                (Some(issued), Some(cutoff)) => issued > cutoff,
+                _ => false,
+            };
+            if !reissued {
+                return Err(SessionRejection::HostDidRevoked);
+            }
+        }
+        if state.revoked_sessions.contains(&session_id) {
+            return Err(SessionRejection::SessionRevoked);
+        }
+
+        // Same string-ordering convention as `SessionGuard::new`.
+        state.sessions.retain(|_, s| s.expiry_utc.as_str() > now_utc);
+        let before = state.sessions.len();
+        if let (Some(now), true) = (parse_utc(now_utc), limits.idle_timeout_secs > 0) {
+            let idle = time::Duration::seconds(limits.idle_timeout_secs.min(i64::MAX as u64) as i64);
+            state.sessions.retain(|id, s| {
+                let last = if s.last_seen_utc.is_empty() { &s.first_seen_utc } else { &s.last_seen_utc };
+                *id == session_id
+                    || parse_utc(last).is_none_or(|last| last.checked_add(idle).is_none_or(|end| end > now))
+            });
+        }
+        let evicted = state.sessions.len() != before;
+        if let Some(session) = state.sessions.get_mut(&session_id) {
+            // Not persisted on its own; written out with the next change.
+            session.last_seen_utc = now_utc.to_string();
+            if evicted {
+                self.persist(&state);
+            }
+            return Ok(session_id);
+        }
+
+        let on_device = state
+            .sessions
+            .values()
+            .filter(|s| s.device_fingerprint == token.device_fingerprint)
+            .count();
+        if limits.max_per_device > 0 && on_device >= limits.max_per_device {
+            return Err(SessionRejection::DeviceSessionLimit);
+        }
+
+        state.sessions.insert(
+            session_id.clone(),
+            SessionRecord {
+                session_id: session_id.clone(),
+                host_did: token.host_did.clone(),
+                device_fingerprint: token.device_fingerprint.clone(),
+                first_seen_utc: now_utc.to_string(),
+                last_seen_utc: now_utc.to_string(),
+                expiry_utc: token.expiry_utc.clone(),
+            },
+        );
+        self.persist(&state);
+        Ok(session_id)
+    }
+
+    /// Apply a revocation at `now_utc`; returns the ids of the sessions it ended.
+    pub fn revoke(&self, target: &RevokeTarget, now_utc: &str) -> Vec<String> {
+        let mut state = self.lock();
+        let ended: Vec<String> = match target {
+            RevokeTarget::Session(id) => {
+                state.revoked_sessions.insert(id.clone());
+                state.sessions.remove(id).map(|s| s.session_id).into_iter().collect()
+            }
+            RevokeTarget::Device(fp) => {
+                state.revoked_devices.insert(fp.clone());
+                take_where(&mut state, |s| &s.device_fingerprint == fp)
+            }
+            RevokeTarget::HostDid(did) => {
+                state.revoked_dids.insert(did.clone(), now_utc.to_string());
+                take_where(&mut state, |s| &s.host_did == did)
+            }
+        };
+        for id in &ended {
+            state.revoked_sessions.insert(id.clone());
+        }
+        self.persist(&state);
+        ended
+    }
+
+    /// Active sessions, optionally only those of one DID.
+    pub fn list(&self, host_did: Option<&str>) -> Vec<SessionRecord> {
+        self.lock()
+            .sessions
+            .values()
+            .filter(|s| host_did.is_none_or(|did| s.host_did == did))
+            .cloned()
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StoreState> {
+        self.state.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    fn persist(&self, state: &StoreState) {
+        let Some(path) = &self.path else { return };
+        let written = serde_json::to_vec_pretty(state)
+            .map_err(std::io::Error::other)
+            .and_then(|raw| {
+                let tmp = path.with_extension("tmp");
+                fs::write(&tmp, raw)?;
+                fs::rename(&tmp, path)
+            });
+        if let Err(e) = written {
+            eprintln!("sessionguard: failed to persist session store: {}", e);
+        }
+    }
+}
+
+fn parse_utc(s: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(s, &Rfc3339).ok()
+}
+
+fn take_where(state: &mut StoreState, pred: impl Fn(&SessionRecord) -> bool) -> Vec<String> {
+    let ids: Vec<String> = state
+        .sessions
+        .values()
+        .filter(|s| pred(s))
+        .map(|s| s.session_id.clone())
+        .collect();
+    for id in &ids {
+        state.sessions.remove(id);
+    }
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{NeurorightsFlags, Role, SecureChannelProfile};
+
+    fn token(did: &str, device: &str, stamp: &str) -> SessionToken {
+        SessionToken {
+            host_did: did.into(),
+            bostrom_addr_primary: "bostrom1example".into(),
+            roles: vec![Role::Chat],
+            roh_leq_03: true,
+            expiry_utc: "2999-01-01T00:00:00Z".into(),
+            issued_utc: None,
+            device_fingerprint: device.into(),
+            secure_channel: SecureChannelProfile {
+                dns_fail_closed: true,
+                doh_pinned: true,
+                tls_pinned: true,
+                browserless: true,
+            },
+            neurorights: NeurorightsFlags {
+                cognitive_liberty: true,
+                mental_privacy: true,
+                mental_integrity: true,
+                augmentation_continuity: true,
+            },
+            hex_stamp: stamp.into(),
+        }
+    }
+
+    const NOW: &str = "2026-01-01T00:00:00Z";
+
+    fn limits(max_per_device: usize) -> SessionLimits {
+        SessionLimits { max_per_device, ..SessionLimits::default() }
+    }
+
+    #[test]
+    fn device_cap_counts_distinct_sessions() {
+        let store = SessionStore::open(None).unwrap();
+        assert!(store.admit(&token("did:a", "fp", "0x1"), NOW, &limits(2)).is_ok());
+        assert!(store.admit(&token("did:a", "fp", "0x1"), NOW, &limits(2)).is_ok());
+        assert!(store.admit(&token("did:a", "fp", "0x2"), NOW, &limits(2)).is_ok());
+        assert_eq!(
+            store.admit(&token("did:a", "fp", "0x3"), NOW, &limits(2)),
+            Err(SessionRejection::DeviceSessionLimit)
+        );
+        assert!(store.admit(&token("did:a", "other-fp", "0x3"), NOW, &limits(2)).is_ok());
+    }
+
+    #[test]
+    fn revocations_apply_on_next_admit_and_survive_reopen() {
+        let path = std::env::temp_dir().join(format!("sg-sessions-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let store = SessionStore::open(Some(path.clone())).unwrap();
+        let a1 = token("did:a", "fp-1", "0x1");
+        let a2 = token("did:a", "fp-2", "0x2");
+        let b1 = token("did:b", "fp-3", "0x3");
+        for t in [&a1, &a2, &b1] {
+            store.admit(t, NOW, &limits(0)).unwrap();
+        }
+
+        assert_eq!(store.revoke(&RevokeTarget::HostDid("did:a".into()), NOW).len(), 2);
+        assert_eq!(store.admit(&a1, NOW, &limits(0)), Err(SessionRejection::HostDidRevoked));
+        assert!(store.admit(&b1, NOW, &limits(0)).is_ok());
+
+        store.revoke(&RevokeTarget::Device("fp-3".into()), NOW);
+        drop(store);
+
+        let store = SessionStore::open(Some(path.clone())).unwrap();
+        assert_eq!(store.admit(&b1, NOW, &limits(0)), Err(SessionRejection::DeviceRevoked));
+        assert_eq!(store.admit(&a2, NOW, &limits(0)), Err(SessionRejection::HostDidRevoked));
+        assert!(store.list(None).is_empty());
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn did_revocation_covers_unseen_tokens_until_reissued() {
+        let store = SessionStore::open(None).unwrap();
+        store.admit(&token("did:a", "fp-1", "0x1"), NOW, &limits(0)).unwrap();
+        store.revoke(&RevokeTarget::HostDid("did:a".into()), "2026-01-01T01:00:00Z");
+
+        // Minted before the revocation but never presented.
+        let mut unseen = token("did:a", "fp-2", "0x2");
+        unseen.issued_utc = Some("2026-01-01T00:30:00Z".into());
+        let later = "2026-01-01T02:00:00Z";
+        assert_eq!(store.admit(&unseen, later, &limits(0)), Err(SessionRejection::HostDidRevoked));
+        assert_eq!(store.admit(&token("did:a", "fp-2", "0x3"), later, &limits(0)), Err(SessionRejection::HostDidRevoked));
+
+        // Same instant written with an offset still counts as before the cutoff.
+        unseen.issued_utc = Some("2026-01-01T02:00:00+01:00".into());
+        assert_eq!(store.admit(&unseen, later, &limits(0)), Err(SessionRejection::HostDidRevoked));
+
+        unseen.issued_utc = Some("2026-01-01T01:30:00Z".into());
+        assert!(store.admit(&unseen, later, &limits(0)).is_ok());
+    }
+
+    #[test]
+    fn idle_sessions_free_their_device_slot() {
+        let store = SessionStore::open(None).unwrap();
+        let cap = SessionLimits { max_per_device: 1, idle_timeout_secs: 600, ..SessionLimits::default() };
+        store.admit(&token("did:a", "fp", "0x1"), NOW, &cap).unwrap();
+        assert_eq!(
+            store.admit(&token("did:a", "fp", "0x2"), "2026-01-01T00:09:00Z", &cap),
+            Err(SessionRejection::DeviceSessionLimit)
+        );
+        // Still in use: a request at 00:09 keeps it alive past 00:10.
+        store.admit(&token("did:a", "fp", "0x1"), "2026-01-01T00:09:00Z", &cap).unwrap();
+        assert_eq!(
+            store.admit(&token("did:a", "fp", "0x2"), "2026-01-01T00:15:00Z", &cap),
+            Err(SessionRejection::DeviceSessionLimit)
+        );
+        assert!(store.admit(&token("did:a", "fp", "0x2"), "2026-01-01T00:20:00Z", &cap).is_ok());
+        assert_eq!(store.list(None).len(), 1);
+    }
+}
@@ -0,0 +1,226 @@
+#![forbid(unsafe_code)]
+
+use crate::config::{TlsTermination, UpstreamConfig};
+use crate::errors::find_cause;
+use anyhow::Context;
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{Certificate, CertificateError, ClientConfig, PrivateKey, RootCertStore, ServerConfig, ServerName};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Raised by `PinnedSpkiVerifier` when the backend key is not in the pin set.
+#[derive(Debug)]
+pub struct SpkiPinMismatch {
+    pub observed_sha256: String,
+}
+
+impl std::fmt::Display for SpkiPinMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "backend SPKI sha256 {} is not pinned", self.observed_sha256)
+    }
+}
+
+impl std::error::Error for SpkiPinMismatch {}
+
+/// Accepts a backend certificate iff the SHA-256 of its SubjectPublicKeyInfo
+/// is in the configured pin set. CA chains are deliberately ignored: the pin
+/// is the trust anchor. Handshake signatures are still verified by rustls.
+pub struct PinnedSpkiVerifier {
+    pins: Vec<String>,
+}
+
+impl PinnedSpkiVerifier {
+    pub fn new(pins: &[String]) -> Self {
+        Self {
+            pins: pins.iter().map(|p| p.trim().to_ascii_lowercase()).collect(),
+        }
+    }
+}
+
+impl ServerCertVerifier for PinnedSpkiVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let observed = spki_sha256(&end_entity.0)
+            .map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+
+        if self.pins.contains(&observed) {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::InvalidCertificate(CertificateError::Other(
+                Arc::new(SpkiPinMismatch {
+                    observed_sha256: observed,
+                }),
+            )))
+        }
+    }
+}
+
+/// Client config for the upstream hop, pinned to `upstream.spki_sha256_pins`.
+pub fn pinned_client_config(upstream: &UpstreamConfig) -> ClientConfig {
+    ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(Arc::new(PinnedSpkiVerifier::new(
+            &upstream.spki_sha256_pins,
+        )))
+        .with_no_client_auth()
+}
+
+/// Server config for inbound TLS termination; mutual TLS when `client_ca_path` is set.
+pub fn server_config(tls: &TlsTermination) -> anyhow::Result<Arc<ServerConfig>> {
+    let certs = load_certs(&tls.cert_path)?;
+    let key = load_private_key(&tls.key_path)?;
+
+    let builder = ServerConfig::builder().with_safe_defaults();
+    let builder = match &tls.client_ca_path {
+        Some(ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for ca in load_certs(ca_path)? {
+                roots
+                    .add(&ca)
+                    .with_context(|| format!("invalid client CA in {}", ca_path))?;
+            }
+            builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let cfg = builder
+        .with_single_cert(certs, key)
+        .context("tls cert/key pair rejected")?;
+    Ok(Arc::new(cfg))
+}
+
+/// Hex SHA-256 over the certificate's DER-encoded SubjectPublicKeyInfo.
+pub fn spki_sha256(cert_der: &[u8]) -> Result<String, x509_parser::nom::Err<x509_parser::error::X509Error>> {
+    let (_, cert) = x509_parser::parse_x509_certificate(cert_der)?;
+    Ok(hex::encode(Sha256::digest(cert.public_key().raw)))
+}
+
+/// Hex SHA-256 over the whole certificate DER (client cert fingerprint).
+pub fn cert_sha256(cert_der: &[u8]) -> String {
+    hex::encode(Sha256::digest(cert_der))
+}
+
+/// True when a failed upstream request was caused by `SpkiPinMismatch`.
+pub fn is_pin_mismatch(err: &(dyn std::error::Error + 'static)) -> bool {
+    match find_cause::<rustls::Error>(err) {
+        Some(rustls::Error::InvalidCertificate(CertificateError::Other(other))) => {
+            other.downcast_ref::<SpkiPinMismatch>().is_some()
+        }
+        _ => false,
+    }
+}
+
+pub fn load_certs(path: &str) -> anyhow::Result<Vec<Certificate>> {
+    let mut reader = BufReader::new(File::open(path).with_context(|| format!("open {}", path))?);
+    let certs = rustls_pemfile::certs(&mut reader).with_context(|| format!("parse {}", path))?;
+    anyhow::ensure!(!certs.is_empty(), "no certificates in {}", path);
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+fn load_private_key(path: &str) -> anyhow::Result<PrivateKey> {
+    let mut reader = BufReader::new(File::open(path).with_context(|| format!("open {}", path))?);
+    loop {
+        match rustls_pemfile::read_one(&mut reader).with_context(|| format!("parse {}", path))? {
+            Some(rustls_pemfile::Item::PKCS8Key(k))
+            | Some(rustls_pemfile::Item::RSAKey(k))
+            | Some(rustls_pemfile::Item::ECKey(k)) => return Ok(PrivateKey(k)),
+            Some(_) => continue,
+            None => anyhow::bail!("no private key in {}", path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::service::service_fn;
+    use hyper::{Body, Client, Response};
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+    use tokio::net::TcpListener;
+    use tokio_rustls::TlsAcceptor;
+
+    fn self_signed() -> (Certificate, PrivateKey) {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        (
+            Certificate(cert.serialize_der().unwrap()),
+            PrivateKey(cert.serialize_private_key_der()),
+        )
+    }
+
+    /// HTTPS backend on an ephemeral port that answers 200 to everything.
+    async fn spawn_backend(cert: Certificate, key: PrivateKey) -> SocketAddr {
+        let cfg = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert], key)
+            .unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(cfg));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    if let Ok(tls) = acceptor.accept(tcp).await {
+                        let svc = service_fn(|_req| async {
+                            Ok::<_, Infallible>(Response::new(Body::from("ok")))
+                        });
+                        let _ = hyper::server::conn::Http::new().serve_connection(tls, svc).await;
+                    }
+                });
+            }
+        });
+        addr
+    }
+
+    fn pinned_client(pins: Vec<String>) -> Client<hyper_rustls::HttpsConnector<hyper::client::HttpConnector>> {
+        let upstream = UpstreamConfig {
+            spki_sha256_pins: pins,
+        };
+        let connector = hyper_rustls::HttpsConnectorBuilder::new()
+            .with_tls_config(pinned_client_config(&upstream))
+            .https_or_http()
+            .enable_http1()
+            .build();
+        Client::builder().build(connector)
+    }
+
+    #[tokio::test]
+    async fn pinned_backend_is_reachable() {
+        let (cert, key) = self_signed();
+        let pin = spki_sha256(&cert.0).unwrap();
+        let addr = spawn_backend(cert, key).await;
+
+        let uri = format!("https://localhost:{}/", addr.port()).parse().unwrap();
+        let resp = pinned_client(vec![pin]).get(uri).await.unwrap();
+        assert_eq!(resp.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn unpinned_backend_is_classified_as_pin_mismatch() {
+        let (cert, key) = self_signed();
+        let (other, _) = self_signed();
+        let addr = spawn_backend(cert, key).await;
+
+        let uri = format!("https://localhost:{}/", addr.port()).parse().unwrap();
+        let err = pinned_client(vec![spki_sha256(&other.0).unwrap()])
+            .get(uri)
+            .await
+            .unwrap_err();
+        assert!(is_pin_mismatch(&err));
+    }
+}
@@ -1,10 +1,11 @@
 #![forbid(unsafe_code)]
 
-use governance_core::eligibility::{compute_eligibility, GovernanceIndices};
-use governance_core::policy::{GovernanceSafetyProfile, RoleThresholds};
+use governance_core::eligibility::{compute_eligibility, GovernanceIndices, GovernanceSafetyProfile};
+use governance_core::policy::AlnGovernanceShard;
 use governance_core::roles::{GovernanceRole, StakeSnapshot};
-use neurorights_core::{NeurorightsBound, NeurorightsEnvelope};
-use neurorights_firewall::router::wrap_prompt;
+use neurorights_core::{ConsentLedger, NeurorightsBound, NeurorightsEnvelope};
+use neurorights_firewall::inner_state::InnerStateInspector;
+use neurorights_firewall::router::{wrap_prompt, RouteRefusal};
 use crate::audit::GovernanceDecisionLog;
 
 /// Minimal view of the incoming prompt for governance ops.
@@ -23,6 +24,20 @@ pub struct GovernanceDecision {
     pub indices: GovernanceIndices,
 }
 
+/// Entry for raw governance prompts: binds through [`wrap_prompt`], so a
+/// citizen whose consent for the intent is revoked in `ledger`, or whose
+/// args carry inner-state signals, is refused before any eligibility is
+/// computed.
+pub fn route_governance_query(
+    env: crate::PromptEnvelope,
+    ledger: &ConsentLedger,
+    inspector: &InnerStateInspector,
+    query: GovernanceQuery,
+) -> Result<(GovernanceDecision, GovernanceDecisionLog), RouteRefusal> {
+    let bound = wrap_prompt(env, ledger, inspector)?;
+    Ok(handle_governance_query(bound, query))
+}
+
 pub fn handle_governance_query(
     bound: NeurorightsBound<crate::PromptEnvelope, NeurorightsEnvelope>,
     query: GovernanceQuery,
@@ -30,9 +45,9 @@ pub fn handle_governance_query(
     // PromptEnvelope has already passed neurorights checks via NeurorightsBound.
     let _env = bound.inner(); // safe, retrieval-only
 
-    let thresholds = RoleThresholds::default_thresholds();
+    let shard = AlnGovernanceShard::asset_chat_stake_v1();
     let safety = GovernanceSafetyProfile::default_profile();
-    let (roles, indices) = compute_eligibility(&query.stake, &thresholds, &safety);
+    let (roles, indices) = compute_eligibility(&query.stake, &shard, &safety);
 
     let decision = GovernanceDecision {
         eligible_roles: roles,
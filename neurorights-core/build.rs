@@ -1,4 +1,19 @@
-use std::{env, fs, path::PathBuf};
+use std::collections::BTreeMap;
+use std::{env, fs, path::Path, path::PathBuf};
+
+/// Doctrine limits no `neurorights.toml` (or ALN shard) may loosen.
+const DOCTRINE_ROH_CEILING: f64 = 0.3;
+const DOCTRINE_MAX_INNER_STATE_SCORE: u32 = 0;
+
+/// Effective invariants after merging `[invariants]` with the optional shard.
+struct Invariants {
+    roh_ceiling: f64,
+    max_inner_state_score: u32,
+    allow_neurocoercion: bool,
+    allow_inner_state_scoring: bool,
+    revocable_at_will: bool,
+    source: String,
+}
 
 fn main() {
     let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set"));
@@ -21,31 +36,315 @@ fn main() {
         .and_then(|v| v.as_str())
         .expect("policy.anchor missing");
 
-    // Here you can additionally fetch/validate the remote ALN shard
-    // and derive numeric invariants (e.g., MAX_SCORE) if desired.
+    let mut violations = Vec::new();
+    let expected_anchor = format!("did:aln:{}:{}", id, version);
+    if anchor != expected_anchor {
+        violations.push(format!(
+            "policy.anchor = {:?} does not match policy id/version (expected {:?})",
+            anchor, expected_anchor
+        ));
+    }
+
+    let table = parsed
+        .get("invariants")
+        .and_then(|v| v.as_table())
+        .expect("[invariants] missing in neurorights.toml");
+    let mut inv = read_invariants(table, &mut violations);
+    // Check the table on its own first: the shard may only tighten it, and
+    // a loose table value must be reported even if the shard is stricter.
+    check_doctrine(&inv, &mut violations);
 
+    if let Some(shard_path) = table.get("aln_shard").and_then(|v| v.as_str()) {
+        println!("cargo:rerun-if-changed={}", shard_path);
+        let raw = fs::read_to_string(shard_path)
+            .unwrap_or_else(|e| panic!("invariants.aln_shard {:?}: {}", shard_path, e));
+        merge_shard(&mut inv, id, shard_path, &aln_key_values(&raw), &mut violations);
+    }
+
+    let compat = read_compatibility(&parsed, version, &mut violations);
+
+    if !violations.is_empty() {
+        panic!(
+            "neurorights.toml violates neurorights doctrine ({} problems):\n  - {}",
+            violations.len(),
+            violations.join("\n  - ")
+        );
+    }
+
+    let roh_ceiling_milli = (inv.roh_ceiling * 1000.0).round() as u32;
+    let doctrine_roh_milli = (DOCTRINE_ROH_CEILING * 1000.0).round() as u32;
     let gen = format!(
         r#"
-        // @generated by neurorights-core build.rs
+        // @generated by neurorights-core build.rs from {source}
 
         pub const NEURORIGHTS_POLICY_ID: &str = "{id}";
         pub const NEURORIGHTS_POLICY_VERSION: &str = "{version}";
         pub const NEURORIGHTS_POLICY_ANCHOR: &str = "{anchor}";
 
-        // Example compile-time invariant hooks; wire to ALN shard values:
-        pub const MAX_INNER_STATE_SCORE: u32 = 0;
-        pub const ALLOW_NEUROCOERCION: bool = false;
+        /// Where the invariants below were read from.
+        pub const NEURORIGHTS_INVARIANTS_SOURCE: &str = "{source}";
+
+        /// Risk-of-harm ceiling, and the same value in thousandths for const checks.
+        pub const ROH_CEILING: f64 = {roh:?};
+        pub const ROH_CEILING_MILLI: u32 = {roh_milli};
+        pub const MAX_INNER_STATE_SCORE: u32 = {max_score};
+        pub const ALLOW_NEUROCOERCION: bool = {coercion};
+        pub const ALLOW_INNER_STATE_SCORING: bool = {scoring};
+        pub const CONSENT_REVOCABLE_AT_WILL: bool = {revocable};
 
-        // Static sanity checks; expand with real invariants as the ALN shard matures.
+        // Re-checked at compile time so a hand-edited OUT_DIR cannot slip through.
         const _: () = {{
-            // no inner-state scoring allowed
-            assert!(MAX_INNER_STATE_SCORE == 0);
+            assert!(ROH_CEILING_MILLI > 0);
+            assert!(ROH_CEILING_MILLI <= {doctrine_roh_milli});
+            assert!(MAX_INNER_STATE_SCORE == {doctrine_score});
             assert!(!ALLOW_NEUROCOERCION);
+            assert!(!ALLOW_INNER_STATE_SCORING);
+            assert!(CONSENT_REVOCABLE_AT_WILL);
         }};
-        "#
+        "#,
+        source = inv.source,
+        roh = inv.roh_ceiling,
+        roh_milli = roh_ceiling_milli,
+        max_score = inv.max_inner_state_score,
+        coercion = inv.allow_neurocoercion,
+        scoring = inv.allow_inner_state_scoring,
+        revocable = inv.revocable_at_will,
+        doctrine_score = DOCTRINE_MAX_INNER_STATE_SCORE,
     );
 
+    let mut gen = gen;
+    gen.push_str("\n        /// Older profile versions and how `NeurorightsBound::try_bind` treats them.\n");
+    gen.push_str("        pub const POLICY_COMPATIBILITY: &[crate::PolicyVersion] = &[\n");
+    for (v, rule, note) in &compat {
+        gen.push_str(&format!(
+            "            crate::PolicyVersion {{ version: {:?}, compatibility: crate::Compatibility::{}, note: {:?} }},\n",
+            v, rule, note
+        ));
+    }
+    gen.push_str("        ];\n");
+
     fs::write(out_dir.join("generated_neurorights.rs"), gen)
         .expect("failed to write generated_neurorights.rs");
     println!("cargo:rerun-if-changed=neurorights.toml");
 }
+
+/// `[[compatibility]]` entries as `(version, Compatibility variant, note)`.
+fn read_compatibility(
+    parsed: &toml::Value,
+    current_version: &str,
+    violations: &mut Vec<String>,
+) -> Vec<(String, &'static str, String)> {
+    let mut out: Vec<(String, &'static str, String)> = Vec::new();
+    let entries = match parsed.get("compatibility") {
+        Some(toml::Value::Array(entries)) => entries.as_slice(),
+        Some(_) => {
+            violations.push("compatibility must be an array of tables ([[compatibility]])".to_string());
+            return out;
+        }
+        None => return out,
+    };
+
+    for (i, entry) in entries.iter().enumerate() {
+        let field = |key: &str| entry.get(key).and_then(|v| v.as_str()).unwrap_or("");
+        let version = field("version");
+        let rule = match field("rule") {
+            "accept" => "Accept",
+            "accept_with_upgrade" => "AcceptWithUpgrade",
+            "reject" => "Reject",
+            other => {
+                violations.push(format!(
+                    "compatibility[{}].rule = {:?} must be accept, accept_with_upgrade or reject",
+                    i, other
+                ));
+                continue;
+            }
+        };
+        if version.is_empty() {
+            violations.push(format!("compatibility[{}].version is missing", i));
+        } else if version == current_version {
+            violations.push(format!(
+                "compatibility[{}].version = {:?} is the current policy version; it is always accepted",
+                i, version
+            ));
+        } else if out.iter().any(|(v, _, _)| v == version) {
+            violations.push(format!("compatibility[{}].version = {:?} is listed twice", i, version));
+        } else {
+            out.push((version.to_string(), rule, field("note").to_string()));
+        }
+    }
+    out
+}
+
+fn read_invariants(table: &toml::value::Table, violations: &mut Vec<String>) -> Invariants {
+    let mut float = |key: &str| match table.get(key) {
+        Some(v) => match v.as_float().or_else(|| v.as_integer().map(|i| i as f64)) {
+            Some(f) => f,
+            None => {
+                violations.push(format!("invariants.{} must be a number, got {}", key, v));
+                0.0
+            }
+        },
+        None => {
+            violations.push(format!("invariants.{} is missing", key));
+            0.0
+        }
+    };
+    let roh_ceiling = float("roh_ceiling");
+    let max_inner_state_score = float("max_inner_state_score");
+
+    let mut flag = |key: &str| match table.get(key).map(|v| v.as_bool()) {
+        Some(Some(b)) => b,
+        Some(None) => {
+            violations.push(format!("invariants.{} must be a boolean", key));
+            false
+        }
+        None => {
+            violations.push(format!("invariants.{} is missing", key));
+            false
+        }
+    };
+    let allow_neurocoercion = flag("allow_neurocoercion");
+    let allow_inner_state_scoring = flag("allow_inner_state_scoring");
+    let revocable_at_will = flag("revocable_at_will");
+
+    if max_inner_state_score < 0.0 || max_inner_state_score.fract() != 0.0 {
+        violations.push(format!(
+            "invariants.max_inner_state_score = {} must be a non-negative integer",
+            max_inner_state_score
+        ));
+    }
+
+    Invariants {
+        roh_ceiling,
+        max_inner_state_score: max_inner_state_score.max(0.0) as u32,
+        allow_neurocoercion,
+        allow_inner_state_scoring,
+        revocable_at_will,
+        source: "neurorights.toml".to_string(),
+    }
+}
+
+/// Tighten `inv` with values found in the ALN shard; a shard value looser
+/// than the doctrine is reported against the shard itself.
+fn merge_shard(
+    inv: &mut Invariants,
+    policy_id: &str,
+    shard_path: &str,
+    values: &BTreeMap<String, String>,
+    violations: &mut Vec<String>,
+) {
+    let lookup = |leaf: &str| {
+        values
+            .iter()
+            .find(|(k, _)| k.as_str() == leaf || k.ends_with(&format!(".{}", leaf)))
+            .map(|(k, v)| (k.clone(), v.clone()))
+    };
+    let shard = Path::new(shard_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| shard_path.to_string());
+
+    if let Some((key, envelope)) = lookup("neurorights_envelope") {
+        if envelope != policy_id {
+            violations.push(format!(
+                "{}: {} = {:?} does not anchor policy {:?}",
+                shard, key, envelope, policy_id
+            ));
+        }
+    }
+
+    for leaf in ["risk_of_harm_ceiling", "roh_ceiling"] {
+        if let Some((key, raw)) = lookup(leaf) {
+            match raw.parse::<f64>() {
+                Ok(v) if !(v > 0.0 && v <= DOCTRINE_ROH_CEILING) => violations.push(format!(
+                    "{}: {} = {} must be in (0, {}] (doctrine RoH ceiling)",
+                    shard, key, v, DOCTRINE_ROH_CEILING
+                )),
+                Ok(v) => inv.roh_ceiling = inv.roh_ceiling.min(v),
+                Err(_) => violations.push(format!("{}: {} = {:?} is not a number", shard, key, raw)),
+            }
+        }
+    }
+
+    let mut require_true = |leaf: &str, apply: &mut dyn FnMut()| {
+        if let Some((key, raw)) = lookup(leaf) {
+            match raw.as_str() {
+                "true" => apply(),
+                other => violations.push(format!("{}: {} = {} but doctrine requires true", shard, key, other)),
+            }
+        }
+    };
+    require_true("no_neurocoercion", &mut || inv.allow_neurocoercion = false);
+    require_true("no_score_from_inner_state", &mut || {
+        inv.allow_inner_state_scoring = false;
+        inv.max_inner_state_score = 0;
+    });
+    require_true("revocable_at_will", &mut || inv.revocable_at_will = true);
+
+    inv.source = format!("neurorights.toml + {}", shard);
+}
+
+fn check_doctrine(inv: &Invariants, violations: &mut Vec<String>) {
+    if !(inv.roh_ceiling > 0.0 && inv.roh_ceiling <= DOCTRINE_ROH_CEILING) {
+        violations.push(format!(
+            "invariants.roh_ceiling = {} must be in (0, {}]",
+            inv.roh_ceiling, DOCTRINE_ROH_CEILING
+        ));
+    }
+    if inv.max_inner_state_score != DOCTRINE_MAX_INNER_STATE_SCORE {
+        violations.push(format!(
+            "invariants.max_inner_state_score = {} but inner-state scoring is forbidden (must be {})",
+            inv.max_inner_state_score, DOCTRINE_MAX_INNER_STATE_SCORE
+        ));
+    }
+    if inv.allow_neurocoercion {
+        violations.push("invariants.allow_neurocoercion = true but neurocoercion is forbidden".to_string());
+    }
+    if inv.allow_inner_state_scoring {
+        violations.push("invariants.allow_inner_state_scoring = true but inner-state scoring is forbidden".to_string());
+    }
+    if !inv.revocable_at_will {
+        violations.push("invariants.revocable_at_will = false but consent must be revocable at will".to_string());
+    }
+}
+
+/// Flatten the indentation-nested `key: value` lines of an ALN shard into
+/// dotted paths (`fields.risk.risk_of_harm_ceiling -> 0.3`). Lists, block
+/// scalars and comments are skipped; only scalar leaves are needed here.
+fn aln_key_values(raw: &str) -> BTreeMap<String, String> {
+    let mut out = BTreeMap::new();
+    let mut stack: Vec<(usize, String)> = Vec::new();
+
+    for line in raw.lines() {
+        let content = line.split(" #").next().unwrap_or("").trim_end();
+        let trimmed = content.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('-') {
+            continue;
+        }
+        let indent = content.len() - trimmed.len();
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        if key.contains(' ') {
+            continue;
+        }
+
+        while stack.last().is_some_and(|(depth, _)| *depth >= indent) {
+            stack.pop();
+        }
+        let path = stack
+            .iter()
+            .map(|(_, k)| k.as_str())
+            .chain(std::iter::once(key))
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let value = value.trim().trim_matches('"');
+        if value.is_empty() || value == ">" || value == "|" {
+            stack.push((indent, key.to_string()));
+        } else {
+            out.insert(path, value.to_string());
+        }
+    }
+    out
+}
@@ -0,0 +1,236 @@
+use crate::router::PromptEnvelope;
+use serde_json::Value;
+use std::fmt;
+
+/// Field names and purposes the inspector treats as inner-state scoring.
+///
+/// A field entry ending in `*` matches by prefix (`eeg_*`). Names are
+/// compared case-insensitively with `-` and `.` folded to `_`; purposes are
+/// matched against the words of `PromptEnvelope.intent`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InnerStateConfig {
+    pub signal_fields: Vec<String>,
+    pub scoring_purposes: Vec<String>,
+}
+
+impl Default for InnerStateConfig {
+    fn default() -> Self {
+        let fields = [
+            "eeg_*",
+            "alpha_power",
+            "beta_power",
+            "theta_power",
+            "delta_power",
+            "gamma_power",
+            "engagement_index",
+            "engagement_score",
+            "attention_score",
+            "stress_score",
+            "stress_level",
+            "stress_index",
+            "hrv",
+            "hrv_*",
+            "heart_rate_variability",
+            "arousal_score",
+            "valence_score",
+        ];
+        let purposes = ["score", "scoring", "rank", "ranking", "eligibility", "eligible"];
+        Self {
+            signal_fields: fields.iter().map(|s| s.to_string()).collect(),
+            scoring_purposes: purposes.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+/// Where an inner-state field was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FindingSource {
+    Args,
+    ToolResult { tool: String },
+}
+
+/// One inner-state signal in a payload whose purpose is scoring.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InnerStateFinding {
+    pub source: FindingSource,
+    /// JSON pointer to the field, e.g. `/biometrics/eeg_alpha`.
+    pub path: String,
+    pub field: String,
+    pub purpose: String,
+}
+
+impl InnerStateFinding {
+    pub fn code(&self) -> &'static str {
+        "neurorights_inner_state_scoring"
+    }
+}
+
+impl fmt::Display for InnerStateFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match &self.source {
+            FindingSource::Args => "args".to_string(),
+            FindingSource::ToolResult { tool } => format!("tool result {}", tool),
+        };
+        write!(
+            f,
+            "inner-state field {:?} at {}{} used for purpose {:?}",
+            self.field, source, self.path, self.purpose
+        )
+    }
+}
+
+/// Returned by the router when a payload carries inner-state signals into
+/// a scoring purpose (`no_score_from_inner_state`, `MAX_INNER_STATE_SCORE = 0`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InnerStateViolation {
+    pub findings: Vec<InnerStateFinding>,
+}
+
+impl InnerStateViolation {
+    pub fn code(&self) -> &'static str {
+        "neurorights_inner_state_scoring"
+    }
+}
+
+impl fmt::Display for InnerStateViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} inner-state scoring finding(s)", self.findings.len())?;
+        for finding in &self.findings {
+            write!(f, "; {}", finding)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InnerStateViolation {}
+
+/// Static inspector for `PromptEnvelope.args` and tool results.
+#[derive(Clone, Debug, Default)]
+pub struct InnerStateInspector {
+    config: InnerStateConfig,
+}
+
+impl InnerStateInspector {
+    pub fn new(config: InnerStateConfig) -> Self {
+        let normalize_all = |list: Vec<String>| list.iter().map(|s| normalize(s)).collect();
+        Self {
+            config: InnerStateConfig {
+                signal_fields: normalize_all(config.signal_fields),
+                scoring_purposes: normalize_all(config.scoring_purposes),
+            },
+        }
+    }
+
+    pub fn config(&self) -> &InnerStateConfig {
+        &self.config
+    }
+
+    /// The scoring purpose named by `intent`, if any.
+    pub fn scoring_purpose(&self, intent: &str) -> Option<String> {
+        normalize(intent)
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .find(|word| self.config.scoring_purposes.iter().any(|p| p == word))
+            .map(str::to_string)
+    }
+
+    /// Inner-state fields in `env.args` and `tool_results`. Empty when the
+    /// declared purpose is not scoring, ranking or eligibility.
+    pub fn inspect(&self, env: &PromptEnvelope, tool_results: &[(String, Value)]) -> Vec<InnerStateFinding> {
+        let Some(purpose) = self.scoring_purpose(&env.intent) else {
+            return Vec::new();
+        };
+
+        let mut findings = Vec::new();
+        self.walk(&env.args, &mut String::new(), &FindingSource::Args, &purpose, &mut findings);
+        for (tool, output) in tool_results {
+            let source = FindingSource::ToolResult { tool: tool.clone() };
+            self.walk(output, &mut String::new(), &source, &purpose, &mut findings);
+        }
+        findings
+    }
+
+    fn walk(
+        &self,
+        value: &Value,
+        path: &mut String,
+        source: &FindingSource,
+        purpose: &str,
+        findings: &mut Vec<InnerStateFinding>,
+    ) {
+        let len = path.len();
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    path.push('/');
+                    path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                    if !child.is_null() && self.is_signal(key) {
+                        findings.push(InnerStateFinding {
+                            source: source.clone(),
+                            path: path.clone(),
+                            field: key.clone(),
+                            purpose: purpose.to_string(),
+                        });
+                    }
+                    self.walk(child, path, source, purpose, findings);
+                    path.truncate(len);
+                }
+            }
+            Value::Array(items) => {
+                for (i, child) in items.iter().enumerate() {
+                    path.push('/');
+                    path.push_str(&i.to_string());
+                    self.walk(child, path, source, purpose, findings);
+                    path.truncate(len);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn is_signal(&self, key: &str) -> bool {
+        let key = normalize(key);
+        self.config.signal_fields.iter().any(|field| match field.strip_suffix('*') {
+            Some(prefix) => key.starts_with(prefix),
+            None => key == *field,
+        })
+    }
+}
+
+fn normalize(s: &str) -> String {
+    s.trim().to_ascii_lowercase().replace(['-', '.'], "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neurorights_core::NeurorightsProfile;
+    use serde_json::json;
+
+    fn envelope(intent: &str, args: Value) -> PromptEnvelope {
+        PromptEnvelope {
+            trace_id: "hex:01".into(),
+            intent: intent.into(),
+            args,
+            security_level: "standard".into(),
+            identity: "did:example:a".into(),
+            provenance: String::new(),
+            governance: String::new(),
+            neurorights_profile: NeurorightsProfile::current(),
+        }
+    }
+
+    #[test]
+    fn flags_inner_state_fields_only_for_scoring_purposes() {
+        let inspector = InnerStateInspector::default();
+        let args = json!({"applicant": {"Engagement-Index": 0.7, "name": "a"}, "readings": [{"eeg_alpha": 9.1}]});
+        let tool = vec![("wearable".to_string(), json!({"HRV_rmssd": 42, "stress_score": null}))];
+
+        let findings = inspector.inspect(&envelope("rank_applicants", args.clone()), &tool);
+        let paths: Vec<_> = findings.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["/applicant/Engagement-Index", "/readings/0/eeg_alpha", "/HRV_rmssd"]);
+        assert_eq!(findings[2].source, FindingSource::ToolResult { tool: "wearable".into() });
+        assert!(findings.iter().all(|f| f.purpose == "rank"));
+
+        assert!(inspector.inspect(&envelope("summarize", args), &tool).is_empty());
+    }
+}
@@ -3,6 +3,14 @@
 pub mod router;
 pub mod audit;
 pub mod ci_guards;
+pub mod inner_state;
 
-pub use router::wrap_prompt;
+pub use router::{screen_inner_state, wrap_prompt, RouteRefusal};
+pub use inner_state::{
+    FindingSource, InnerStateConfig, InnerStateFinding, InnerStateInspector, InnerStateViolation,
+};
 pub use audit::{Authorship, EvidenceStamp};
+pub use neurorights_core::{
+    Compatibility, ConsentLedger, HasConsentSubject, HasNeurorightsProfile, HasTraceId, NeurorightsBound, NeurorightsEnvelope, NeurorightsProfile,
+    NeurorightsViolation, PolicyMigration, PolicyRegistry,
+};
@@ -1,33 +1,145 @@
-use neurorights_core::{NeurorightsBound, NeurorightsEnvelope, NeurorightsProfile};
+use crate::inner_state::{InnerStateInspector, InnerStateViolation};
+use neurorights_core::{ConsentLedger, NeurorightsBound, NeurorightsEnvelope, NeurorightsProfile, NeurorightsViolation};
+use std::fmt;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, neurorights_macros::NeurorightsEnvelope)]
 pub struct PromptEnvelope {
     pub trace_id: String,
+    #[neurorights(purpose)]
     pub intent: String,          // integrate with your actual Intent enum
     pub args: serde_json::Value,
     pub security_level: String,  // integrate with real type
+    #[neurorights(subject)]
     pub identity: String,        // DID / ALN / Bostrom identity
     pub provenance: String,
     pub governance: String,
     pub neurorights_profile: NeurorightsProfile,
 }
 
+/// Why `wrap_prompt` refused to route a prompt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RouteRefusal {
+    Neurorights(NeurorightsViolation),
+    InnerState(InnerStateViolation),
+}
+
+impl RouteRefusal {
+    /// Stable machine-readable code for logs and API errors.
+    pub fn code(&self) -> &'static str {
+        match self {
+            RouteRefusal::Neurorights(violation) => violation.code(),
+            RouteRefusal::InnerState(violation) => violation.code(),
+        }
+    }
+}
+
+impl fmt::Display for RouteRefusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteRefusal::Neurorights(violation) => violation.fmt(f),
+            RouteRefusal::InnerState(violation) => violation.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for RouteRefusal {}
+
+impl From<NeurorightsViolation> for RouteRefusal {
+    fn from(violation: NeurorightsViolation) -> Self {
+        RouteRefusal::Neurorights(violation)
+    }
+}
+
+impl From<InnerStateViolation> for RouteRefusal {
+    fn from(violation: InnerStateViolation) -> Self {
+        RouteRefusal::InnerState(violation)
+    }
+}
+
 /// Construct a bound envelope from a raw `PromptEnvelope`.
-/// This is the only allowed entry path for router handlers.
-pub fn wrap_prompt(env: PromptEnvelope) -> NeurorightsBound<PromptEnvelope, NeurorightsEnvelope> {
-    let profile = &env.neurorights_profile;
-
-    assert_eq!(
-        profile.id,
-        neurorights_core::NEURORIGHTS_POLICY_ID,
-        "PromptEnvelope neurorights_profile.id mismatch"
-    );
-    assert_eq!(
-        profile.version,
-        neurorights_core::NEURORIGHTS_POLICY_VERSION,
-        "PromptEnvelope neurorights_profile.version mismatch"
-    );
-
-    let envelope = NeurorightsEnvelope::compiled();
-    NeurorightsBound::new(env, envelope)
+/// This is the only allowed entry path for router handlers; a profile
+/// mismatch is returned to the caller instead of panicking, and an older
+/// supported policy version is upgraded and noted in `provenance`.
+///
+/// The caller's `identity` must hold an unrevoked grant in `ledger` for the
+/// prompt's `intent`, so a revocation refuses the very next route. Once
+/// bound, `args` are screened by `inspector`; tool results are screened
+/// with [`screen_inner_state`] as they arrive.
+pub fn wrap_prompt(
+    env: PromptEnvelope,
+    ledger: &ConsentLedger,
+    inspector: &InnerStateInspector,
+) -> Result<NeurorightsBound<PromptEnvelope, NeurorightsEnvelope>, RouteRefusal> {
+    let bound = NeurorightsBound::try_bind_with_consent(env, ledger)?;
+    screen_inner_state(&bound, &[], inspector)?;
+    Ok(bound)
+}
+
+/// Block a bound prompt whose args or tool results carry inner-state
+/// signals into a scoring, ranking or eligibility purpose.
+pub fn screen_inner_state(
+    bound: &NeurorightsBound<PromptEnvelope, NeurorightsEnvelope>,
+    tool_results: &[(String, serde_json::Value)],
+    inspector: &InnerStateInspector,
+) -> Result<(), InnerStateViolation> {
+    let findings = inspector.inspect(bound.payload(), tool_results);
+    if findings.is_empty() {
+        Ok(())
+    } else {
+        Err(InnerStateViolation { findings })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(intent: &str, args: serde_json::Value) -> PromptEnvelope {
+        PromptEnvelope {
+            trace_id: "hex:02".into(),
+            intent: intent.into(),
+            args,
+            security_level: "standard".into(),
+            identity: "did:example:citizen".into(),
+            provenance: String::new(),
+            governance: String::new(),
+            neurorights_profile: NeurorightsProfile::current(),
+        }
+    }
+
+    #[test]
+    fn revoked_consent_refuses_the_next_route() {
+        let ledger = ConsentLedger::new();
+        let inspector = InnerStateInspector::default();
+        let route = |ledger: &ConsentLedger| wrap_prompt(prompt("fetch_record", serde_json::Value::Null), ledger, &inspector);
+        assert_eq!(route(&ledger).unwrap_err().code(), "neurorights_consent_not_granted");
+
+        ledger.grant("did:example:citizen", "fetch_record", "2026-01-01T00:00:00Z").unwrap();
+        assert!(route(&ledger).is_ok());
+
+        ledger.revoke("did:example:citizen", "fetch_record", "2026-01-01T00:05:00Z").unwrap();
+        assert_eq!(route(&ledger).unwrap_err().code(), "neurorights_consent_revoked");
+    }
+
+    #[test]
+    fn inner_state_args_refuse_a_scoring_route_after_binding() {
+        let ledger = ConsentLedger::new();
+        let inspector = InnerStateInspector::default();
+        ledger.grant("did:example:citizen", "rank_applicants", "2026-01-01T00:00:00Z").unwrap();
+
+        let args = serde_json::json!({"applicant": {"eeg_alpha": 9.1}});
+        let refusal = wrap_prompt(prompt("rank_applicants", args), &ledger, &inspector).unwrap_err();
+        let RouteRefusal::InnerState(violation) = refusal else {
+            panic!("expected an inner-state refusal, got {refusal:?}");
+        };
+        assert_eq!(violation.findings[0].path, "/applicant/eeg_alpha");
+
+        let args = serde_json::json!({"applicant": {"name": "a"}});
+        let bound = wrap_prompt(prompt("rank_applicants", args), &ledger, &inspector).unwrap();
+        let tool = vec![("wearable".to_string(), serde_json::json!({"stress_score": 0.4}))];
+        assert_eq!(
+            screen_inner_state(&bound, &tool, &inspector).unwrap_err().code(),
+            "neurorights_inner_state_scoring"
+        );
+    }
 }
@@ -0,0 +1,187 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Field, Fields, Ident};
+
+/// Which accessor a field backs, from its name or `#[neurorights(...)]`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Profile,
+    TraceId,
+    Provenance,
+    Subject,
+    Purpose,
+}
+
+impl Role {
+    fn key(self) -> &'static str {
+        match self {
+            Role::Profile => "profile",
+            Role::TraceId => "trace_id",
+            Role::Provenance => "provenance",
+            Role::Subject => "subject",
+            Role::Purpose => "purpose",
+        }
+    }
+
+    /// Field picked when no field is marked; consent roles must be marked.
+    fn default_field(self) -> Option<&'static str> {
+        match self {
+            Role::Profile => Some("neurorights_profile"),
+            Role::TraceId => Some("trace_id"),
+            Role::Provenance => Some("provenance"),
+            Role::Subject | Role::Purpose => None,
+        }
+    }
+}
+
+const ROLES: [Role; 5] = [Role::Profile, Role::TraceId, Role::Provenance, Role::Subject, Role::Purpose];
+
+pub fn expand(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    match derive(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn derive(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => &named.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "#[derive(NeurorightsEnvelope)] requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[derive(NeurorightsEnvelope)] only supports structs",
+            ))
+        }
+    };
+
+    let mut tagged: Vec<(Role, &Field)> = Vec::new();
+    for field in fields {
+        for role in field_roles(field)? {
+            if tagged.iter().any(|(r, _)| *r == role) {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    format!("more than one field is marked #[neurorights({})]", role.key()),
+                ));
+            }
+            tagged.push((role, field));
+        }
+    }
+    let pick = |role: Role| -> Option<&Ident> {
+        tagged
+            .iter()
+            .find(|(r, _)| *r == role)
+            .map(|(_, f)| *f)
+            .or_else(|| {
+                let default = role.default_field()?;
+                fields
+                    .iter()
+                    .find(|f| f.ident.as_ref().is_some_and(|i| i == default))
+            })
+            .and_then(|f| f.ident.as_ref())
+    };
+
+    let Some(profile) = pick(Role::Profile) else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(NeurorightsEnvelope)] needs a `neurorights_profile: NeurorightsProfile` field \
+             (or a field marked #[neurorights(profile)])",
+        ));
+    };
+    let Some(provenance) = pick(Role::Provenance) else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[derive(NeurorightsEnvelope)] needs a `provenance` field (or a field marked \
+             #[neurorights(provenance)]) to record policy migrations",
+        ));
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let trace_id_impl = pick(Role::TraceId).map(|trace_id| {
+        quote! {
+            impl #impl_generics ::neurorights_core::HasTraceId for #name #ty_generics #where_clause {
+                fn trace_id(&self) -> &str {
+                    ::core::convert::AsRef::<str>::as_ref(&self.#trace_id)
+                }
+            }
+        }
+    });
+
+    let consent_subject_impl = match (pick(Role::Subject), pick(Role::Purpose)) {
+        (Some(subject), Some(purpose)) => Some(quote! {
+            impl #impl_generics ::neurorights_core::HasConsentSubject for #name #ty_generics #where_clause {
+                fn subject_did(&self) -> &str {
+                    ::core::convert::AsRef::<str>::as_ref(&self.#subject)
+                }
+
+                fn processing_purpose(&self) -> &str {
+                    ::core::convert::AsRef::<str>::as_ref(&self.#purpose)
+                }
+            }
+        }),
+        (None, None) => None,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[neurorights(subject)] and #[neurorights(purpose)] must be used together",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::neurorights_core::HasNeurorightsProfile for #name #ty_generics #where_clause {
+            fn neurorights_profile(&self) -> &::neurorights_core::NeurorightsProfile {
+                &self.#profile
+            }
+
+            fn set_neurorights_profile(&mut self, profile: ::neurorights_core::NeurorightsProfile) {
+                self.#profile = profile;
+            }
+
+            fn record_policy_migration(&mut self, migration: &::neurorights_core::PolicyMigration) {
+                ::neurorights_core::ProvenanceLog::record_migration(&mut self.#provenance, migration);
+            }
+        }
+
+        #trace_id_impl
+
+        #consent_subject_impl
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Check the profile against the compiled neurorights policy.
+            pub fn validate(&self) -> ::core::result::Result<(), ::neurorights_core::NeurorightsViolation> {
+                ::neurorights_core::NeurorightsEnvelope::compiled().check_profile(&self.#profile)
+            }
+        }
+    })
+}
+
+/// Roles named in the field's `#[neurorights(...)]` attributes.
+fn field_roles(field: &Field) -> syn::Result<Vec<Role>> {
+    let mut roles = Vec::new();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("neurorights")) {
+        attr.parse_nested_meta(|meta| {
+            match ROLES.iter().find(|r| meta.path.is_ident(r.key())) {
+                Some(role) => {
+                    roles.push(*role);
+                    Ok(())
+                }
+                None => Err(meta.error(
+                    "expected `profile`, `trace_id`, `provenance`, `subject` or `purpose`",
+                )),
+            }
+        })?;
+    }
+    Ok(roles)
+}
@@ -1,34 +1,101 @@
 use proc_macro::TokenStream;
-use quote::{quote, format_ident};
-use syn::{parse_macro_input, ItemFn};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, FnArg, GenericArgument, ItemFn, Pat, PathArguments, Type};
 
-pub fn expand(_attr: TokenStream, item: TokenStream) -> TokenStream {
+const FIRST_PARAM: &str =
+    "#[neurorights_handler] requires a first parameter of type `NeurorightsBound<_, NeurorightsEnvelope>`";
+
+/// Keeps the handler's signature (async-ness, generics, return type) and
+/// prefixes its body with a compile-time input check and an audit call.
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        let attr = TokenStream2::from(attr);
+        return syn::Error::new_spanned(attr, "#[neurorights_handler] takes no arguments")
+            .to_compile_error()
+            .into();
+    }
     let input = parse_macro_input!(item as ItemFn);
-    let vis = &input.vis;
-    let sig = &input.sig;
-    let block = &input.block;
-    let name = &sig.ident;
-
-    let wrapped_name = format_ident!("{}_inner", name);
-
-    // Original logic moved into `_inner`.
-    let gen = quote! {
-        #vis fn #wrapped_name(
-            bound: ::neurorights_core::NeurorightsBound<
-                crate::PromptEnvelope,
-                ::neurorights_core::NeurorightsEnvelope
-            >
-        ) #block
-
-        #vis fn #name(
-            bound: ::neurorights_core::NeurorightsBound<
-                crate::PromptEnvelope,
-                ::neurorights_core::NeurorightsEnvelope
-            >
-        ) {
-            #wrapped_name(bound)
-        }
+    match rewrite(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn rewrite(mut func: ItemFn) -> syn::Result<TokenStream2> {
+    let handler = func.sig.ident.to_string();
+    let bound_ident = format_ident!("__neurorights_bound");
+
+    // A method receiver is allowed; the first non-receiver parameter is checked.
+    let Some(FnArg::Typed(first)) = func
+        .sig
+        .inputs
+        .iter_mut()
+        .find(|arg| matches!(arg, FnArg::Typed(_)))
+    else {
+        return Err(syn::Error::new(func.sig.paren_token.span.join(), FIRST_PARAM));
+    };
+    check_spelled_type(&first.ty)?;
+
+    let ty = (*first.ty).clone();
+    let pat = std::mem::replace(
+        &mut *first.pat,
+        Pat::Verbatim(quote! { #bound_ident }),
+    );
+
+    // Spanned on the parameter type, so a type that is not a valid
+    // `NeurorightsHandlerInput` is reported there.
+    let record = quote_spanned! {ty.span()=>
+        ::neurorights_core::record_handler_call::<#ty>(#handler, &#bound_ident);
     };
+    let block = &func.block;
+    let body = quote! {{
+        #record
+        let #pat = #bound_ident;
+        #block
+    }};
+    *func.block = syn::parse2(body)?;
 
-    gen.into()
+    Ok(quote! { #func })
+}
+
+/// Catch `NeurorightsBound<_, SomethingElse>` early with a precise message.
+/// Other spellings (aliases included) are checked by the trait bound on
+/// `record_handler_call`.
+fn check_spelled_type(ty: &Type) -> syn::Result<()> {
+    let Type::Path(path) = ty else {
+        return Ok(());
+    };
+    let Some(last) = path.path.segments.last() else {
+        return Ok(());
+    };
+    if last.ident != "NeurorightsBound" {
+        return Ok(());
+    }
+
+    let envelope = match &last.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|a| match a {
+                GenericArgument::Type(t) => Some(t),
+                _ => None,
+            })
+            .nth(1),
+        _ => None,
+    };
+    let is_envelope = matches!(
+        envelope,
+        Some(Type::Path(p)) if p.path.segments.last().is_some_and(|s| s.ident == "NeurorightsEnvelope")
+    );
+    if is_envelope {
+        Ok(())
+    } else {
+        let span = envelope.map(|e| e.span()).unwrap_or_else(|| ty.span());
+        Err(syn::Error::new(
+            span,
+            "#[neurorights_handler] handlers must be bound to `NeurorightsEnvelope`",
+        ))
+    }
 }
@@ -1,10 +1,31 @@
 #![forbid(unsafe_code)]
 
+mod derive_neurorights_envelope;
 mod handler_attr;
 
 use proc_macro::TokenStream;
 
+/// Marks a neurorights-bound handler.
+///
+/// The first (non-receiver) parameter must be `NeurorightsBound<_, NeurorightsEnvelope>`;
+/// anything else is a compile error. The signature is kept as written and the body is
+/// prefixed with `neurorights_core::record_handler_call`, which records the handler
+/// name, the payload's trace id and the bound policy version (for `async` handlers,
+/// when the future is first polled).
 #[proc_macro_attribute]
 pub fn neurorights_handler(attr: TokenStream, item: TokenStream) -> TokenStream {
     handler_attr::expand(attr, item)
 }
+
+/// Implements `HasNeurorightsProfile` (and `HasTraceId` when a trace id field
+/// exists) plus an inherent `validate()` for a payload struct.
+///
+/// Fields are found by name (`neurorights_profile`, `trace_id`, `provenance`)
+/// or by `#[neurorights(profile | trace_id | provenance)]`. A struct without a
+/// profile or provenance field is a compile error. Fields marked
+/// `#[neurorights(subject)]` and `#[neurorights(purpose)]` also implement
+/// `HasConsentSubject`, for binding through the consent ledger.
+#[proc_macro_derive(NeurorightsEnvelope, attributes(neurorights))]
+pub fn derive_neurorights_envelope(item: TokenStream) -> TokenStream {
+    derive_neurorights_envelope::expand(item)
+}
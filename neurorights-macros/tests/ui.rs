@@ -0,0 +1,13 @@
+#[test]
+fn neurorights_handler_ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/handler_pass.rs");
+    t.compile_fail("tests/ui/handler_fail_*.rs");
+}
+
+#[test]
+fn derive_neurorights_envelope_ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/derive_pass.rs");
+    t.compile_fail("tests/ui/derive_fail_*.rs");
+}
@@ -0,0 +1,8 @@
+use neurorights_macros::NeurorightsEnvelope;
+
+#[derive(NeurorightsEnvelope)]
+enum Prompt {
+    Empty,
+}
+
+fn main() {}
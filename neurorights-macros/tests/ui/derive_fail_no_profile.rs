@@ -0,0 +1,9 @@
+use neurorights_macros::NeurorightsEnvelope;
+
+#[derive(NeurorightsEnvelope)]
+struct Prompt {
+    trace_id: String,
+    provenance: String,
+}
+
+fn main() {}
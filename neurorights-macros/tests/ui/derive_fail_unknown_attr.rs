@@ -0,0 +1,11 @@
+use neurorights_core::NeurorightsProfile;
+use neurorights_macros::NeurorightsEnvelope;
+
+#[derive(NeurorightsEnvelope)]
+struct Prompt {
+    #[neurorights(anchor)]
+    neurorights_profile: NeurorightsProfile,
+    provenance: String,
+}
+
+fn main() {}
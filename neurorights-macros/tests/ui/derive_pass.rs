@@ -0,0 +1,66 @@
+use neurorights_core::{
+    ConsentLedger, HasNeurorightsProfile, HasTraceId, NeurorightsBound, NeurorightsProfile, NeurorightsViolation,
+};
+use neurorights_macros::NeurorightsEnvelope;
+
+#[derive(NeurorightsEnvelope)]
+struct Prompt {
+    trace_id: String,
+    provenance: Vec<String>,
+    neurorights_profile: NeurorightsProfile,
+}
+
+#[derive(NeurorightsEnvelope)]
+struct Tagged<T> {
+    #[neurorights(profile)]
+    policy: NeurorightsProfile,
+    #[neurorights(provenance)]
+    history: String,
+    args: T,
+}
+
+#[derive(NeurorightsEnvelope)]
+struct Consented {
+    neurorights_profile: NeurorightsProfile,
+    provenance: String,
+    #[neurorights(subject)]
+    identity: String,
+    #[neurorights(purpose)]
+    intent: String,
+}
+
+fn main() {
+    let mut prompt = Prompt {
+        trace_id: "hex:01".into(),
+        provenance: Vec::new(),
+        neurorights_profile: NeurorightsProfile::current(),
+    };
+    assert_eq!(prompt.trace_id(), "hex:01");
+    assert!(prompt.validate().is_ok());
+
+    prompt.neurorights_profile.consent.mental_integrity = false;
+    assert_eq!(
+        prompt.validate(),
+        Err(NeurorightsViolation::ConsentMissing { flag: "mental_integrity" })
+    );
+
+    let tagged = Tagged {
+        policy: NeurorightsProfile::current(),
+        history: String::new(),
+        args: 7u8,
+    };
+    assert_eq!(tagged.neurorights_profile().id, neurorights_core::NEURORIGHTS_POLICY_ID);
+    let bound = NeurorightsBound::try_bind(tagged).unwrap();
+    assert_eq!(bound.payload().args, 7);
+
+    let consented = || Consented {
+        neurorights_profile: NeurorightsProfile::current(),
+        provenance: String::new(),
+        identity: "did:example:a".into(),
+        intent: "summarize".into(),
+    };
+    let ledger = ConsentLedger::new();
+    assert!(NeurorightsBound::try_bind_with_consent(consented(), &ledger).is_err());
+    ledger.grant("did:example:a", "summarize", "2026-01-01T00:00:00Z").unwrap();
+    assert!(NeurorightsBound::try_bind_with_consent(consented(), &ledger).is_ok());
+}
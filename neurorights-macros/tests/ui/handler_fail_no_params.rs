@@ -0,0 +1,8 @@
+use neurorights_macros::neurorights_handler;
+
+#[neurorights_handler]
+fn handler() -> u8 {
+    0
+}
+
+fn main() {}
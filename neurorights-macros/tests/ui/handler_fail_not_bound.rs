@@ -0,0 +1,8 @@
+use neurorights_macros::neurorights_handler;
+
+#[neurorights_handler]
+fn handler(prompt: String) -> usize {
+    prompt.len()
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use neurorights_macros::neurorights_handler;
+
+struct OtherEnvelope;
+
+#[neurorights_handler]
+fn handler(bound: neurorights_core::NeurorightsBound<String, OtherEnvelope>) {}
+
+fn main() {}
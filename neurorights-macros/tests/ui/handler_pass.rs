@@ -0,0 +1,79 @@
+use neurorights_core::{
+    HandlerAuditRecord, HasNeurorightsProfile, HasTraceId, NeurorightsBound, NeurorightsEnvelope,
+    NeurorightsProfile, PolicyMigration,
+};
+use neurorights_macros::neurorights_handler;
+use std::sync::Mutex;
+
+struct Prompt {
+    trace_id: String,
+    profile: NeurorightsProfile,
+}
+
+impl HasTraceId for Prompt {
+    fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+}
+
+impl HasNeurorightsProfile for Prompt {
+    fn neurorights_profile(&self) -> &NeurorightsProfile {
+        &self.profile
+    }
+    fn set_neurorights_profile(&mut self, profile: NeurorightsProfile) {
+        self.profile = profile;
+    }
+    fn record_policy_migration(&mut self, _migration: &PolicyMigration) {}
+}
+
+type Bound = NeurorightsBound<Prompt, NeurorightsEnvelope>;
+
+#[neurorights_handler]
+fn fetch(bound: NeurorightsBound<Prompt, NeurorightsEnvelope>, limit: usize) -> Result<usize, String> {
+    if limit == 0 {
+        return Err("empty".into());
+    }
+    Ok(bound.payload().trace_id.len().min(limit))
+}
+
+#[neurorights_handler]
+async fn plan(_: Bound) -> &'static str {
+    "planned"
+}
+
+struct Router;
+
+impl Router {
+    #[neurorights_handler]
+    fn route(&self, mut bound: Bound) -> String {
+        if bound.payload().trace_id.is_empty() {
+            bound = bind("hex:empty");
+        }
+        bound.into_inner().trace_id
+    }
+}
+
+static SEEN: Mutex<Vec<HandlerAuditRecord>> = Mutex::new(Vec::new());
+
+fn bind(trace_id: &str) -> Bound {
+    NeurorightsBound::try_bind(Prompt {
+        trace_id: trace_id.into(),
+        profile: NeurorightsProfile::current(),
+    })
+    .unwrap()
+}
+
+fn main() {
+    neurorights_core::set_handler_audit_sink(Box::new(|r| SEEN.lock().unwrap().push(r.clone())))
+        .ok()
+        .unwrap();
+
+    assert_eq!(fetch(bind("hex:abc"), 2), Ok(2));
+    assert_eq!(Router.route(bind("hex:def")), "hex:def");
+    let _unpolled = plan(bind("hex:ghi"));
+
+    let seen = SEEN.lock().unwrap();
+    let names: Vec<_> = seen.iter().map(|r| (r.handler, r.trace_id.as_str())).collect();
+    assert_eq!(names, [("fetch", "hex:abc"), ("route", "hex:def")]);
+    assert_eq!(seen[0].policy_version, neurorights_core::NEURORIGHTS_POLICY_VERSION);
+}
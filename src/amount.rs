@@ -0,0 +1,138 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::CHAT_DECIMALS;
+
+/// Smallest-unit count per whole CHAT (`10^CHAT_DECIMALS`).
+const SCALE: u128 = 10u128.pow(CHAT_DECIMALS);
+
+/// Non-negative CHAT amount with the fixed precision declared in
+/// asset.chat.stake.v1 (`decimals`), stored as a count of smallest units.
+///
+/// Parsing is strict: ASCII digits, optionally a `.` followed by one to
+/// `CHAT_DECIMALS` digits. Signs, whitespace, exponents and bare `.5` / `5.`
+/// are rejected. `Display` always prints every decimal place.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChatAmount(u128);
+
+impl ChatAmount {
+    pub const ZERO: ChatAmount = ChatAmount(0);
+    pub const MAX: ChatAmount = ChatAmount(u128::MAX);
+
+    pub const fn from_units(units: u128) -> Self {
+        ChatAmount(units)
+    }
+
+    pub const fn units(self) -> u128 {
+        self.0
+    }
+
+    pub fn checked_add(self, rhs: ChatAmount) -> Option<ChatAmount> {
+        self.0.checked_add(rhs.0).map(ChatAmount)
+    }
+
+    pub fn checked_sub(self, rhs: ChatAmount) -> Option<ChatAmount> {
+        self.0.checked_sub(rhs.0).map(ChatAmount)
+    }
+
+    pub fn saturating_sub(self, rhs: ChatAmount) -> ChatAmount {
+        ChatAmount(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Multiply by a whole-number factor.
+    pub fn checked_mul(self, factor: u128) -> Option<ChatAmount> {
+        self.0.checked_mul(factor).map(ChatAmount)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseChatAmountError {
+    #[error("empty CHAT amount")]
+    Empty,
+
+    #[error("invalid character {found:?} at byte {index} in CHAT amount")]
+    InvalidCharacter { index: usize, found: char },
+
+    #[error("CHAT amount needs digits on both sides of the decimal point")]
+    MissingDigits,
+
+    #[error("CHAT amount has more than {max} decimal places")]
+    TooManyDecimals { max: u32 },
+
+    #[error("CHAT amount out of range")]
+    Overflow,
+}
+
+impl FromStr for ChatAmount {
+    type Err = ParseChatAmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseChatAmountError::Empty);
+        }
+        if let Some((index, found)) = s.char_indices().find(|(_, c)| !c.is_ascii_digit() && *c != '.') {
+            return Err(ParseChatAmountError::InvalidCharacter { index, found });
+        }
+
+        let (whole, frac) = match s.split_once('.') {
+            Some((whole, frac)) => (whole, Some(frac)),
+            None => (s, None),
+        };
+        if let Some(index) = frac.and_then(|f| f.find('.')) {
+            return Err(ParseChatAmountError::InvalidCharacter { index: whole.len() + 1 + index, found: '.' });
+        }
+        if whole.is_empty() || frac.is_some_and(str::is_empty) {
+            return Err(ParseChatAmountError::MissingDigits);
+        }
+        let frac = frac.unwrap_or("");
+        if frac.len() > CHAT_DECIMALS as usize {
+            return Err(ParseChatAmountError::TooManyDecimals { max: CHAT_DECIMALS });
+        }
+
+        let mut units: u128 = 0;
+        for b in whole.bytes() {
+            units = units
+                .checked_mul(10)
+                .and_then(|u| u.checked_add(u128::from(b - b'0')))
+                .ok_or(ParseChatAmountError::Overflow)?;
+        }
+        units = units.checked_mul(SCALE).ok_or(ParseChatAmountError::Overflow)?;
+
+        let mut frac_units: u128 = 0;
+        for b in frac.bytes() {
+            frac_units = frac_units * 10 + u128::from(b - b'0');
+        }
+        frac_units *= 10u128.pow(CHAT_DECIMALS - frac.len() as u32);
+
+        units
+            .checked_add(frac_units)
+            .map(ChatAmount)
+            .ok_or(ParseChatAmountError::Overflow)
+    }
+}
+
+impl fmt::Display for ChatAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{:0width$}",
+            self.0 / SCALE,
+            self.0 % SCALE,
+            width = CHAT_DECIMALS as usize
+        )
+    }
+}
+
+impl Serialize for ChatAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ChatAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
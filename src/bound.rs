@@ -1,4 +1,6 @@
-use crate::NeurorightsMarker;
+use crate::{
+    ConsentLedger, HasConsentSubject, HasNeurorightsProfile, NeurorightsEnvelope, NeurorightsMarker, NeurorightsViolation, PolicyRegistry,
+};
 
 /// Generic gate that binds a payload `P` to a neurorights envelope `N`.
 #[derive(Clone, Debug)]
@@ -7,7 +9,45 @@ pub struct NeurorightsBound<P, N: NeurorightsMarker> {
     envelope: N,
 }
 
+impl<P: HasNeurorightsProfile> NeurorightsBound<P, NeurorightsEnvelope> {
+    /// Bind `payload` to the compiled envelope after checking its profile
+    /// id/version, anchor and consent. The only checked way to construct
+    /// a `NeurorightsBound` outside this crate.
+    ///
+    /// Older policy versions are negotiated through `PolicyRegistry`; an
+    /// upgrade is written back to the payload and recorded in its provenance.
+    pub fn try_bind(mut payload: P) -> Result<Self, NeurorightsViolation> {
+        let mut profile = payload.neurorights_profile().clone();
+        if let Some(migration) = PolicyRegistry::compiled().negotiate(&mut profile)? {
+            payload.set_neurorights_profile(profile);
+            payload.record_policy_migration(&migration);
+        }
+
+        let envelope = NeurorightsEnvelope::compiled();
+        envelope.check_profile(payload.neurorights_profile())?;
+        Ok(Self { payload, envelope })
+    }
+}
+
+impl<P: HasNeurorightsProfile + HasConsentSubject> NeurorightsBound<P, NeurorightsEnvelope> {
+    /// `try_bind`, after checking the ledger holds an unrevoked grant for
+    /// the payload's DID and purpose.
+    pub fn try_bind_with_consent(payload: P, ledger: &ConsentLedger) -> Result<Self, NeurorightsViolation> {
+        ledger.check(payload.subject_did(), payload.processing_purpose())?;
+        Self::try_bind(payload)
+    }
+
+    /// Re-check the ledger for a value bound earlier, so long-lived work
+    /// stops as soon as consent is revoked.
+    pub fn check_consent(&self, ledger: &ConsentLedger) -> Result<(), NeurorightsViolation> {
+        ledger.check(self.payload.subject_did(), self.payload.processing_purpose())
+    }
+}
+
 impl<P, N: NeurorightsMarker> NeurorightsBound<P, N> {
+    /// Unchecked construction; kept public for one release so downstream
+    /// crates can migrate, then it becomes crate-private.
+    #[deprecated(since = "0.1.0", note = "use `NeurorightsBound::try_bind`, which validates the profile")]
     pub fn new(payload: P, envelope: N) -> Self {
         Self { payload, envelope }
     }
@@ -24,3 +64,100 @@ impl<P, N: NeurorightsMarker> NeurorightsBound<P, N> {
         &self.envelope
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NeurorightsProfile;
+
+    #[derive(Debug)]
+    struct Payload(NeurorightsProfile, Vec<String>);
+
+    impl HasNeurorightsProfile for Payload {
+        fn neurorights_profile(&self) -> &NeurorightsProfile {
+            &self.0
+        }
+        fn set_neurorights_profile(&mut self, profile: NeurorightsProfile) {
+            self.0 = profile;
+        }
+        fn record_policy_migration(&mut self, migration: &crate::PolicyMigration) {
+            self.1.push(migration.to_string());
+        }
+    }
+
+    impl HasConsentSubject for Payload {
+        fn subject_did(&self) -> &str {
+            "did:example:citizen"
+        }
+        fn processing_purpose(&self) -> &str {
+            "retrieval"
+        }
+    }
+
+    fn stamped(version: &str) -> Payload {
+        let mut profile = NeurorightsProfile::current();
+        profile.anchor = format!("did:aln:{}:{}", profile.id, version);
+        profile.version = version.into();
+        Payload(profile, Vec::new())
+    }
+
+    #[test]
+    fn try_bind_checks_profile_and_consent() {
+        assert!(NeurorightsBound::try_bind(stamped(crate::NEURORIGHTS_POLICY_VERSION)).is_ok());
+
+        assert_eq!(
+            NeurorightsBound::try_bind(stamped("0.9")).unwrap_err().code(),
+            "neurorights_profile_version_mismatch"
+        );
+
+        let mut withheld = stamped(crate::NEURORIGHTS_POLICY_VERSION);
+        withheld.0.consent.mental_privacy = false;
+        assert_eq!(
+            NeurorightsBound::try_bind(withheld).unwrap_err(),
+            NeurorightsViolation::ConsentMissing { flag: "mental_privacy" }
+        );
+    }
+
+    #[test]
+    fn older_versions_follow_their_compatibility_rule() {
+        let registry = PolicyRegistry::compiled();
+        for entry in crate::POLICY_COMPATIBILITY {
+            let result = NeurorightsBound::try_bind(stamped(entry.version));
+            match entry.compatibility {
+                crate::Compatibility::Reject => assert_eq!(
+                    result.unwrap_err(),
+                    NeurorightsViolation::PolicyVersionRejected { found: entry.version.into() }
+                ),
+                crate::Compatibility::AcceptWithUpgrade => {
+                    let bound = result.unwrap();
+                    assert_eq!(bound.payload().0.version, registry.current_version());
+                    assert_eq!(bound.payload().0.anchor, crate::NEURORIGHTS_POLICY_ANCHOR);
+                    assert_eq!(bound.payload().1.len(), 1);
+                }
+                crate::Compatibility::Accept => {
+                    assert!(result.unwrap().payload().1.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn revoked_consent_blocks_binding_immediately() {
+        let ledger = ConsentLedger::new();
+        let current = || stamped(crate::NEURORIGHTS_POLICY_VERSION);
+        assert_eq!(
+            NeurorightsBound::try_bind_with_consent(current(), &ledger).unwrap_err().code(),
+            "neurorights_consent_not_granted"
+        );
+
+        ledger.grant("did:example:citizen", "retrieval", "2026-01-01T00:00:00Z").unwrap();
+        let bound = NeurorightsBound::try_bind_with_consent(current(), &ledger).unwrap();
+
+        ledger.revoke("did:example:citizen", "retrieval", "2026-01-02T00:00:00Z").unwrap();
+        assert_eq!(bound.check_consent(&ledger).unwrap_err().code(), "neurorights_consent_revoked");
+        assert!(NeurorightsBound::try_bind_with_consent(current(), &ledger).is_err());
+
+        assert!(ledger.grant("did:example:citizen", "retrieval", "2025-12-31T00:00:00Z").is_err());
+        assert_eq!(ledger.history().len(), 2);
+    }
+}
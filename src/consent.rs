@@ -0,0 +1,267 @@
+use crate::NeurorightsViolation;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::RwLock;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// Payloads that name the citizen and the processing purpose consent is checked for.
+pub trait HasConsentSubject {
+    fn subject_did(&self) -> &str;
+    fn processing_purpose(&self) -> &str;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsentAction {
+    Grant,
+    Revoke,
+}
+
+/// One append-only ledger entry.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsentEvent {
+    pub seq: u64,
+    pub did: String,
+    pub purpose: String,
+    pub action: ConsentAction,
+    /// RFC 3339; strictly later than the previous event for the same (did, purpose).
+    pub at_utc: String,
+}
+
+/// Current consent for one (did, purpose), folded from the history.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsentRecord {
+    pub did: String,
+    pub purpose: String,
+    pub granted_at_utc: Option<String>,
+    pub revoked_at_utc: Option<String>,
+    /// `seq` and action of the latest event, which alone decides the state.
+    pub last_seq: u64,
+    pub last_action: ConsentAction,
+}
+
+impl ConsentRecord {
+    /// The latest event for this (did, purpose) is a grant.
+    pub fn is_granted(&self) -> bool {
+        self.last_action == ConsentAction::Grant
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConsentLedgerError {
+    EmptyField { field: &'static str },
+    /// `at_utc` is not an RFC 3339 timestamp.
+    InvalidTimestamp { at_utc: String },
+    /// `at_utc` is not strictly later than the last event for the same (did, purpose).
+    OutOfOrder { did: String, purpose: String, last_utc: String, at_utc: String },
+    /// A replayed history skips or repeats a `seq`.
+    SeqGap { expected: u64, found: u64 },
+}
+
+impl fmt::Display for ConsentLedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsentLedgerError::EmptyField { field } => write!(f, "consent event {} is empty", field),
+            ConsentLedgerError::InvalidTimestamp { at_utc } => {
+                write!(f, "consent event time {:?} is not RFC 3339", at_utc)
+            }
+            ConsentLedgerError::OutOfOrder { did, purpose, last_utc, at_utc } => write!(
+                f,
+                "consent event for {} / {} at {} is not after the last one at {}",
+                did, purpose, at_utc, last_utc
+            ),
+            ConsentLedgerError::SeqGap { expected, found } => {
+                write!(f, "consent history has seq {} where {} was expected", found, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConsentLedgerError {}
+
+#[derive(Default)]
+struct LedgerState {
+    history: Vec<ConsentEvent>,
+    /// Folded record plus the parsed time of its latest event.
+    current: BTreeMap<(String, String), (ConsentRecord, OffsetDateTime)>,
+}
+
+/// Per-DID, per-purpose consent with an append-only history.
+///
+/// Shared by reference (typically `Arc<ConsentLedger>`) between the code
+/// that records grants/revocations and the binding path, so a revocation is
+/// seen by the very next `try_bind_with_consent`.
+#[derive(Default)]
+pub struct ConsentLedger {
+    state: RwLock<LedgerState>,
+}
+
+impl ConsentLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild a ledger by replaying a persisted history, which must be
+    /// complete and in `seq` order.
+    pub fn from_history(events: impl IntoIterator<Item = ConsentEvent>) -> Result<Self, ConsentLedgerError> {
+        let ledger = Self::new();
+        for (expected, event) in (0u64..).zip(events) {
+            if event.seq != expected {
+                return Err(ConsentLedgerError::SeqGap { expected, found: event.seq });
+            }
+            ledger.append(&event.did, &event.purpose, event.action, &event.at_utc)?;
+        }
+        Ok(ledger)
+    }
+
+    pub fn grant(&self, did: &str, purpose: &str, at_utc: &str) -> Result<ConsentEvent, ConsentLedgerError> {
+        self.append(did, purpose, ConsentAction::Grant, at_utc)
+    }
+
+    pub fn revoke(&self, did: &str, purpose: &str, at_utc: &str) -> Result<ConsentEvent, ConsentLedgerError> {
+        self.append(did, purpose, ConsentAction::Revoke, at_utc)
+    }
+
+    pub fn record(&self, did: &str, purpose: &str) -> Option<ConsentRecord> {
+        self.read()
+            .current
+            .get(&(did.to_string(), purpose.to_string()))
+            .map(|(record, _)| record.clone())
+    }
+
+    pub fn is_granted(&self, did: &str, purpose: &str) -> bool {
+        self.record(did, purpose).is_some_and(|r| r.is_granted())
+    }
+
+    /// `Ok` only while consent is granted; the binding path's check.
+    pub fn check(&self, did: &str, purpose: &str) -> Result<(), NeurorightsViolation> {
+        match self.record(did, purpose) {
+            Some(record) if record.is_granted() => Ok(()),
+            Some(ConsentRecord { last_action: ConsentAction::Revoke, revoked_at_utc: Some(revoked_at_utc), .. }) => {
+                Err(NeurorightsViolation::ConsentRevoked {
+                    did: did.to_string(),
+                    purpose: purpose.to_string(),
+                    revoked_at_utc,
+                })
+            }
+            _ => Err(NeurorightsViolation::ConsentNotGranted {
+                did: did.to_string(),
+                purpose: purpose.to_string(),
+            }),
+        }
+    }
+
+    /// Every event in append order.
+    pub fn history(&self) -> Vec<ConsentEvent> {
+        self.read().history.clone()
+    }
+
+    fn append(
+        &self,
+        did: &str,
+        purpose: &str,
+        action: ConsentAction,
+        at_utc: &str,
+    ) -> Result<ConsentEvent, ConsentLedgerError> {
+        for (field, value) in [("did", did), ("purpose", purpose), ("at_utc", at_utc)] {
+            if value.trim().is_empty() {
+                return Err(ConsentLedgerError::EmptyField { field });
+            }
+        }
+
+        let at = OffsetDateTime::parse(at_utc, &Rfc3339)
+            .map_err(|_| ConsentLedgerError::InvalidTimestamp { at_utc: at_utc.to_string() })?;
+
+        let mut state = self.state.write().unwrap_or_else(|p| p.into_inner());
+        let seq = state.history.len() as u64;
+        let key = (did.to_string(), purpose.to_string());
+        if let Some((record, last)) = state.current.get(&key).filter(|(_, last)| at <= *last) {
+            let last_utc = match record.last_action {
+                ConsentAction::Grant => record.granted_at_utc.clone(),
+                ConsentAction::Revoke => record.revoked_at_utc.clone(),
+            };
+            return Err(ConsentLedgerError::OutOfOrder {
+                did: did.to_string(),
+                purpose: purpose.to_string(),
+                last_utc: last_utc.unwrap_or_else(|| last.to_string()),
+                at_utc: at_utc.to_string(),
+            });
+        }
+
+        let (record, last) = state.current.entry(key).or_insert_with(|| {
+            let record = ConsentRecord {
+                did: did.to_string(),
+                purpose: purpose.to_string(),
+                granted_at_utc: None,
+                revoked_at_utc: None,
+                last_seq: seq,
+                last_action: action,
+            };
+            (record, at)
+        });
+        match action {
+            ConsentAction::Grant => record.granted_at_utc = Some(at_utc.to_string()),
+            ConsentAction::Revoke => record.revoked_at_utc = Some(at_utc.to_string()),
+        }
+        record.last_seq = seq;
+        record.last_action = action;
+        *last = at;
+
+        let event = ConsentEvent {
+            seq,
+            did: did.to_string(),
+            purpose: purpose.to_string(),
+            action,
+            at_utc: at_utc.to_string(),
+        };
+        state.history.push(event.clone());
+        Ok(event)
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, LedgerState> {
+        self.state.read().unwrap_or_else(|p| p.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_event_decides_and_times_must_strictly_increase() {
+        let ledger = ConsentLedger::new();
+        ledger.grant("did:a", "retrieval", "2026-01-01T00:00:00Z").unwrap();
+        ledger.revoke("did:a", "retrieval", "2026-01-02T00:00:00Z").unwrap();
+
+        // Same instant as the revocation: rejected, so state stays revoked.
+        for at in ["2026-01-02T00:00:00Z", "2026-01-02T01:00:00+01:00", "2026-01-02T00:00:00.000Z"] {
+            assert!(matches!(
+                ledger.grant("did:a", "retrieval", at),
+                Err(ConsentLedgerError::OutOfOrder { .. })
+            ));
+        }
+        assert!(!ledger.is_granted("did:a", "retrieval"));
+
+        // Later instant whose string sorts before the revocation.
+        ledger.grant("did:a", "retrieval", "2026-01-01T20:00:00.5-05:00").unwrap();
+        let record = ledger.record("did:a", "retrieval").unwrap();
+        assert!(record.is_granted());
+        assert_eq!(record.last_seq, 2);
+
+        assert!(matches!(
+            ledger.grant("did:a", "retrieval", "yesterday"),
+            Err(ConsentLedgerError::InvalidTimestamp { .. })
+        ));
+
+        let replayed = ConsentLedger::from_history(ledger.history()).unwrap();
+        assert!(replayed.is_granted("did:a", "retrieval"));
+        let mut gapped = ledger.history();
+        gapped.remove(1);
+        assert_eq!(
+            ConsentLedger::from_history(gapped).err(),
+            Some(ConsentLedgerError::SeqGap { expected: 1, found: 2 })
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use crate::NeurorightsMarkerSealed;
+use crate::sealed::NeurorightsMarkerSealed;
+use crate::{Compatibility, NeurorightsProfile, NeurorightsViolation, PolicyRegistry};
 
 #[derive(Clone, Debug)]
 pub struct NeurorightsEnvelope {
@@ -6,9 +7,12 @@ pub struct NeurorightsEnvelope {
     pub policy_version: &'static str,
     pub policy_anchor: &'static str,
 
-    // Invariants mirrored from ALN shard.
+    // Invariants compiled from neurorights.toml and its ALN shard.
+    pub roh_ceiling: f64,
     pub max_inner_state_score: u32,
     pub allow_neurocoercion: bool,
+    pub allow_inner_state_scoring: bool,
+    pub consent_revocable_at_will: bool,
 }
 
 impl NeurorightsEnvelope {
@@ -17,8 +21,41 @@ impl NeurorightsEnvelope {
             policy_id: crate::NEURORIGHTS_POLICY_ID,
             policy_version: crate::NEURORIGHTS_POLICY_VERSION,
             policy_anchor: crate::NEURORIGHTS_POLICY_ANCHOR,
+            roh_ceiling: crate::ROH_CEILING,
             max_inner_state_score: crate::MAX_INNER_STATE_SCORE,
             allow_neurocoercion: crate::ALLOW_NEUROCOERCION,
+            allow_inner_state_scoring: crate::ALLOW_INNER_STATE_SCORING,
+            consent_revocable_at_will: crate::CONSENT_REVOCABLE_AT_WILL,
+        }
+    }
+
+    /// Check that `profile` names this envelope's policy (or a version the
+    /// registry accepts unchanged) and grants every required consent flag.
+    pub fn check_profile(&self, profile: &NeurorightsProfile) -> Result<(), NeurorightsViolation> {
+        if profile.id != self.policy_id {
+            return Err(NeurorightsViolation::ProfileIdMismatch {
+                expected: self.policy_id,
+                found: profile.id.clone(),
+            });
+        }
+        let accepted = profile.version == self.policy_version
+            || PolicyRegistry::compiled().compatibility(&profile.version) == Some(Compatibility::Accept);
+        if !accepted {
+            return Err(NeurorightsViolation::ProfileVersionMismatch {
+                expected: self.policy_version,
+                found: profile.version.clone(),
+            });
+        }
+        let own_anchor = format!("did:aln:{}:{}", profile.id, profile.version);
+        if profile.anchor != self.policy_anchor && profile.anchor != own_anchor {
+            return Err(NeurorightsViolation::AnchorMismatch {
+                expected: self.policy_anchor,
+                found: profile.anchor.clone(),
+            });
+        }
+        match profile.consent.first_missing() {
+            Some(flag) => Err(NeurorightsViolation::ConsentMissing { flag }),
+            None => Ok(()),
         }
     }
 }
@@ -0,0 +1,64 @@
+use crate::{NeurorightsBound, NeurorightsEnvelope};
+use std::sync::OnceLock;
+
+/// Payloads that carry a trace id for audit correlation.
+pub trait HasTraceId {
+    fn trace_id(&self) -> &str;
+}
+
+/// Inputs a `#[neurorights_handler]` accepts as its first parameter.
+///
+/// Only implemented for `NeurorightsBound<P, NeurorightsEnvelope>`, so the
+/// generated `record_handler_call` rejects every other type at compile time.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid #[neurorights_handler] input",
+    label = "first parameter must be `NeurorightsBound<_, NeurorightsEnvelope>`",
+    note = "the bound payload must also implement `HasTraceId`"
+)]
+pub trait NeurorightsHandlerInput {
+    fn audit_trace_id(&self) -> &str;
+    fn audit_policy_version(&self) -> &'static str;
+}
+
+impl<P: HasTraceId> NeurorightsHandlerInput for NeurorightsBound<P, NeurorightsEnvelope> {
+    fn audit_trace_id(&self) -> &str {
+        self.payload().trace_id()
+    }
+
+    fn audit_policy_version(&self) -> &'static str {
+        self.neurorights_envelope().policy_version
+    }
+}
+
+/// One handler invocation, as seen by the audit sink.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HandlerAuditRecord {
+    pub handler: &'static str,
+    pub trace_id: String,
+    pub policy_id: &'static str,
+    pub policy_version: &'static str,
+}
+
+pub type HandlerAuditSink = Box<dyn Fn(&HandlerAuditRecord) + Send + Sync>;
+
+static SINK: OnceLock<HandlerAuditSink> = OnceLock::new();
+
+/// Install the process-wide sink for handler audit records. Only the first
+/// call wins; later calls get their sink back. Without a sink, records are
+/// dropped.
+pub fn set_handler_audit_sink(sink: HandlerAuditSink) -> Result<(), HandlerAuditSink> {
+    SINK.set(sink)
+}
+
+/// Called by `#[neurorights_handler]` before the handler body runs.
+pub fn record_handler_call<I: NeurorightsHandlerInput>(handler: &'static str, input: &I) {
+    let Some(sink) = SINK.get() else {
+        return;
+    };
+    sink(&HandlerAuditRecord {
+        handler,
+        trace_id: input.audit_trace_id().to_string(),
+        policy_id: crate::NEURORIGHTS_POLICY_ID,
+        policy_version: input.audit_policy_version(),
+    });
+}
@@ -1,27 +1,54 @@
 use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
 
-use neurorights_core::{NeurorightsBound, NeurorightsEnvelope};
-use neurorights_firewall::PromptEnvelope;
+use neurorights_core::{NeurorightsBound, NeurorightsEnvelope, NeurorightsProfile};
 
+use crate::amount::ChatAmount;
 use crate::roles::{role_for_stake, GovernanceRole, Identity};
-use crate::risk::{RiskEnvelope, RiskError};
-use crate::governance_constraints;
+use crate::risk::{RiskEnvelope, RiskError, RiskFactors, RiskScope};
+use crate::site_policy::{content_hash, SitePolicyStore};
+use crate::voting::ActionClass;
+use crate::{governance_constraints, permissions, risk_weights};
+
+/// The prompt a website governance action arrives in: the firewall's
+/// `PromptEnvelope` fields with typed args.
+#[derive(Clone, Debug, neurorights_macros::NeurorightsEnvelope)]
+pub struct WebsiteGovPrompt {
+    pub trace_id: String,
+    #[neurorights(purpose)]
+    pub intent: String,
+    pub args: WebsiteGovArgs,
+    pub security_level: String,
+    #[neurorights(subject)]
+    pub identity: String,
+    pub provenance: String,
+    pub governance: String,
+    pub neurorights_profile: NeurorightsProfile,
+}
 
 /// A normalized, neurorights-bound envelope for website governance actions.
-pub type WebsiteGovEnvelope =
-    NeurorightsBound<PromptEnvelope<WebsiteGovArgs>, NeurorightsEnvelope>;
+pub type WebsiteGovEnvelope = NeurorightsBound<WebsiteGovPrompt, NeurorightsEnvelope>;
+
+/// Content address of a bound envelope: `sha256:` over its trace id and
+/// canonical args, in the same form as `site_policy::content_hash`.
+pub fn envelope_hash(env: &WebsiteGovEnvelope) -> String {
+    let payload = env.payload();
+    let args = serde_json::to_value(&payload.args).unwrap_or_default();
+    content_hash(&serde_json::json!({ "trace_id": payload.trace_id, "args": args }))
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebsiteGovArgs {
     pub identity: Identity,
-    pub chat_stake_decimal: String,
+    /// Decimal string on the wire, parsed strictly (see `ChatAmount`).
+    pub chat_stake_decimal: ChatAmount,
     pub action: WebsiteAction,
     pub page_id: String,
     pub section_id: Option<String>,
     pub eibonlabel: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WebsiteAction {
     ProposePage,
     ReviewPage,
@@ -33,17 +60,70 @@ pub enum WebsiteAction {
     VetoChange,
 }
 
+impl WebsiteAction {
+    /// The ALN permission string this action requires.
+    pub fn permission(&self) -> &'static str {
+        match self {
+            WebsiteAction::ProposePage => permissions::WEBSITE_PAGE_PROPOSE,
+            WebsiteAction::ReviewPage => permissions::WEBSITE_PAGE_REVIEW,
+            WebsiteAction::PublishPage => permissions::WEBSITE_PAGE_PUBLISH,
+            WebsiteAction::ReviewSection => permissions::WEBSITE_SECTION_REVIEW,
+            WebsiteAction::PublishSection => permissions::WEBSITE_SECTION_PUBLISH,
+            WebsiteAction::UpdateSitePolicy => permissions::WEBSITE_SITEWIDE_POLICY_UPDATE,
+            WebsiteAction::RollbackSitePolicy => permissions::WEBSITE_SITEWIDE_ROLLBACK,
+            WebsiteAction::VetoChange => permissions::WEBSITE_GOVERNANCE_VETO,
+        }
+    }
+
+    /// The council vote this action must be backed by, if any.
+    pub fn council_class(&self) -> Option<ActionClass> {
+        match self {
+            WebsiteAction::PublishPage => Some(ActionClass::PagePublish),
+            WebsiteAction::PublishSection => Some(ActionClass::SectionPublish),
+            WebsiteAction::UpdateSitePolicy => Some(ActionClass::SitewidePolicyUpdate),
+            _ => None,
+        }
+    }
+}
+
 /// Entry point for neurorights-bound website governance.
 /// This is designed to be called only from routers that already required
-/// NeurorightsBound<PromptEnvelope<WebsiteGovArgs>, NeurorightsEnvelope>.
+/// NeurorightsBound<WebsiteGovPrompt, NeurorightsEnvelope>.
+///
+/// Risk-of-harm is estimated from the action, its scope, the actor's role
+/// and the rollbacks `site_policy` recorded within the ALN churn window. The
+/// caller's `risk_envelope.risk_of_harm` only acts as a floor; the decision
+/// records both values and carries the effective one in its envelope.
 pub fn handle_website_governance(
     env: WebsiteGovEnvelope,
     risk_envelope: RiskEnvelope,
+    site_policy: &SitePolicyStore,
 ) -> Result<GovernanceDecision, GovernanceError> {
     if !governance_constraints::NEURORIGHTS_REQUIRED {
         return Err(GovernanceError::NeurorightsConstraintMissing);
     }
 
+    let args = env.payload().args.clone(); // WebsiteGovPrompt.args
+    let role = role_for_stake(args.chat_stake_decimal)
+        .ok_or(GovernanceError::InsufficientStake)?;
+
+    enforce_permissions(role, &args.action)?;
+
+    let window = Duration::seconds(risk_weights::ROLLBACK_CHURN_WINDOW_SECS as i64);
+    let recent_rollbacks = site_policy.rollbacks_since(OffsetDateTime::now_utc() - window);
+    let estimated_risk_of_harm = RiskFactors {
+        action: args.action,
+        scope: RiskScope::for_action(args.action, args.section_id.as_deref()),
+        role,
+        recent_rollbacks,
+    }
+    .estimate_risk_of_harm();
+    let caller_risk_of_harm = risk_envelope.risk_of_harm;
+    let risk_envelope = RiskEnvelope {
+        risk_of_harm: estimated_risk_of_harm.max(caller_risk_of_harm),
+        ..risk_envelope
+    };
+
     risk_envelope.validate()?;
 
     if risk_envelope.risk_of_harm > governance_constraints::RISK_OF_HARM_CEILING {
@@ -53,57 +133,65 @@ pub fn handle_website_governance(
         });
     }
 
-    let args = env.inner().args.clone(); // PromptEnvelope<WebsiteGovArgs>.args
-    let role = role_for_stake(&args.chat_stake_decimal)
-        .ok_or(GovernanceError::InsufficientStake)?;
-
-    enforce_permissions(role, &args.action)?;
-
     Ok(GovernanceDecision {
-        allowed: true,
-        role,
-        page_id: args.page_id,
-        section_id: args.section_id,
-        eibonlabel: args.eibonlabel,
-        risk_envelope,
+        record: GovernanceDecisionRecord {
+            allowed: true,
+            role,
+            action: args.action,
+            page_id: args.page_id,
+            section_id: args.section_id,
+            eibonlabel: args.eibonlabel,
+            estimated_risk_of_harm,
+            caller_risk_of_harm,
+            risk_envelope,
+        },
     })
 }
 
-fn enforce_permissions(role: GovernanceRole, action: &WebsiteAction) -> Result<(), GovernanceError> {
-    use GovernanceRole::*;
-    use WebsiteAction::*;
-
-    let allowed = match (role, action) {
-        (Stakeholder, ProposePage) => true,
-        (Stakeholder, _) => false,
-
-        (Council, ProposePage | ReviewPage | ReviewSection) => true,
-        (Council, _) => false,
-
-        (Superchair, ProposePage
-            | ReviewPage
-            | PublishPage
-            | ReviewSection
-            | PublishSection
-            | UpdateSitePolicy
-            | RollbackSitePolicy
-            | VetoChange) => true,
-    };
-
-    if allowed {
+/// Checks `action` against the permission matrix generated from
+/// governance.chat.website.v1.aln.
+pub(crate) fn enforce_permissions(role: GovernanceRole, action: &WebsiteAction) -> Result<(), GovernanceError> {
+    if role.has_permission(action.permission()) {
         Ok(())
     } else {
-        Err(GovernanceError::PermissionDenied { role, action: format!("{:?}", action) })
+        Err(GovernanceError::PermissionDenied { role, action: action.permission().to_string() })
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A decision issued by `handle_website_governance`. It has no public
+/// constructor, is neither `Clone` nor `Deserialize`, and is consumed by the
+/// store it authorizes, so holding one proves the handler's checks ran once.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
 pub struct GovernanceDecision {
+    record: GovernanceDecisionRecord,
+}
+
+impl GovernanceDecision {
+    pub fn record(&self) -> &GovernanceDecisionRecord {
+        &self.record
+    }
+
+    pub fn into_record(self) -> GovernanceDecisionRecord {
+        self.record
+    }
+}
+
+/// What a `GovernanceDecision` allowed, as kept in logs and stores. Plain
+/// data: it authorizes nothing by itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceDecisionRecord {
     pub allowed: bool,
     pub role: GovernanceRole,
+    pub action: WebsiteAction,
     pub page_id: String,
     pub section_id: Option<String>,
     pub eibonlabel: String,
+    /// Handler's estimate for this action, scope, role and rollback history.
+    pub estimated_risk_of_harm: f64,
+    /// The caller-supplied value, applied as a floor.
+    pub caller_risk_of_harm: f64,
+    /// Envelope with the effective (larger) risk-of-harm.
     pub risk_envelope: RiskEnvelope,
 }
 
@@ -124,3 +212,103 @@ pub enum GovernanceError {
     #[error(transparent)]
     Risk(#[from] RiskError),
 }
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::*;
+    use crate::stake_thresholds;
+    use crate::voting::{Ballot, DecisionRecord, VoteChoice};
+
+    /// A caller's DID and stake, as they end up in the envelope args.
+    pub(crate) struct Actor {
+        pub did: String,
+        pub chat_stake: ChatAmount,
+    }
+
+    /// `actor` asking for `action` on `page_id`, bound under the current profile.
+    pub(crate) fn bound(actor: &Actor, action: WebsiteAction, page_id: &str) -> WebsiteGovEnvelope {
+        bound_section(actor, action, page_id, None)
+    }
+
+    /// As [`bound`], for one section of the page.
+    pub(crate) fn bound_section(
+        actor: &Actor,
+        action: WebsiteAction,
+        page_id: &str,
+        section_id: Option<&str>,
+    ) -> WebsiteGovEnvelope {
+        let args = WebsiteGovArgs {
+            identity: Identity {
+                userdid: actor.did.clone(),
+                aln: "aln:test".into(),
+                bostromaddress: "bostrom1test".into(),
+            },
+            chat_stake_decimal: actor.chat_stake,
+            action,
+            page_id: page_id.to_string(),
+            section_id: section_id.map(str::to_string),
+            eibonlabel: "eibon:test".into(),
+        };
+        NeurorightsBound::try_bind(WebsiteGovPrompt {
+            trace_id: format!("{}:{:?}:{}", actor.did, action, page_id),
+            intent: "website_governance".into(),
+            args,
+            security_level: "standard".into(),
+            identity: actor.did.clone(),
+            provenance: String::new(),
+            governance: String::new(),
+            neurorights_profile: NeurorightsProfile::current(),
+        })
+        .unwrap()
+    }
+
+    /// A four-seat council vote on `subject_id` with `approvals` approvals
+    /// and the rest rejections, decided after its veto window.
+    pub(crate) fn council_vote(class: ActionClass, subject_id: &str, approvals: u32) -> DecisionRecord {
+        let mut ballot = Ballot::open(class, subject_id, 4);
+        for i in 0..4 {
+            let member = Actor { did: format!("did:example:c{i}"), chat_stake: stake_thresholds::COUNCIL_MIN };
+            let choice = if i < approvals { VoteChoice::Approve } else { VoteChoice::Reject };
+            ballot.cast(&bound(&member, class.vote_action(), "site"), choice, 1).unwrap();
+        }
+        ballot.close(2).unwrap();
+        ballot.decide(2 + class.quorum().veto_window_secs).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use time::format_description::well_known::Rfc3339;
+
+    use super::testing::{bound, council_vote, Actor};
+    use super::*;
+    use crate::stake_thresholds;
+
+    #[test]
+    fn recent_rollbacks_in_the_store_raise_risk_but_never_block_rollback() {
+        let chair = Actor { did: "did:example:chair".into(), chat_stake: stake_thresholds::SUPERCHAIR_MIN };
+        let decide = |action, store: &SitePolicyStore| {
+            handle_website_governance(bound(&chair, action, "site"), RiskEnvelope::default("0x1a2f"), store)
+        };
+        let now = || OffsetDateTime::now_utc().format(&Rfc3339).unwrap();
+
+        let mut store = SitePolicyStore::open(None).unwrap();
+        for version in ["v1", "v2"] {
+            let decision = decide(WebsiteAction::UpdateSitePolicy, &store).unwrap();
+            let council = council_vote(ActionClass::SitewidePolicyUpdate, version, 3);
+            store.update(version, json!({ "v": version }), decision, council, &now()).unwrap();
+        }
+        let calm = decide(WebsiteAction::UpdateSitePolicy, &store).unwrap().record().estimated_risk_of_harm;
+
+        for to in ["v1", "v2", "v1", "v2"] {
+            let decision = decide(WebsiteAction::RollbackSitePolicy, &store).unwrap();
+            store.rollback(to, decision, &now()).unwrap();
+        }
+        assert!(matches!(
+            decide(WebsiteAction::UpdateSitePolicy, &store),
+            Err(GovernanceError::Risk(RiskError::RiskOfHarmExceeded { roh, .. })) if roh > calm
+        ));
+        assert!(decide(WebsiteAction::RollbackSitePolicy, &store).is_ok());
+    }
+}
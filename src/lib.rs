@@ -1,17 +1,39 @@
 #![forbid(unsafe_code)]
 
+mod consent;
 mod envelope;
+mod handler_audit;
 mod profile;
+mod registry;
 mod bound;
 mod sealed;
 mod version;
+mod violation;
 
+pub use consent::{
+    ConsentAction, ConsentEvent, ConsentLedger, ConsentLedgerError, ConsentRecord,
+    HasConsentSubject,
+};
 pub use envelope::NeurorightsEnvelope;
-pub use profile::NeurorightsProfile;
+pub use handler_audit::{
+    record_handler_call, set_handler_audit_sink, HandlerAuditRecord,
+    HandlerAuditSink, HasTraceId, NeurorightsHandlerInput,
+};
+pub use profile::{HasNeurorightsProfile, NeurorightsConsent, NeurorightsProfile, ProvenanceLog};
+pub use registry::{Compatibility, PolicyMigration, PolicyRegistry, PolicyVersion};
 pub use bound::NeurorightsBound;
-pub use sealed::NeurorightsMarkerSealed;
+pub use sealed::NeurorightsMarker;
+pub use violation::NeurorightsViolation;
 pub use version::{
+    ALLOW_INNER_STATE_SCORING,
+    ALLOW_NEUROCOERCION,
+    CONSENT_REVOCABLE_AT_WILL,
+    MAX_INNER_STATE_SCORE,
+    NEURORIGHTS_INVARIANTS_SOURCE,
     NEURORIGHTS_POLICY_ANCHOR,
     NEURORIGHTS_POLICY_ID,
     NEURORIGHTS_POLICY_VERSION,
+    POLICY_COMPATIBILITY,
+    ROH_CEILING,
+    ROH_CEILING_MILLI,
 };
@@ -1,8 +1,44 @@
+use crate::PolicyMigration;
+
 #[derive(Clone, Debug)]
 pub struct NeurorightsProfile {
     pub id: String,
     pub version: String,
     pub anchor: String,
+    pub consent: NeurorightsConsent,
+}
+
+/// Consent the host has granted for this envelope; every flag is required.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NeurorightsConsent {
+    pub cognitive_liberty: bool,
+    pub mental_privacy: bool,
+    pub mental_integrity: bool,
+    pub augmentation_continuity: bool,
+}
+
+impl NeurorightsConsent {
+    pub const fn granted() -> Self {
+        Self {
+            cognitive_liberty: true,
+            mental_privacy: true,
+            mental_integrity: true,
+            augmentation_continuity: true,
+        }
+    }
+
+    /// Name of the first required flag that is not granted.
+    pub fn first_missing(&self) -> Option<&'static str> {
+        [
+            ("cognitive_liberty", self.cognitive_liberty),
+            ("mental_privacy", self.mental_privacy),
+            ("mental_integrity", self.mental_integrity),
+            ("augmentation_continuity", self.augmentation_continuity),
+        ]
+        .into_iter()
+        .find(|(_, granted)| !granted)
+        .map(|(flag, _)| flag)
+    }
 }
 
 impl NeurorightsProfile {
@@ -11,6 +47,46 @@ impl NeurorightsProfile {
             id: super::NEURORIGHTS_POLICY_ID.to_string(),
             version: super::NEURORIGHTS_POLICY_VERSION.to_string(),
             anchor: super::NEURORIGHTS_POLICY_ANCHOR.to_string(),
+            consent: NeurorightsConsent::granted(),
         }
     }
+
+    /// The compiled citizen profile under a caller-supplied anchor;
+    /// `NeurorightsBound::try_bind` rejects it unless the anchor matches.
+    pub fn citizen_v1(anchor: impl Into<String>) -> Self {
+        Self {
+            anchor: anchor.into(),
+            ..Self::current()
+        }
+    }
+}
+
+/// Payloads that carry a `NeurorightsProfile` and can be bound with
+/// `NeurorightsBound::try_bind`.
+pub trait HasNeurorightsProfile {
+    fn neurorights_profile(&self) -> &NeurorightsProfile;
+    fn set_neurorights_profile(&mut self, profile: NeurorightsProfile);
+    /// Append `migration` to the payload's provenance.
+    fn record_policy_migration(&mut self, migration: &PolicyMigration);
+}
+
+/// Provenance fields that can record a policy migration; used by
+/// `#[derive(NeurorightsEnvelope)]`.
+pub trait ProvenanceLog {
+    fn record_migration(&mut self, migration: &PolicyMigration);
+}
+
+impl ProvenanceLog for String {
+    fn record_migration(&mut self, migration: &PolicyMigration) {
+        if !self.is_empty() {
+            self.push(';');
+        }
+        self.push_str(&migration.to_string());
+    }
+}
+
+impl ProvenanceLog for Vec<String> {
+    fn record_migration(&mut self, migration: &PolicyMigration) {
+        self.push(migration.to_string());
+    }
 }
@@ -0,0 +1,556 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::handlers::{enforce_permissions, envelope_hash, GovernanceError, WebsiteAction, WebsiteGovEnvelope};
+use crate::roles::{role_for_stake, GovernanceRole};
+use crate::voting::{ActionClass, Ballot, DecisionOutcome, DecisionRecord, VoteChoice, VotingError};
+
+/// Council approval quorum for one kind of change (governance.chat.website.v1 `quorum`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuorumRule {
+    /// Fraction of seated council members that must approve, in thousandths.
+    pub council_approval_milli: u32,
+    /// Fraction of seated council members that must vote at all, in thousandths.
+    pub participation_milli: u32,
+    /// Publishing must be done by a superchair once the quorum is met.
+    pub superchair_required: bool,
+    /// How long a superchair may veto a passed vote after it closes.
+    pub veto_window_secs: u64,
+}
+
+impl QuorumRule {
+    /// Approvals needed out of `council_seats`, rounded up and never below one.
+    pub fn required_approvals(&self, council_seats: u32) -> u32 {
+        seats_for(self.council_approval_milli, council_seats)
+    }
+
+    /// Votes (of any choice) needed out of `council_seats`, rounded up and never below one.
+    pub fn required_participation(&self, council_seats: u32) -> u32 {
+        seats_for(self.participation_milli, council_seats)
+    }
+}
+
+fn seats_for(milli: u32, council_seats: u32) -> u32 {
+    let needed = (u64::from(milli) * u64::from(council_seats)).div_ceil(1000);
+    (needed as u32).max(1)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalKind {
+    Page,
+    Section,
+}
+
+impl ProposalKind {
+    /// The council decision that publishes this kind of proposal.
+    pub fn action_class(&self) -> ActionClass {
+        match self {
+            ProposalKind::Page => ActionClass::PagePublish,
+            ProposalKind::Section => ActionClass::SectionPublish,
+        }
+    }
+
+    pub fn quorum(&self) -> QuorumRule {
+        self.action_class().quorum()
+    }
+
+    fn review_action(&self) -> WebsiteAction {
+        match self {
+            ProposalKind::Page => WebsiteAction::ReviewPage,
+            ProposalKind::Section => WebsiteAction::ReviewSection,
+        }
+    }
+
+    fn publish_action(&self) -> WebsiteAction {
+        match self {
+            ProposalKind::Page => WebsiteAction::PublishPage,
+            ProposalKind::Section => WebsiteAction::PublishSection,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalState {
+    Draft,
+    UnderReview,
+    Approved,
+    /// Approval became unreachable from the seats that had not voted.
+    Rejected,
+    Published,
+    Vetoed,
+    RolledBack,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalTransition {
+    /// Draft -> UnderReview, by the proposer only; opens the council ballot.
+    Submit,
+    /// A council vote while UnderReview (see `ProposalStore::review`). Once
+    /// the ballot is settled it closes and moves to Approved or Rejected.
+    Review { choice: VoteChoice },
+    /// Approved -> Published, once the ballot's veto window has ended.
+    Publish,
+    /// Approved -> Vetoed, by a superchair inside the veto window.
+    Veto,
+    /// Published -> RolledBack.
+    Rollback,
+}
+
+impl ProposalTransition {
+    fn action(&self, kind: ProposalKind) -> WebsiteAction {
+        match self {
+            ProposalTransition::Submit => WebsiteAction::ProposePage,
+            ProposalTransition::Review { .. } => kind.review_action(),
+            ProposalTransition::Publish => kind.publish_action(),
+            ProposalTransition::Veto => WebsiteAction::VetoChange,
+            // Undoing a published page or section takes the same authority as publishing it.
+            ProposalTransition::Rollback => kind.publish_action(),
+        }
+    }
+}
+
+/// One append-only history entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposalEvent {
+    pub seq: u64,
+    pub at_unix: u64,
+    pub actor_did: String,
+    pub role: GovernanceRole,
+    pub transition: ProposalTransition,
+    pub from: ProposalState,
+    pub to: ProposalState,
+    /// Hash of the `WebsiteGovEnvelope` that carried the transition.
+    pub envelope_hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Proposal {
+    pub id: String,
+    pub kind: ProposalKind,
+    pub page_id: String,
+    pub section_id: Option<String>,
+    pub proposer_did: String,
+    pub state: ProposalState,
+    /// Council ballot, opened on submit.
+    pub ballot: Option<Ballot>,
+    /// Final council decision once the ballot is rejected, published or vetoed.
+    pub decision: Option<DecisionRecord>,
+    pub history: Vec<ProposalEvent>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProposalError {
+    #[error("proposal {0} not found")]
+    NotFound(String),
+
+    #[error("proposal {0} already exists")]
+    AlreadyExists(String),
+
+    #[error("cannot {transition:?} a proposal that is {from:?}")]
+    IllegalTransition { from: ProposalState, transition: ProposalTransition },
+
+    #[error("only the proposer can submit; {did} is not {proposer_did}")]
+    NotProposer { did: String, proposer_did: String },
+
+    #[error("envelope is for page {found}, expected {expected}")]
+    WrongPage { expected: String, found: String },
+
+    #[error("envelope carries {found:?}, expected {expected:?}")]
+    WrongAction { expected: WebsiteAction, found: WebsiteAction },
+
+    #[error("quorum requires a superchair to publish")]
+    SuperchairRequired,
+
+    #[error("council decision is {0:?}, not approved")]
+    NotApproved(DecisionOutcome),
+
+    #[error(transparent)]
+    Governance(#[from] GovernanceError),
+
+    #[error(transparent)]
+    Voting(#[from] VotingError),
+
+    #[error("proposal store i/o: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("proposal store json: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Page and section proposals, optionally persisted as JSON.
+pub struct ProposalStore {
+    path: Option<PathBuf>,
+    council_seats: u32,
+    proposals: BTreeMap<String, Proposal>,
+}
+
+impl ProposalStore {
+    /// `council_seats` is the size of the council the ALN quorum fractions apply to.
+    pub fn open(path: Option<PathBuf>, council_seats: u32) -> Result<Self, ProposalError> {
+        let proposals = match &path {
+            Some(p) => match fs::read_to_string(p) {
+                Ok(raw) => serde_json::from_str(&raw)?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+                Err(e) => return Err(e.into()),
+            },
+            None => BTreeMap::new(),
+        };
+        Ok(Self { path, council_seats, proposals })
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Proposal> {
+        self.proposals.get(id)
+    }
+
+    /// Open a draft for the page and section named in `env`, which must
+    /// carry `ProposePage` from a role holding that permission.
+    pub fn create(&mut self, id: &str, kind: ProposalKind, env: &WebsiteGovEnvelope) -> Result<&Proposal, ProposalError> {
+        if self.proposals.contains_key(id) {
+            return Err(ProposalError::AlreadyExists(id.to_string()));
+        }
+        Self::role_for(env, WebsiteAction::ProposePage)?;
+        let args = &env.payload().args;
+
+        self.proposals.insert(
+            id.to_string(),
+            Proposal {
+                id: id.to_string(),
+                kind,
+                page_id: args.page_id.clone(),
+                section_id: args.section_id.clone(),
+                proposer_did: args.identity.userdid.clone(),
+                state: ProposalState::Draft,
+                ballot: None,
+                decision: None,
+                history: Vec::new(),
+            },
+        );
+        self.save()?;
+        Ok(&self.proposals[id])
+    }
+
+    /// Apply `transition` as carried by `env`, append it to the history and
+    /// persist. The envelope must be for this proposal's page and carry the
+    /// transition's action; the actor and role come from its args. Reviews
+    /// go through [`ProposalStore::review`].
+    pub fn apply(
+        &mut self,
+        id: &str,
+        transition: ProposalTransition,
+        env: &WebsiteGovEnvelope,
+        now_unix: u64,
+    ) -> Result<&Proposal, ProposalError> {
+        let council_seats = self.council_seats;
+        let proposal = self
+            .proposals
+            .get_mut(id)
+            .ok_or_else(|| ProposalError::NotFound(id.to_string()))?;
+        check_page(proposal, env)?;
+        let role = Self::role_for(env, transition.action(proposal.kind))?;
+        let did = &env.payload().args.identity.userdid;
+
+        use ProposalState::*;
+        let from = proposal.state;
+        let to = match (&transition, from) {
+            (ProposalTransition::Submit, Draft) => {
+                if *did != proposal.proposer_did {
+                    return Err(ProposalError::NotProposer {
+                        did: did.clone(),
+                        proposer_did: proposal.proposer_did.clone(),
+                    });
+                }
+                proposal.ballot = Some(Ballot::open(proposal.kind.action_class(), id, council_seats));
+                UnderReview
+            }
+            (ProposalTransition::Publish, Approved) => {
+                if proposal.kind.quorum().superchair_required && role != GovernanceRole::Superchair {
+                    return Err(ProposalError::SuperchairRequired);
+                }
+                let decision = ballot_mut(proposal, &transition)?.decide(now_unix)?;
+                if decision.outcome != DecisionOutcome::Approved {
+                    return Err(ProposalError::NotApproved(decision.outcome));
+                }
+                proposal.decision = Some(decision);
+                Published
+            }
+            (ProposalTransition::Veto, Approved) => {
+                let ballot = ballot_mut(proposal, &transition)?;
+                ballot.veto(env, now_unix)?;
+                proposal.decision = Some(ballot.decide(now_unix)?);
+                Vetoed
+            }
+            (ProposalTransition::Rollback, Published) => RolledBack,
+            _ => return Err(ProposalError::IllegalTransition { from, transition }),
+        };
+
+        record(proposal, transition, did, role, to, now_unix, envelope_hash(env));
+        self.save()?;
+        Ok(&self.proposals[id])
+    }
+
+    /// Count a council vote carried by `env` while the proposal is under
+    /// review. Once the ballot is settled it closes, and the proposal moves
+    /// to Approved (entering the veto window) or Rejected.
+    pub fn review(
+        &mut self,
+        id: &str,
+        env: &WebsiteGovEnvelope,
+        choice: VoteChoice,
+        now_unix: u64,
+    ) -> Result<&Proposal, ProposalError> {
+        let proposal = self
+            .proposals
+            .get_mut(id)
+            .ok_or_else(|| ProposalError::NotFound(id.to_string()))?;
+        let transition = ProposalTransition::Review { choice };
+        if proposal.state != ProposalState::UnderReview {
+            return Err(ProposalError::IllegalTransition { from: proposal.state, transition });
+        }
+        check_page(proposal, env)?;
+
+        let ballot = ballot_mut(proposal, &transition)?;
+        let vote = ballot.cast(env, choice, now_unix)?;
+        let (voter_did, role, envelope_hash) = (vote.voter_did.clone(), vote.role, vote.envelope_hash.clone());
+        let to = if !ballot.settled() {
+            ProposalState::UnderReview
+        } else {
+            ballot.close(now_unix)?;
+            if ballot.passed() {
+                ProposalState::Approved
+            } else {
+                proposal.decision = Some(ballot.decide(now_unix)?);
+                ProposalState::Rejected
+            }
+        };
+
+        record(proposal, transition, &voter_did, role, to, now_unix, envelope_hash);
+        self.save()?;
+        Ok(&self.proposals[id])
+    }
+
+    /// Role of the envelope's caller, which must carry `action` and hold its permission.
+    fn role_for(env: &WebsiteGovEnvelope, action: WebsiteAction) -> Result<GovernanceRole, ProposalError> {
+        let args = &env.payload().args;
+        if args.action != action {
+            return Err(ProposalError::WrongAction { expected: action, found: args.action });
+        }
+        let role = role_for_stake(args.chat_stake_decimal).ok_or(GovernanceError::InsufficientStake)?;
+        enforce_permissions(role, &action)?;
+        Ok(role)
+    }
+
+    fn save(&self) -> Result<(), ProposalError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&self.proposals)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+fn check_page(proposal: &Proposal, env: &WebsiteGovEnvelope) -> Result<(), ProposalError> {
+    let page_id = &env.payload().args.page_id;
+    if *page_id != proposal.page_id {
+        return Err(ProposalError::WrongPage { expected: proposal.page_id.clone(), found: page_id.clone() });
+    }
+    Ok(())
+}
+
+/// The ballot opened on submit; every state past Draft has one.
+fn ballot_mut<'a>(proposal: &'a mut Proposal, transition: &ProposalTransition) -> Result<&'a mut Ballot, ProposalError> {
+    let from = proposal.state;
+    proposal
+        .ballot
+        .as_mut()
+        .ok_or_else(|| ProposalError::IllegalTransition { from, transition: transition.clone() })
+}
+
+fn record(
+    proposal: &mut Proposal,
+    transition: ProposalTransition,
+    actor_did: &str,
+    role: GovernanceRole,
+    to: ProposalState,
+    at_unix: u64,
+    envelope_hash: String,
+) {
+    let from = proposal.state;
+    proposal.state = to;
+    proposal.history.push(ProposalEvent {
+        seq: proposal.history.len() as u64,
+        at_unix,
+        actor_did: actor_did.to_string(),
+        role,
+        transition,
+        from,
+        to,
+        envelope_hash,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::amount::ChatAmount;
+    use crate::handlers::testing::{bound, bound_section, Actor};
+    use crate::stake_thresholds;
+
+    fn actor(did: &str, chat_stake: ChatAmount) -> Actor {
+        Actor { did: did.to_string(), chat_stake }
+    }
+
+    fn council(n: u32) -> Vec<Actor> {
+        (0..n).map(|i| actor(&format!("did:example:c{i}"), stake_thresholds::COUNCIL_MIN)).collect()
+    }
+
+    #[test]
+    fn page_moves_through_review_and_veto_window_to_publish() {
+        let proposer = actor("did:example:p", stake_thresholds::STAKEHOLDER_MIN);
+        let chair = actor("did:example:chair", stake_thresholds::SUPERCHAIR_MIN);
+        let council = council(3);
+        let vote = |member: &Actor| bound(member, WebsiteAction::ReviewPage, "home");
+        let publish = bound(&chair, WebsiteAction::PublishPage, "home");
+
+        let mut store = ProposalStore::open(None, 4).unwrap();
+        store.create("p1", ProposalKind::Page, &bound(&proposer, WebsiteAction::ProposePage, "home")).unwrap();
+        assert!(matches!(
+            store.apply("p1", ProposalTransition::Publish, &publish, 0),
+            Err(ProposalError::IllegalTransition { from: ProposalState::Draft, .. })
+        ));
+        let submit = bound(&proposer, WebsiteAction::ProposePage, "home");
+        assert!(matches!(
+            store.apply("p1", ProposalTransition::Submit, &bound(&proposer, WebsiteAction::ProposePage, "about"), 1),
+            Err(ProposalError::WrongPage { .. })
+        ));
+        store.apply("p1", ProposalTransition::Submit, &submit, 1).unwrap();
+        assert!(matches!(
+            store.review("p1", &vote(&proposer), VoteChoice::Approve, 2),
+            Err(ProposalError::Voting(VotingError::Governance(GovernanceError::PermissionDenied { .. })))
+        ));
+
+        let required = ProposalKind::Page.quorum().required_approvals(4) as usize;
+        for member in council.iter().take(required) {
+            assert_eq!(store.get("p1").unwrap().state, ProposalState::UnderReview);
+            store.review("p1", &vote(member), VoteChoice::Approve, 3).unwrap();
+        }
+        assert!(matches!(
+            store.review("p1", &vote(&council[0]), VoteChoice::Approve, 4),
+            Err(ProposalError::IllegalTransition { from: ProposalState::Approved, .. })
+        ));
+
+        let window = ProposalKind::Page.quorum().veto_window_secs;
+        assert!(matches!(
+            store.apply("p1", ProposalTransition::Publish, &publish, 4),
+            Err(ProposalError::Voting(VotingError::VetoWindowOpen { .. }))
+        ));
+        assert!(matches!(
+            store.apply("p1", ProposalTransition::Publish, &bound(&chair, WebsiteAction::VetoChange, "home"), 3 + window),
+            Err(ProposalError::WrongAction { .. })
+        ));
+        let published = store.apply("p1", ProposalTransition::Publish, &publish, 3 + window).unwrap();
+        assert_eq!(published.state, ProposalState::Published);
+        assert_eq!(published.decision.as_ref().unwrap().outcome, DecisionOutcome::Approved);
+        assert_eq!(published.history.len(), 2 + required);
+        let last = published.history.last().unwrap();
+        assert_eq!(last.from, ProposalState::Approved);
+        assert_eq!((last.actor_did.as_str(), last.envelope_hash.clone()), ("did:example:chair", envelope_hash(&publish)));
+    }
+
+    #[test]
+    fn met_participation_keeps_a_reachable_page_under_review() {
+        let council = council(5);
+        let vote = |member: &Actor| bound(member, WebsiteAction::ReviewPage, "home");
+        let proposer = actor("did:example:p", stake_thresholds::STAKEHOLDER_MIN);
+        let propose = bound(&proposer, WebsiteAction::ProposePage, "home");
+
+        let mut store = ProposalStore::open(None, 5).unwrap();
+        store.create("p1", ProposalKind::Page, &propose).unwrap();
+        store.apply("p1", ProposalTransition::Submit, &propose, 1).unwrap();
+        for (member, choice) in council.iter().zip([VoteChoice::Approve, VoteChoice::Reject, VoteChoice::Approve]) {
+            store.review("p1", &vote(member), choice, 2).unwrap();
+        }
+        assert_eq!(store.get("p1").unwrap().state, ProposalState::UnderReview);
+        let approved = store.review("p1", &vote(&council[3]), VoteChoice::Approve, 3).unwrap();
+        assert_eq!(approved.state, ProposalState::Approved);
+    }
+
+    #[test]
+    fn rejection_veto_and_rollback_survive_reopen() {
+        let proposer = actor("did:example:p", stake_thresholds::STAKEHOLDER_MIN);
+        let other = actor("did:example:q", stake_thresholds::STAKEHOLDER_MIN);
+        let chair = actor("did:example:chair", stake_thresholds::SUPERCHAIR_MIN);
+        let council = council(4);
+        let vote = |member: &Actor| bound(member, WebsiteAction::ReviewSection, "home");
+        let propose = |member: &Actor| bound_section(member, WebsiteAction::ProposePage, "home", Some("hero"));
+        let veto = bound(&chair, WebsiteAction::VetoChange, "home");
+        let publish = |member: &Actor| bound(member, WebsiteAction::PublishSection, "home");
+        let window = ProposalKind::Section.quorum().veto_window_secs;
+
+        let path = std::env::temp_dir().join(format!("wg-proposals-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut store = ProposalStore::open(Some(path.clone()), 4).unwrap();
+        for id in ["rejected", "vetoed", "rolled_back"] {
+            let created = store.create(id, ProposalKind::Section, &propose(&proposer)).unwrap();
+            assert_eq!(created.section_id.as_deref(), Some("hero"));
+            assert!(matches!(
+                store.apply(id, ProposalTransition::Submit, &propose(&other), 1),
+                Err(ProposalError::NotProposer { .. })
+            ));
+            store.apply(id, ProposalTransition::Submit, &propose(&proposer), 1).unwrap();
+        }
+
+        // Section quorum on four seats needs three approvals: two rejections make it unreachable.
+        store.review("rejected", &vote(&council[0]), VoteChoice::Approve, 2).unwrap();
+        store.review("rejected", &vote(&council[1]), VoteChoice::Reject, 2).unwrap();
+        assert_eq!(store.get("rejected").unwrap().state, ProposalState::UnderReview);
+        let rejected = store.review("rejected", &vote(&council[2]), VoteChoice::Reject, 2).unwrap();
+        assert_eq!(rejected.state, ProposalState::Rejected);
+        assert_eq!(rejected.decision.as_ref().unwrap().outcome, DecisionOutcome::Rejected);
+
+        for id in ["vetoed", "rolled_back"] {
+            for member in &council[..3] {
+                store.review(id, &vote(member), VoteChoice::Approve, 3).unwrap();
+            }
+        }
+        assert!(matches!(
+            store.apply("vetoed", ProposalTransition::Veto, &bound(&council[0], WebsiteAction::VetoChange, "home"), 4),
+            Err(ProposalError::Governance(GovernanceError::PermissionDenied { .. }))
+        ));
+        assert!(matches!(
+            store.apply("vetoed", ProposalTransition::Veto, &veto, 3 + window),
+            Err(ProposalError::Voting(VotingError::VetoWindowClosed { .. }))
+        ));
+        store.apply("vetoed", ProposalTransition::Veto, &veto, 4).unwrap();
+
+        store.apply("rolled_back", ProposalTransition::Publish, &publish(&chair), 3 + window).unwrap();
+        assert!(matches!(
+            store.apply("rolled_back", ProposalTransition::Rollback, &publish(&council[0]), 5 + window),
+            Err(ProposalError::Governance(GovernanceError::PermissionDenied { action, .. }))
+                if action == "website.section.publish"
+        ));
+        store.apply("rolled_back", ProposalTransition::Rollback, &publish(&chair), 5 + window).unwrap();
+
+        let reopened = ProposalStore::open(Some(path.clone()), 4).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(reopened.get("rejected").unwrap().state, ProposalState::Rejected);
+        let vetoed = reopened.get("vetoed").unwrap();
+        assert_eq!(vetoed.state, ProposalState::Vetoed);
+        assert!(matches!(
+            &vetoed.decision.as_ref().unwrap().outcome,
+            DecisionOutcome::Vetoed { at_unix: 4, envelope_hash, .. } if *envelope_hash == super::envelope_hash(&veto)
+        ));
+        assert_eq!(vetoed.history.last().unwrap().envelope_hash, envelope_hash(&veto));
+        let rolled_back = reopened.get("rolled_back").unwrap();
+        assert_eq!(rolled_back.state, ProposalState::RolledBack);
+        assert_eq!(rolled_back, store.get("rolled_back").unwrap());
+        assert_eq!(rolled_back.history.last().unwrap().from, ProposalState::Published);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{NeurorightsProfile, NeurorightsViolation};
+use std::fmt;
+
+/// What binding does with a profile stamped with a given policy version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compatibility {
+    /// Bind unchanged.
+    Accept,
+    /// Migrate the profile to the current version, then bind.
+    AcceptWithUpgrade,
+    /// Refuse with `NeurorightsViolation::PolicyVersionRejected`.
+    Reject,
+}
+
+/// One `[[compatibility]]` entry from `neurorights.toml`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolicyVersion {
+    pub version: &'static str,
+    pub compatibility: Compatibility,
+    pub note: &'static str,
+}
+
+/// A profile moved forward to the current policy version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyMigration {
+    pub policy_id: String,
+    pub from_version: String,
+    pub to_version: &'static str,
+}
+
+impl fmt::Display for PolicyMigration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "neurorights-policy-upgrade:{}:{}->{}",
+            self.policy_id, self.from_version, self.to_version
+        )
+    }
+}
+
+/// Supported policy versions: the compiled one plus `POLICY_COMPATIBILITY`.
+#[derive(Clone, Copy, Debug)]
+pub struct PolicyRegistry {
+    current_version: &'static str,
+    older: &'static [PolicyVersion],
+}
+
+impl PolicyRegistry {
+    pub const fn compiled() -> Self {
+        Self {
+            current_version: crate::NEURORIGHTS_POLICY_VERSION,
+            older: crate::POLICY_COMPATIBILITY,
+        }
+    }
+
+    pub fn current_version(&self) -> &'static str {
+        self.current_version
+    }
+
+    /// The rule for `version`, or `None` when it is not supported at all.
+    pub fn compatibility(&self, version: &str) -> Option<Compatibility> {
+        if version == self.current_version {
+            return Some(Compatibility::Accept);
+        }
+        self.older
+            .iter()
+            .find(|v| v.version == version)
+            .map(|v| v.compatibility)
+    }
+
+    /// Bring `profile` to a version binding accepts. Returns the migration
+    /// when one was applied; the caller records it in provenance.
+    ///
+    /// The anchor is only rewritten when it is the canonical anchor of the
+    /// old version, so a foreign anchor still fails the later anchor check.
+    pub fn negotiate(
+        &self,
+        profile: &mut NeurorightsProfile,
+    ) -> Result<Option<PolicyMigration>, NeurorightsViolation> {
+        if profile.id != crate::NEURORIGHTS_POLICY_ID {
+            return Err(NeurorightsViolation::ProfileIdMismatch {
+                expected: crate::NEURORIGHTS_POLICY_ID,
+                found: profile.id.clone(),
+            });
+        }
+
+        match self.compatibility(&profile.version) {
+            Some(Compatibility::Accept) => Ok(None),
+            Some(Compatibility::AcceptWithUpgrade) => {
+                let migration = PolicyMigration {
+                    policy_id: profile.id.clone(),
+                    from_version: profile.version.clone(),
+                    to_version: self.current_version,
+                };
+                if profile.anchor == format!("did:aln:{}:{}", profile.id, profile.version) {
+                    profile.anchor = crate::NEURORIGHTS_POLICY_ANCHOR.to_string();
+                }
+                profile.version = self.current_version.to_string();
+                Ok(Some(migration))
+            }
+            Some(Compatibility::Reject) => Err(NeurorightsViolation::PolicyVersionRejected {
+                found: profile.version.clone(),
+            }),
+            None => Err(NeurorightsViolation::ProfileVersionMismatch {
+                expected: self.current_version,
+                found: profile.version.clone(),
+            }),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::handlers::WebsiteAction;
+use crate::roles::GovernanceRole;
 use crate::{
+    risk_weights,
     DEFAULT_CYBOSTATE_FACTOR,
     DEFAULT_KNOWLEDGE_FACTOR,
     DEFAULT_RISK_OF_HARM,
@@ -38,8 +41,79 @@ impl RiskEnvelope {
     }
 }
 
+/// How much of the site an action touches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RiskScope {
+    Section,
+    Page,
+    Sitewide,
+}
+
+impl RiskScope {
+    pub fn for_action(action: WebsiteAction, section_id: Option<&str>) -> Self {
+        match action {
+            WebsiteAction::UpdateSitePolicy | WebsiteAction::RollbackSitePolicy => RiskScope::Sitewide,
+            _ if section_id.is_some() => RiskScope::Section,
+            _ => RiskScope::Page,
+        }
+    }
+}
+
+/// Inputs to the per-action risk-of-harm estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RiskFactors {
+    pub action: WebsiteAction,
+    pub scope: RiskScope,
+    pub role: GovernanceRole,
+    /// Site-policy rollbacks within `risk_weights::ROLLBACK_CHURN_WINDOW_SECS`;
+    /// churn raises risk.
+    pub recent_rollbacks: u32,
+}
+
+impl RiskFactors {
+    /// ALN default RoH plus the ALN `risk_weights` for action, scope, role
+    /// and rollback churn, clamped to [0, 1]. Churn-exempt actions (rollback
+    /// itself) ignore `recent_rollbacks`.
+    pub fn estimate_risk_of_harm(&self) -> f64 {
+        let churn = if risk_weights::churn_exempt(self.action) {
+            0.0
+        } else {
+            f64::from(self.recent_rollbacks) * risk_weights::ROLLBACK_CHURN_PER_ROLLBACK
+        };
+        let weights =
+            risk_weights::action(self.action) + risk_weights::scope(self.scope) + risk_weights::role(self.role);
+        (DEFAULT_RISK_OF_HARM + weights + churn).clamp(0.0, 1.0)
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum RiskError {
     #[error("risk-of-harm {roh} exceeds ceiling {ceiling}")]
     RiskOfHarmExceeded { roh: f64, ceiling: f64 },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimate_grows_with_scope_and_rollback_churn() {
+        let factors = |action, recent_rollbacks| RiskFactors {
+            action,
+            scope: RiskScope::for_action(action, None),
+            role: GovernanceRole::Superchair,
+            recent_rollbacks,
+        };
+        let review = factors(WebsiteAction::ReviewPage, 0).estimate_risk_of_harm();
+        let update = factors(WebsiteAction::UpdateSitePolicy, 0).estimate_risk_of_harm();
+        assert!(review < update && update <= RISK_OF_HARM_CEILING);
+
+        let churned = factors(WebsiteAction::UpdateSitePolicy, 4).estimate_risk_of_harm();
+        assert!(churned > RISK_OF_HARM_CEILING);
+
+        // Churn never blocks the rollback that would end it.
+        let rollback = factors(WebsiteAction::RollbackSitePolicy, 0).estimate_risk_of_harm();
+        assert_eq!(factors(WebsiteAction::RollbackSitePolicy, 10).estimate_risk_of_harm(), rollback);
+        assert!(rollback <= RISK_OF_HARM_CEILING);
+    }
+}
@@ -1,3 +1,4 @@
+/// Not re-exported, so no type outside `neurorights-core` can implement it.
 pub trait NeurorightsMarkerSealed {}
 
 /// Public trait exposed to the rest of Cyber-Retrieval.
@@ -0,0 +1,400 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::handlers::{enforce_permissions, GovernanceDecision, GovernanceDecisionRecord, GovernanceError, WebsiteAction};
+use crate::risk::RiskError;
+use crate::voting::{ActionClass, DecisionOutcome, DecisionRecord};
+
+/// Content address of a policy document: `sha256:` + hex of its canonical JSON.
+pub fn content_hash(document: &Value) -> String {
+    // serde_json maps are key-sorted, so this serialization is canonical.
+    let canonical = serde_json::to_vec(document).unwrap_or_default();
+    format!("sha256:{}", hex::encode(Sha256::digest(canonical)))
+}
+
+/// What authorized a change: the decision plus its risk envelope hexstamp,
+/// and for updates the council vote that passed it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyAuthorization {
+    pub decision: GovernanceDecisionRecord,
+    pub risk_hexstamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub council: Option<DecisionRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SitePolicyVersion {
+    pub version: String,
+    pub content_hash: String,
+    /// Version that was current when this one was published.
+    pub parent: Option<String>,
+    pub created_at_utc: String,
+    pub authorization: PolicyAuthorization,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyChangeKind {
+    Update { version: String },
+    Rollback { from: Option<String>, to: String },
+}
+
+/// One append-only entry in the change log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyChange {
+    pub seq: u64,
+    pub kind: PolicyChangeKind,
+    pub at_utc: String,
+    pub authorization: PolicyAuthorization,
+}
+
+/// One leaf-level difference between two policy documents.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyDiff {
+    Added { path: String, new: Value },
+    Removed { path: String, old: Value },
+    Changed { path: String, old: Value, new: Value },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SitePolicyError {
+    #[error("site policy version {0} not found")]
+    UnknownVersion(String),
+
+    #[error("site policy version {0} already exists")]
+    VersionExists(String),
+
+    #[error("site policy version {0} is already current")]
+    AlreadyCurrent(String),
+
+    #[error("decision was for {found:?}, expected {expected:?}")]
+    WrongAction { expected: WebsiteAction, found: WebsiteAction },
+
+    #[error("decision did not allow the change")]
+    NotAllowed,
+
+    #[error("decision has no risk envelope hexstamp")]
+    MissingHexstamp,
+
+    #[error("{action:?} requires an approved {class:?} council decision")]
+    MissingCouncilDecision { action: WebsiteAction, class: ActionClass },
+
+    #[error("council decision is for {class:?} {subject_id:?}, expected {expected_class:?} {expected:?}")]
+    WrongCouncilDecision { class: ActionClass, subject_id: String, expected_class: ActionClass, expected: String },
+
+    #[error("invalid RFC 3339 timestamp {0:?}")]
+    InvalidTimestamp(String),
+
+    #[error("council decision on {subject_id:?} is {outcome:?}, not approved")]
+    CouncilNotApproved { subject_id: String, outcome: DecisionOutcome },
+
+    #[error(transparent)]
+    Governance(#[from] GovernanceError),
+
+    #[error(transparent)]
+    Risk(#[from] RiskError),
+
+    #[error("site policy store i/o: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("site policy store json: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreState {
+    documents: BTreeMap<String, Value>,
+    versions: BTreeMap<String, SitePolicyVersion>,
+    current: Option<String>,
+    changes: Vec<PolicyChange>,
+}
+
+/// Versioned site policy: content-addressed documents, named versions, a
+/// current pointer and an append-only change log, optionally persisted as JSON.
+pub struct SitePolicyStore {
+    path: Option<PathBuf>,
+    state: StoreState,
+}
+
+impl SitePolicyStore {
+    pub fn open(path: Option<PathBuf>) -> Result<Self, SitePolicyError> {
+        let state = match &path {
+            Some(p) => match fs::read_to_string(p) {
+                Ok(raw) => serde_json::from_str(&raw)?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => StoreState::default(),
+                Err(e) => return Err(e.into()),
+            },
+            None => StoreState::default(),
+        };
+        Ok(Self { path, state })
+    }
+
+    pub fn current(&self) -> Option<&SitePolicyVersion> {
+        self.state.current.as_ref().map(|v| &self.state.versions[v])
+    }
+
+    pub fn version(&self, version: &str) -> Option<&SitePolicyVersion> {
+        self.state.versions.get(version)
+    }
+
+    pub fn document(&self, version: &str) -> Option<&Value> {
+        let v = self.state.versions.get(version)?;
+        self.state.documents.get(&v.content_hash)
+    }
+
+    pub fn changes(&self) -> &[PolicyChange] {
+        &self.state.changes
+    }
+
+    /// Rollbacks recorded at or after `since`, for the handler's risk
+    /// estimate. Times are compared as instants, so offsets and fractional
+    /// seconds do not matter; an entry whose time no longer parses counts.
+    pub fn rollbacks_since(&self, since: OffsetDateTime) -> u32 {
+        self.state
+            .changes
+            .iter()
+            .filter(|c| matches!(c.kind, PolicyChangeKind::Rollback { .. }))
+            .filter(|c| OffsetDateTime::parse(&c.at_utc, &Rfc3339).ok().is_none_or(|at| at >= since))
+            .count() as u32
+    }
+
+    /// Publish `document` as `version` and make it current (`UpdateSitePolicy`).
+    /// `council` must be the approved `SitewidePolicyUpdate` vote on `version`.
+    pub fn update(
+        &mut self,
+        version: &str,
+        document: Value,
+        decision: GovernanceDecision,
+        council: DecisionRecord,
+        at_utc: &str,
+    ) -> Result<&SitePolicyVersion, SitePolicyError> {
+        parse_utc(at_utc)?;
+        let authorization = authorize(decision, WebsiteAction::UpdateSitePolicy, version, Some(council))?;
+        if self.state.versions.contains_key(version) {
+            return Err(SitePolicyError::VersionExists(version.to_string()));
+        }
+
+        let hash = content_hash(&document);
+        self.state.documents.entry(hash.clone()).or_insert(document);
+        self.state.versions.insert(
+            version.to_string(),
+            SitePolicyVersion {
+                version: version.to_string(),
+                content_hash: hash,
+                parent: self.state.current.clone(),
+                created_at_utc: at_utc.to_string(),
+                authorization: authorization.clone(),
+            },
+        );
+        self.state.current = Some(version.to_string());
+        self.record(PolicyChangeKind::Update { version: version.to_string() }, at_utc, authorization)?;
+        Ok(&self.state.versions[version])
+    }
+
+    /// Point `current` back at an existing named version (`RollbackSitePolicy`).
+    pub fn rollback(
+        &mut self,
+        to: &str,
+        decision: GovernanceDecision,
+        at_utc: &str,
+    ) -> Result<&SitePolicyVersion, SitePolicyError> {
+        parse_utc(at_utc)?;
+        let authorization = authorize(decision, WebsiteAction::RollbackSitePolicy, to, None)?;
+        if !self.state.versions.contains_key(to) {
+            return Err(SitePolicyError::UnknownVersion(to.to_string()));
+        }
+        if self.state.current.as_deref() == Some(to) {
+            return Err(SitePolicyError::AlreadyCurrent(to.to_string()));
+        }
+
+        let from = self.state.current.replace(to.to_string());
+        self.record(PolicyChangeKind::Rollback { from, to: to.to_string() }, at_utc, authorization)?;
+        Ok(&self.state.versions[to])
+    }
+
+    /// Leaf-level differences going from version `from` to version `to`.
+    pub fn diff(&self, from: &str, to: &str) -> Result<Vec<PolicyDiff>, SitePolicyError> {
+        let old = self.document(from).ok_or_else(|| SitePolicyError::UnknownVersion(from.to_string()))?;
+        let new = self.document(to).ok_or_else(|| SitePolicyError::UnknownVersion(to.to_string()))?;
+        let mut out = Vec::new();
+        diff_values(old, new, &mut String::new(), &mut out);
+        Ok(out)
+    }
+
+    fn record(
+        &mut self,
+        kind: PolicyChangeKind,
+        at_utc: &str,
+        authorization: PolicyAuthorization,
+    ) -> Result<(), SitePolicyError> {
+        self.state.changes.push(PolicyChange {
+            seq: self.state.changes.len() as u64,
+            kind,
+            at_utc: at_utc.to_string(),
+            authorization,
+        });
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), SitePolicyError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&self.state)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+fn parse_utc(at_utc: &str) -> Result<OffsetDateTime, SitePolicyError> {
+    OffsetDateTime::parse(at_utc, &Rfc3339).map_err(|_| SitePolicyError::InvalidTimestamp(at_utc.to_string()))
+}
+
+/// Re-check a decision before it changes site policy: right action, still
+/// permitted for the role, risk within the ceiling, hexstamp present, and
+/// for voted actions the approved council decision on `subject_id`.
+fn authorize(
+    decision: GovernanceDecision,
+    expected: WebsiteAction,
+    subject_id: &str,
+    council: Option<DecisionRecord>,
+) -> Result<PolicyAuthorization, SitePolicyError> {
+    let decision = decision.into_record();
+    if !decision.allowed {
+        return Err(SitePolicyError::NotAllowed);
+    }
+    if decision.action != expected {
+        return Err(SitePolicyError::WrongAction { expected, found: decision.action });
+    }
+    enforce_permissions(decision.role, &expected)?;
+    decision.risk_envelope.validate()?;
+    let risk_hexstamp = decision.risk_envelope.hexstamp.clone();
+    if risk_hexstamp.trim().is_empty() {
+        return Err(SitePolicyError::MissingHexstamp);
+    }
+    let council = match expected.council_class() {
+        None => None,
+        Some(class) => {
+            let council = council.ok_or(SitePolicyError::MissingCouncilDecision { action: expected, class })?;
+            if council.class != class || council.subject_id != subject_id {
+                return Err(SitePolicyError::WrongCouncilDecision {
+                    class: council.class,
+                    subject_id: council.subject_id,
+                    expected_class: class,
+                    expected: subject_id.to_string(),
+                });
+            }
+            if council.outcome != DecisionOutcome::Approved {
+                return Err(SitePolicyError::CouncilNotApproved { subject_id: council.subject_id, outcome: council.outcome });
+            }
+            Some(council)
+        }
+    };
+    Ok(PolicyAuthorization { decision, risk_hexstamp, council })
+}
+
+/// Objects are compared key by key; anything else is compared as a whole.
+fn diff_values(old: &Value, new: &Value, path: &mut String, out: &mut Vec<PolicyDiff>) {
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            let len = path.len();
+            for (key, old_child) in a {
+                path.push('/');
+                path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                match b.get(key) {
+                    Some(new_child) => diff_values(old_child, new_child, path, out),
+                    None => out.push(PolicyDiff::Removed { path: path.clone(), old: old_child.clone() }),
+                }
+                path.truncate(len);
+            }
+            for (key, new_child) in b.iter().filter(|(k, _)| !a.contains_key(*k)) {
+                let added = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                out.push(PolicyDiff::Added { path: added, new: new_child.clone() });
+            }
+        }
+        _ if old != new => out.push(PolicyDiff::Changed {
+            path: path.clone(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::handle_website_governance;
+    use crate::handlers::testing::{bound, council_vote, Actor};
+    use crate::risk::RiskEnvelope;
+    use crate::stake_thresholds;
+    use serde_json::json;
+
+    fn decision(action: WebsiteAction, store: &SitePolicyStore) -> GovernanceDecision {
+        let chair = Actor { did: "did:example:chair".into(), chat_stake: stake_thresholds::SUPERCHAIR_MIN };
+        handle_website_governance(bound(&chair, action, "site"), RiskEnvelope::default("0x1a2f"), store).unwrap()
+    }
+
+    fn council(version: &str, approvals: u32) -> DecisionRecord {
+        council_vote(ActionClass::SitewidePolicyUpdate, version, approvals)
+    }
+
+    #[test]
+    fn update_diff_and_rollback_to_named_version() {
+        let mut store = SitePolicyStore::open(None).unwrap();
+        let v1 = json!({"comments": {"enabled": true}, "retention_days": 30});
+        let v2 = json!({"comments": {"enabled": false}, "banner": "beta"});
+        let update = |store: &SitePolicyStore| decision(WebsiteAction::UpdateSitePolicy, store);
+        store.update("v1", v1.clone(), update(&store), council("v1", 3), "2026-01-01T00:00:01Z").unwrap();
+        store.update("v2", v2, update(&store), council("v2", 3), "2026-01-01T00:00:02Z").unwrap();
+        let t3 = "2026-01-01T00:00:03Z";
+        let wrong_action = decision(WebsiteAction::RollbackSitePolicy, &store);
+        assert!(matches!(
+            store.update("v3", json!({}), wrong_action, council("v3", 3), t3),
+            Err(SitePolicyError::WrongAction { .. })
+        ));
+        assert!(matches!(
+            store.update("v3", json!({}), update(&store), council("v2", 3), t3),
+            Err(SitePolicyError::WrongCouncilDecision { .. })
+        ));
+        assert!(matches!(
+            store.update("v3", json!({}), update(&store), council("v3", 2), t3),
+            Err(SitePolicyError::CouncilNotApproved { outcome: DecisionOutcome::Rejected, .. })
+        ));
+        assert!(matches!(
+            store.update("v3", json!({}), update(&store), council("v3", 3), "t3"),
+            Err(SitePolicyError::InvalidTimestamp(_))
+        ));
+
+        let diff = store.diff("v1", "v2").unwrap();
+        assert_eq!(diff.len(), 3);
+        assert!(diff.contains(&PolicyDiff::Changed {
+            path: "/comments/enabled".into(),
+            old: json!(true),
+            new: json!(false)
+        }));
+        assert!(diff.contains(&PolicyDiff::Added { path: "/banner".into(), new: json!("beta") }));
+
+        let rollback = decision(WebsiteAction::RollbackSitePolicy, &store);
+        let current = store.rollback("v1", rollback, "2026-01-01T00:00:04Z").unwrap();
+        assert_eq!(current.content_hash, content_hash(&v1));
+        assert_eq!(store.current().unwrap().version, "v1");
+        assert_eq!(store.changes().len(), 3);
+        assert_eq!(store.changes()[2].authorization.risk_hexstamp, "0x1a2f");
+        let at = |s| OffsetDateTime::parse(s, &Rfc3339).unwrap();
+        assert!(store.changes()[2].authorization.council.is_none());
+        assert_eq!(store.changes()[1].authorization.council.as_ref().unwrap().subject_id, "v2");
+        // 01:00:04+01:00 is the rollback's own instant; 00:00:04.5Z is just after it.
+        assert_eq!(store.rollbacks_since(at("2026-01-01T01:00:04+01:00")), 1);
+        assert_eq!(store.rollbacks_since(at("2026-01-01T00:00:04.5Z")), 0);
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt;
+
+/// Why a payload could not be bound to the compiled neurorights envelope.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NeurorightsViolation {
+    ProfileIdMismatch { expected: &'static str, found: String },
+    ProfileVersionMismatch { expected: &'static str, found: String },
+    AnchorMismatch { expected: &'static str, found: String },
+    /// The version is known but its compatibility rule is `reject`.
+    PolicyVersionRejected { found: String },
+    /// A consent flag the doctrine requires was not granted.
+    ConsentMissing { flag: &'static str },
+    /// The consent ledger has no grant for this DID and purpose.
+    ConsentNotGranted { did: String, purpose: String },
+    /// The consent ledger records a revocation after the latest grant.
+    ConsentRevoked { did: String, purpose: String, revoked_at_utc: String },
+}
+
+impl NeurorightsViolation {
+    /// Stable machine-readable code for logs and API errors.
+    pub fn code(&self) -> &'static str {
+        match self {
+            NeurorightsViolation::ProfileIdMismatch { .. } => "neurorights_profile_id_mismatch",
+            NeurorightsViolation::ProfileVersionMismatch { .. } => {
+                "neurorights_profile_version_mismatch"
+            }
+            NeurorightsViolation::AnchorMismatch { .. } => "neurorights_anchor_mismatch",
+            NeurorightsViolation::PolicyVersionRejected { .. } => "neurorights_policy_version_rejected",
+            NeurorightsViolation::ConsentMissing { .. } => "neurorights_consent_missing",
+            NeurorightsViolation::ConsentNotGranted { .. } => "neurorights_consent_not_granted",
+            NeurorightsViolation::ConsentRevoked { .. } => "neurorights_consent_revoked",
+        }
+    }
+}
+
+impl fmt::Display for NeurorightsViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NeurorightsViolation::ProfileIdMismatch { expected, found } => {
+                write!(f, "neurorights profile id {:?}, expected {:?}", found, expected)
+            }
+            NeurorightsViolation::ProfileVersionMismatch { expected, found } => {
+                write!(f, "neurorights profile version {:?}, expected {:?}", found, expected)
+            }
+            NeurorightsViolation::AnchorMismatch { expected, found } => {
+                write!(f, "neurorights anchor {:?}, expected {:?}", found, expected)
+            }
+            NeurorightsViolation::PolicyVersionRejected { found } => {
+                write!(f, "neurorights policy version {:?} is no longer accepted", found)
+            }
+            NeurorightsViolation::ConsentMissing { flag } => {
+                write!(f, "required neurorights consent {:?} not granted", flag)
+            }
+            NeurorightsViolation::ConsentNotGranted { did, purpose } => {
+                write!(f, "no consent from {} for purpose {:?}", did, purpose)
+            }
+            NeurorightsViolation::ConsentRevoked { did, purpose, revoked_at_utc } => write!(
+                f,
+                "consent from {} for purpose {:?} revoked at {}",
+                did, purpose, revoked_at_utc
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NeurorightsViolation {}
@@ -0,0 +1,331 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::handlers::{enforce_permissions, envelope_hash, GovernanceError, WebsiteAction, WebsiteGovEnvelope};
+use crate::proposal::QuorumRule;
+use crate::quorum;
+use crate::roles::{role_for_stake, GovernanceRole};
+
+/// Kinds of council decision, each with its own quorum from the ALN shard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionClass {
+    PagePublish,
+    SectionPublish,
+    SitewidePolicyUpdate,
+}
+
+impl ActionClass {
+    pub fn quorum(&self) -> QuorumRule {
+        match self {
+            ActionClass::PagePublish => quorum::PAGE_PUBLISH,
+            ActionClass::SectionPublish => quorum::SECTION_PUBLISH,
+            ActionClass::SitewidePolicyUpdate => quorum::SITEWIDE_POLICY_UPDATE,
+        }
+    }
+
+    /// Action a vote's envelope must carry; the voter's role must hold its
+    /// permission. Site-policy votes are cast by the same page reviewers that
+    /// sit on the council.
+    pub fn vote_action(&self) -> WebsiteAction {
+        match self {
+            ActionClass::PagePublish | ActionClass::SitewidePolicyUpdate => WebsiteAction::ReviewPage,
+            ActionClass::SectionPublish => WebsiteAction::ReviewSection,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteChoice {
+    Approve,
+    Reject,
+    Abstain,
+}
+
+/// A vote as counted, tied to the neurorights-bound envelope that carried it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CastVote {
+    pub voter_did: String,
+    pub role: GovernanceRole,
+    pub choice: VoteChoice,
+    pub cast_at_unix: u64,
+    /// Hash of the `WebsiteGovEnvelope` the vote arrived in.
+    pub envelope_hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionOutcome {
+    Approved,
+    Rejected,
+    NoQuorum,
+    /// `envelope_hash` is the hash of the `VetoChange` envelope.
+    Vetoed { by_did: String, at_unix: u64, envelope_hash: String },
+}
+
+/// Final, self-contained record of a council decision.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    pub class: ActionClass,
+    pub subject_id: String,
+    pub outcome: DecisionOutcome,
+    pub council_seats: u32,
+    pub required_participation: u32,
+    pub required_approvals: u32,
+    pub approvals: u32,
+    pub rejections: u32,
+    pub abstentions: u32,
+    pub closed_at_unix: u64,
+    pub decided_at_unix: u64,
+    pub votes: Vec<CastVote>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VotingError {
+    #[error("{did} has already voted")]
+    DuplicateVote { did: String },
+
+    #[error("envelope carries {found:?}, expected {expected:?}")]
+    WrongAction { expected: WebsiteAction, found: WebsiteAction },
+
+    #[error("voting is closed")]
+    Closed,
+
+    #[error("voting is still open")]
+    StillOpen,
+
+    #[error("only a superchair can veto")]
+    NotSuperchair,
+
+    #[error("veto window ended at {until_unix}")]
+    VetoWindowClosed { until_unix: u64 },
+
+    #[error("veto window open until {until_unix}")]
+    VetoWindowOpen { until_unix: u64 },
+
+    #[error("vote did not pass; nothing to veto")]
+    NothingToVeto,
+
+    #[error(transparent)]
+    Governance(#[from] GovernanceError),
+}
+
+/// Votes on one subject (proposal, section, site policy) for one action class.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ballot {
+    pub class: ActionClass,
+    pub subject_id: String,
+    pub council_seats: u32,
+    votes: BTreeMap<String, CastVote>,
+    closed_at_unix: Option<u64>,
+    veto: Option<(String, u64, String)>,
+}
+
+impl Ballot {
+    pub fn open(class: ActionClass, subject_id: &str, council_seats: u32) -> Self {
+        Self {
+            class,
+            subject_id: subject_id.to_string(),
+            council_seats,
+            votes: BTreeMap::new(),
+            closed_at_unix: None,
+            veto: None,
+        }
+    }
+
+    /// Count one vote per DID from a role allowed to review this class. The
+    /// voter, stake and action come from the bound envelope, whose hash is
+    /// recorded with the vote.
+    pub fn cast(&mut self, env: &WebsiteGovEnvelope, choice: VoteChoice, now_unix: u64) -> Result<&CastVote, VotingError> {
+        let args = &env.payload().args;
+        if self.closed_at_unix.is_some() {
+            return Err(VotingError::Closed);
+        }
+        if args.action != self.class.vote_action() {
+            return Err(VotingError::WrongAction { expected: self.class.vote_action(), found: args.action });
+        }
+        let did = &args.identity.userdid;
+        if self.votes.contains_key(did) {
+            return Err(VotingError::DuplicateVote { did: did.clone() });
+        }
+        let role = role_for_stake(args.chat_stake_decimal).ok_or(GovernanceError::InsufficientStake)?;
+        enforce_permissions(role, &args.action)?;
+
+        let vote = CastVote {
+            voter_did: did.clone(),
+            role,
+            choice,
+            cast_at_unix: now_unix,
+            envelope_hash: envelope_hash(env),
+        };
+        Ok(self.votes.entry(did.clone()).or_insert(vote))
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed_at_unix.is_some()
+    }
+
+    /// True once further votes cannot change whether the vote passes: it
+    /// already passed, or the approvals can no longer be reached from the
+    /// seats that have not voted.
+    pub fn settled(&self) -> bool {
+        let (approvals, rejections, abstentions) = self.tally();
+        let outstanding = self.council_seats.saturating_sub(approvals + rejections + abstentions);
+        self.passed() || approvals + outstanding < self.class.quorum().required_approvals(self.council_seats)
+    }
+
+    /// Stop accepting votes; a passing vote then enters its veto window.
+    pub fn close(&mut self, now_unix: u64) -> Result<(), VotingError> {
+        if self.closed_at_unix.is_some() {
+            return Err(VotingError::Closed);
+        }
+        self.closed_at_unix = Some(now_unix);
+        Ok(())
+    }
+
+    /// Superchair veto of a passed vote, only inside the veto window. The
+    /// vetoer and stake come from the bound `VetoChange` envelope, whose hash
+    /// is recorded with the veto.
+    pub fn veto(&mut self, env: &WebsiteGovEnvelope, now_unix: u64) -> Result<(), VotingError> {
+        let args = &env.payload().args;
+        let closed_at = self.closed_at_unix.ok_or(VotingError::StillOpen)?;
+        if args.action != WebsiteAction::VetoChange {
+            return Err(VotingError::WrongAction { expected: WebsiteAction::VetoChange, found: args.action });
+        }
+        let role = role_for_stake(args.chat_stake_decimal).ok_or(GovernanceError::InsufficientStake)?;
+        if role != GovernanceRole::Superchair {
+            return Err(VotingError::NotSuperchair);
+        }
+        enforce_permissions(role, &args.action)?;
+        if !self.passed() {
+            return Err(VotingError::NothingToVeto);
+        }
+        let until_unix = closed_at + self.class.quorum().veto_window_secs;
+        if now_unix >= until_unix {
+            return Err(VotingError::VetoWindowClosed { until_unix });
+        }
+        self.veto.get_or_insert_with(|| (args.identity.userdid.clone(), now_unix, envelope_hash(env)));
+        Ok(())
+    }
+
+    /// The decision, once it can no longer change.
+    pub fn decide(&self, now_unix: u64) -> Result<DecisionRecord, VotingError> {
+        let closed_at = self.closed_at_unix.ok_or(VotingError::StillOpen)?;
+        let rule = self.class.quorum();
+        let (approvals, rejections, abstentions) = self.tally();
+
+        let outcome = if let Some((by_did, at_unix, envelope_hash)) = &self.veto {
+            DecisionOutcome::Vetoed { by_did: by_did.clone(), at_unix: *at_unix, envelope_hash: envelope_hash.clone() }
+        } else if approvals + rejections + abstentions < rule.required_participation(self.council_seats) {
+            DecisionOutcome::NoQuorum
+        } else if !self.passed() {
+            DecisionOutcome::Rejected
+        } else {
+            let until_unix = closed_at + rule.veto_window_secs;
+            if now_unix < until_unix {
+                return Err(VotingError::VetoWindowOpen { until_unix });
+            }
+            DecisionOutcome::Approved
+        };
+
+        Ok(DecisionRecord {
+            class: self.class,
+            subject_id: self.subject_id.clone(),
+            outcome,
+            council_seats: self.council_seats,
+            required_participation: rule.required_participation(self.council_seats),
+            required_approvals: rule.required_approvals(self.council_seats),
+            approvals,
+            rejections,
+            abstentions,
+            closed_at_unix: closed_at,
+            decided_at_unix: now_unix,
+            votes: self.votes.values().cloned().collect(),
+        })
+    }
+
+    /// Both the participation and the approval quorum are met.
+    pub fn passed(&self) -> bool {
+        let rule = self.class.quorum();
+        let (approvals, rejections, abstentions) = self.tally();
+        approvals + rejections + abstentions >= rule.required_participation(self.council_seats)
+            && approvals >= rule.required_approvals(self.council_seats)
+    }
+
+    fn tally(&self) -> (u32, u32, u32) {
+        let count = |choice| self.votes.values().filter(|v| v.choice == choice).count() as u32;
+        (count(VoteChoice::Approve), count(VoteChoice::Reject), count(VoteChoice::Abstain))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::testing::{bound, Actor};
+    use crate::stake_thresholds;
+
+    fn member(i: u32) -> Actor {
+        Actor { did: format!("did:example:c{i}"), chat_stake: stake_thresholds::COUNCIL_MIN }
+    }
+
+    fn vote(i: u32) -> WebsiteGovEnvelope {
+        bound(&member(i), WebsiteAction::ReviewPage, "p1")
+    }
+
+    #[test]
+    fn passed_vote_can_be_vetoed_only_inside_window() {
+        let chair = Actor { did: "did:example:chair".into(), chat_stake: stake_thresholds::SUPERCHAIR_MIN };
+        let window = ActionClass::PagePublish.quorum().veto_window_secs;
+        let seats = 5;
+        let needed = ActionClass::PagePublish.quorum().required_approvals(seats);
+
+        let mut ballot = Ballot::open(ActionClass::PagePublish, "p1", seats);
+        for i in 0..needed {
+            ballot.cast(&vote(i), VoteChoice::Approve, 10).unwrap();
+        }
+        assert!(ballot.settled());
+        assert!(matches!(ballot.cast(&vote(0), VoteChoice::Reject, 11), Err(VotingError::DuplicateVote { .. })));
+        let publish = bound(&member(9), WebsiteAction::PublishPage, "p1");
+        assert!(matches!(ballot.cast(&publish, VoteChoice::Approve, 11), Err(VotingError::WrongAction { .. })));
+        ballot.close(100).unwrap();
+        assert!(matches!(ballot.decide(100), Err(VotingError::VetoWindowOpen { .. })));
+
+        let approved = ballot.decide(100 + window).unwrap();
+        assert_eq!(approved.outcome, DecisionOutcome::Approved);
+        assert_eq!(approved.votes[0].envelope_hash, envelope_hash(&vote(0)));
+        let veto = bound(&chair, WebsiteAction::VetoChange, "p1");
+        assert!(matches!(ballot.veto(&veto, 100 + window), Err(VotingError::VetoWindowClosed { .. })));
+        let not_chair = bound(&member(0), WebsiteAction::VetoChange, "p1");
+        assert!(matches!(ballot.veto(&not_chair, 101), Err(VotingError::NotSuperchair)));
+        let publish = bound(&chair, WebsiteAction::PublishPage, "p1");
+        assert!(matches!(ballot.veto(&publish, 101), Err(VotingError::WrongAction { .. })));
+
+        ballot.veto(&veto, 101).unwrap();
+        assert_eq!(
+            ballot.decide(101).unwrap().outcome,
+            DecisionOutcome::Vetoed { by_did: "did:example:chair".into(), at_unix: 101, envelope_hash: envelope_hash(&veto) }
+        );
+
+        let mut sparse = Ballot::open(ActionClass::SitewidePolicyUpdate, "policy", 8);
+        sparse.cast(&vote(0), VoteChoice::Approve, 1).unwrap();
+        assert!(!sparse.settled());
+        sparse.close(2).unwrap();
+        assert_eq!(sparse.decide(2).unwrap().outcome, DecisionOutcome::NoQuorum);
+    }
+
+    #[test]
+    fn met_participation_does_not_settle_a_reachable_approval() {
+        let mut ballot = Ballot::open(ActionClass::PagePublish, "p1", 5);
+        for (i, choice) in [VoteChoice::Approve, VoteChoice::Reject, VoteChoice::Approve].into_iter().enumerate() {
+            ballot.cast(&vote(i as u32), choice, 10).unwrap();
+        }
+        assert!(!ballot.passed());
+        assert!(!ballot.settled());
+
+        ballot.cast(&vote(3), VoteChoice::Approve, 11).unwrap();
+        assert!(ballot.settled());
+        assert!(ballot.passed());
+    }
+}